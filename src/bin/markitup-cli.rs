@@ -1,6 +1,7 @@
 use clap::{Arg, Command};
 use markitup;
-use std::path::PathBuf;
+use markitup::generator::pdf2md::{PdfError, PdfOpenError};
+use std::path::{Path, PathBuf};
 
 fn main() {
     let matches = Command::new("markitup")
@@ -41,6 +42,25 @@ fn main() {
                 .help("Disable AI enhancement features")
                 .conflicts_with("ai-enable"),
         )
+        .arg(
+            Arg::new("password")
+                .long("password")
+                .value_name("PASSWORD")
+                .help("Password for encrypted PDFs"),
+        )
+        .arg(
+            Arg::new("pages")
+                .long("pages")
+                .value_name("RANGE")
+                .help("PDF pages to convert, e.g. 1-5,10,20-"),
+        )
+        .arg(
+            Arg::new("split-pages")
+                .long("split-pages")
+                .action(clap::ArgAction::SetTrue)
+                .help("Write one file per PDF page, named after the output file")
+                .requires("output"),
+        )
         .get_matches();
 
     let file_path = matches.get_one::<String>("input").unwrap();
@@ -48,6 +68,8 @@ fn main() {
     // 收集CLI覆盖参数
     let image_path_override = matches.get_one::<String>("image-path").map(PathBuf::from);
     let output_path_override = matches.get_one::<String>("output").map(PathBuf::from);
+    let password_override = matches.get_one::<String>("password").cloned();
+    let pages_override = matches.get_one::<String>("pages").cloned();
     let ai_enable_override = if matches.get_flag("ai-enable") {
         Some(true)
     } else if matches.get_flag("no-ai") {
@@ -61,12 +83,45 @@ fn main() {
         image_path_override,
         output_path_override,
         ai_enable_override,
+        password_override,
+        pages_override,
     );
 
     // 获取更新后的配置
     let settings = markitup::config::get_settings();
 
-    let output = markitup::convert_from_path(file_path);
+    // 每页单独输出到一个文件
+    if matches.get_flag("split-pages") {
+        let output_path = settings.output_path.as_ref().unwrap();
+        let pages = match markitup::convert_pdf_pages_from_path(file_path) {
+            Ok(pages) => pages,
+            Err(err) => {
+                eprintln!("Error: {}", pdf_error_message(err));
+                std::process::exit(1);
+            }
+        };
+        for page in pages {
+            let page_path = page_output_path(output_path, page.number);
+            match std::fs::write(&page_path, &page.markdown) {
+                Ok(_) => println!("Page {} written to: {}", page.label, page_path.display()),
+                Err(err) => {
+                    eprintln!("Error writing to file: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        return;
+    }
+
+    // PDF 走带类型的错误，以便提示缺少密码
+    let is_pdf = Path::new(file_path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"));
+    let output = if is_pdf {
+        markitup::convert_pdf_from_path(file_path).map_err(pdf_error_message)
+    } else {
+        markitup::convert_from_path(file_path)
+    };
     match output {
         Ok(markup) => {
             if let Some(output_path) = &settings.output_path {
@@ -87,3 +142,21 @@ fn main() {
         }
     }
 }
+
+// 加密 PDF 缺少密码时提示 --password
+fn pdf_error_message(err: PdfError) -> String {
+    match err {
+        PdfError::Open(PdfOpenError::PasswordRequired) => format!("{}, use --password to give it", err),
+        err => err.to_string(),
+    }
+}
+
+// out.md -> out-3.md for page 3
+fn page_output_path(output_path: &Path, number: usize) -> PathBuf {
+    let stem = output_path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match output_path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, number, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, number),
+    };
+    output_path.with_file_name(file_name)
+}
@@ -12,7 +12,7 @@
 use config::{Config, ConfigError, Environment, File, FileFormat};
 use once_cell::sync::Lazy;
 use serde::Deserialize;
-use std::{env, fs, path::PathBuf, sync::RwLock};
+use std::{collections::HashMap, env, fs, path::PathBuf, sync::RwLock};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
@@ -23,6 +23,18 @@ pub struct Settings {
     pub doubao_api_key: Option<String>,
     pub is_ai_sweep: bool,
     pub deepseek_api_key: Option<String>,
+    pub docx_include_headers_footers: bool,
+    pub docx_pandoc_flavor: String,
+    pub pptx_skip_hidden_slides: bool,
+    pub pptx_include_notes: bool,
+    pub pdf_include_headers_footers: bool,
+    pub pdf_page_marker: String,
+    pub pdf_min_image_size: f32,
+    pub pdf_table_of_contents: bool,
+    pub pdf_password: Option<String>,
+    pub pdf_pages: String,
+    #[serde(default)]
+    pub docx_style_map: HashMap<String, String>,
 }
 
 // debug print settings
@@ -36,6 +48,17 @@ fn debug_print_settings(settings: &Settings) {
         eprintln!("doubao_api_key: {:?}", settings.doubao_api_key.as_ref());
         eprintln!("is_ai_sweep: {}", settings.is_ai_sweep);
         eprintln!("deepseek_api_key: {:?}", settings.deepseek_api_key.as_ref());
+        eprintln!("docx_include_headers_footers: {}", settings.docx_include_headers_footers);
+        eprintln!("docx_pandoc_flavor: {}", settings.docx_pandoc_flavor);
+        eprintln!("pptx_skip_hidden_slides: {}", settings.pptx_skip_hidden_slides);
+        eprintln!("pptx_include_notes: {}", settings.pptx_include_notes);
+        eprintln!("pdf_include_headers_footers: {}", settings.pdf_include_headers_footers);
+        eprintln!("pdf_page_marker: {}", settings.pdf_page_marker);
+        eprintln!("pdf_min_image_size: {}", settings.pdf_min_image_size);
+        eprintln!("pdf_table_of_contents: {}", settings.pdf_table_of_contents);
+        eprintln!("pdf_password: {:?}", settings.pdf_password.as_ref().map(|_| "***"));
+        eprintln!("pdf_pages: {}", settings.pdf_pages);
+        eprintln!("docx_style_map: {:?}", settings.docx_style_map);
         eprintln!("==============================");
     }
 }
@@ -76,6 +99,15 @@ pub fn set_doubao_api_key(key: Option<String>) {
     debug_print_settings(&settings);
 }
 
+// 设置加密 PDF 的密码（用户密码或所有者密码），供库调用方在转换前使用；CLI 通过 --password 设置
+pub fn set_pdf_password(password: Option<String>) {
+    let mut settings = SETTINGS.write().unwrap();
+    settings.pdf_password = password;
+
+    // Debug output after setting the password
+    debug_print_settings(&settings);
+}
+
 // 提供一个便捷的访问函数，保持原有的使用方式
 pub fn get_settings() -> Settings {
     SETTINGS.read().unwrap().clone()
@@ -86,6 +118,8 @@ pub fn update_settings_with_cli_args(
     image_path: Option<PathBuf>,
     output_path: Option<PathBuf>,
     ai_enable: Option<bool>,
+    pdf_password: Option<String>,
+    pdf_pages: Option<String>,
 ) {
     let mut settings = SETTINGS.write().unwrap();
 
@@ -97,6 +131,14 @@ pub fn update_settings_with_cli_args(
         settings.output_path = Some(path);
     }
 
+    if let Some(password) = pdf_password {
+        settings.pdf_password = Some(password);
+    }
+
+    if let Some(pages) = pdf_pages {
+        settings.pdf_pages = pages;
+    }
+
     if let Some(enable) = ai_enable {
         settings.is_ai_entitle = enable;
         settings.is_ai_sweep = enable; // Assuming is_ai_sweep should also be updated
@@ -0,0 +1,844 @@
+use std::io::{Cursor, Read, Write};
+use std::collections::HashMap;
+use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use base64::Engine;
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+use docx_rust::{
+    document::{BodyContent, TableCellContent, TableRowContent, ParagraphContent},
+    DocxFile,
+};
+use crate::generator::image2md::{self, ImageProcessingMode};
+use crate::config::SETTINGS;
+
+mod omml;
+mod preprocess;
+mod styles;
+
+use styles::{StyleMap, StyleRole};
+
+pub fn run(file_stream: &[u8]) -> Result<String, String> {
+    // Check if pandoc is available
+    if is_pandoc_available() {
+        run_with_pandoc(file_stream)
+    } else {
+        run_with_images(file_stream)
+    }
+}
+
+fn is_pandoc_available() -> bool {
+    Command::new("pandoc")
+        .arg("--version")
+        .output()
+        .is_ok()
+}
+
+/// Target formats accepted for `docx_pandoc_flavor` (pandoc `-t` names)
+const PANDOC_FLAVORS: [&str; 7] = [
+    "markdown",
+    "gfm",
+    "commonmark",
+    "commonmark_x",
+    "markdown_strict",
+    "markdown_mmd",
+    "markdown_phpextra",
+];
+
+/// A uniquely named directory under the system temp dir, removed with its contents on drop
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    fn new() -> Result<TempDir, String> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let name = format!(
+            "markitup-docx-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        std::fs::create_dir(&path)
+            .map_err(|e| format!("Failed to create temporary directory: {}", e))?;
+
+        Ok(TempDir { path })
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+fn run_with_pandoc(file_stream: &[u8]) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+
+    let flavor = cfg.docx_pandoc_flavor.as_str();
+    if !PANDOC_FLAVORS.contains(&flavor) {
+        return Err(format!(
+            "Unsupported pandoc Markdown flavor '{}', expected one of: {}",
+            flavor,
+            PANDOC_FLAVORS.join(", ")
+        ));
+    }
+
+    // Per-call directory so concurrent conversions never share files;
+    // it is removed on every return path
+    let temp_dir = TempDir::new()?;
+    let input_path = temp_dir.path.join("input.docx");
+    let output_path = temp_dir.path.join("output.md");
+    let media_dir = temp_dir.path.join("extracted");
+    
+    // Write DOCX data to temporary file
+    std::fs::write(&input_path, file_stream)
+        .map_err(|e| format!("Failed to write temporary DOCX file: {}", e))?;
+    
+    // Prepare pandoc command
+    let mut cmd = Command::new("pandoc");
+    cmd.arg(&input_path)
+        .arg("-o")
+        .arg(&output_path)
+        .arg("-f")
+        .arg("docx")
+        .arg("-t")
+        .arg(flavor);
+    
+    // Handle image extraction based on configuration
+    if !cfg.image_path.as_os_str().is_empty() {
+        // Extract images to configured directory
+        cmd.arg("--extract-media")
+            .arg(&cfg.image_path);
+    } else {
+        // Extract next to the input, to be inlined as base64 below
+        cmd.arg("--extract-media")
+            .arg(&media_dir);
+    }
+    
+    // Execute pandoc
+    let output = cmd.output()
+        .map_err(|e| format!("Failed to execute pandoc: {}", e))?;
+    
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Pandoc execution failed: {}", error_msg));
+    }
+    
+    // Read the generated markdown
+    let mut markdown = std::fs::read_to_string(&output_path)
+        .map_err(|e| format!("Failed to read pandoc output: {}", e))?;
+    
+    // Post-process images if needed
+    if !cfg.image_path.as_os_str().is_empty() {
+        markdown = process_pandoc_images(markdown)?;
+    } else {
+        // Convert image references to base64 if no image_path is configured
+        markdown = convert_image_refs_to_base64(markdown, &media_dir)?;
+    }
+    
+    Ok(markdown)
+}
+
+fn process_pandoc_images(markdown: String) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    
+    // If we have an output path, calculate relative paths
+    if let Some(output_path) = &cfg.output_path {
+        if !output_path.as_os_str().is_empty() {
+            // Calculate relative path from output file's directory to image directory
+            let output_dir = output_path.parent().unwrap_or(Path::new("."));
+            
+            // Pandoc creates a 'media' subdirectory under the specified extract-media path
+            let pandoc_media_path = cfg.image_path.join("media");
+            
+            if let Ok(relative_path) = pandoc_media_path.strip_prefix(output_dir) {
+                let relative_str = relative_path.to_string_lossy();
+                
+                // Replace pandoc's absolute media paths with relative paths  
+                let updated = markdown.replace(
+                    &format!("]({})", pandoc_media_path.to_string_lossy()),
+                    &format!("](./{})", relative_str)
+                );
+                return Ok(updated);
+            }
+        }
+    }
+    
+    // If no output path or empty output path, use absolute paths
+    Ok(markdown)
+}
+
+/// Replaces references to the media pandoc extracted under `media_dir` with data URIs.
+/// Works on the plain path, so both `![](path)` and raw `<img src="path">` are covered.
+fn convert_image_refs_to_base64(markdown: String, media_dir: &Path) -> Result<String, String> {
+    let mut files = Vec::new();
+    collect_files(media_dir, &mut files)?;
+
+    // longest paths first, so `image1.png` never clobbers part of `image10.png`
+    files.sort_by_key(|path| std::cmp::Reverse(path.as_os_str().len()));
+
+    let mut markdown = markdown;
+    for path in files {
+        let reference = path.to_string_lossy().to_string();
+        if !markdown.contains(&reference) {
+            continue;
+        }
+
+        let data = std::fs::read(&path)
+            .map_err(|e| format!("Failed to read extracted image {}: {}", reference, e))?;
+        let mime_type = infer::get(&data)
+            .map(|kind| kind.mime_type())
+            .unwrap_or("application/octet-stream");
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+
+        markdown = markdown.replace(
+            &reference,
+            &format!("data:{};base64,{}", mime_type, encoded),
+        );
+    }
+
+    Ok(markdown)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read media directory: {}", e))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read media directory: {}", e))?
+            .path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn run_with_images(file_stream: &[u8]) -> Result<String, String> {
+    // docx_rust drops everything it does not model, so rewrite those parts first
+    let file_stream = &preprocess_document(file_stream)?;
+
+    let cursor = Cursor::new(file_stream);
+    let mut archive = ZipArchive::new(cursor)
+        .map_err(|e| format!("Failed to open DOCX archive: {}", e))?;
+
+    // First, extract all images from the archive
+    let mut images = HashMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to access file in ZIP archive: {}", e))?;
+        
+        if file.name().starts_with("word/media/") {
+            let mut image_data = Vec::new();
+            file.read_to_end(&mut image_data)
+                .map_err(|e| format!("Failed to read image data: {}", e))?;
+            
+            let filename = file.name().to_string();
+            images.insert(filename, image_data);
+        }
+    }
+
+    // Reset cursor and parse DOCX with docx_rust
+    let cursor = Cursor::new(file_stream);
+    let docx_file = DocxFile::from_reader(cursor)
+        .map_err(|e| format!("Failed to read DOCX file: {}", e))?;
+    
+    let doc = docx_file.parse()
+        .map_err(|e| format!("Failed to parse DOCX file: {}", e))?;
+
+    let cfg = &*SETTINGS.read().unwrap();
+    let style_map = StyleMap::new(&cfg.docx_style_map, &doc.styles)?;
+
+    let mut markdown = String::new();
+    markdown.push_str("# Document\n\n");
+
+    // Headers and footers usually repeat per section, so each distinct one is emitted once
+    if cfg.docx_include_headers_footers {
+        let headers: Vec<_> = sorted_parts(&doc.headers)
+            .into_iter()
+            .map(|header| &header.content)
+            .collect();
+        let headers_md = process_distinct_parts(&headers, &images, &style_map)?;
+        if !headers_md.is_empty() {
+            markdown.push_str(&headers_md);
+            markdown.push_str("---\n\n");
+        }
+    }
+
+    markdown.push_str(&process_body(&doc.document.body.content, &images, &style_map)?);
+
+    if cfg.docx_include_headers_footers {
+        let footers: Vec<_> = sorted_parts(&doc.footers)
+            .into_iter()
+            .map(|footer| &footer.content)
+            .collect();
+        let footers_md = process_distinct_parts(&footers, &images, &style_map)?;
+        if !footers_md.is_empty() {
+            markdown.push_str("---\n\n");
+            markdown.push_str(&footers_md);
+        }
+    }
+
+    Ok(markdown)
+}
+
+fn process_body(
+    content: &[BodyContent],
+    images: &HashMap<String, Vec<u8>>,
+    style_map: &StyleMap,
+) -> Result<String, String> {
+    let mut markdown = String::new();
+    // consecutive paragraphs sharing a block role (code, quote, callout) form one block
+    let mut block: Option<(StyleRole, Vec<String>)> = None;
+
+    for content in content {
+        match content {
+            BodyContent::Paragraph(paragraph) => {
+                let role = paragraph_role(paragraph, style_map).filter(StyleRole::is_block);
+                if block.as_ref().is_some_and(|(current, _)| Some(current) != role.as_ref()) {
+                    markdown.push_str(&format_block(block.take()));
+                }
+
+                let paragraph_md = process_paragraph(paragraph, images, style_map)?;
+                if let Some(role) = role {
+                    block.get_or_insert_with(|| (role, Vec::new())).1.push(paragraph_md);
+                } else if !paragraph_md.trim().is_empty() {
+                    markdown.push_str(&paragraph_md);
+                    markdown.push_str("\n\n");
+                }
+            }
+            BodyContent::Table(table) => {
+                markdown.push_str(&format_block(block.take()));
+                let table_md = process_table(table)?;
+                if !table_md.trim().is_empty() {
+                    markdown.push_str(&table_md);
+                    markdown.push_str("\n\n");
+                }
+            }
+            _ => {}
+        }
+    }
+    markdown.push_str(&format_block(block.take()));
+
+    Ok(markdown)
+}
+
+fn paragraph_role(
+    paragraph: &docx_rust::document::Paragraph,
+    style_map: &StyleMap,
+) -> Option<StyleRole> {
+    let style_id = paragraph.property.as_ref()?.style_id.as_ref()?;
+    style_map.resolve(&style_id.value)
+}
+
+/// Renders a group of paragraphs sharing a block role
+fn format_block(block: Option<(StyleRole, Vec<String>)>) -> String {
+    let Some((role, paragraphs)) = block else {
+        return String::new();
+    };
+
+    match role {
+        StyleRole::CodeBlock => {
+            let code = paragraphs.join("\n");
+            format!("```\n{}\n```\n\n", code.trim_end_matches('\n'))
+        }
+        StyleRole::Quote | StyleRole::Callout(_) => {
+            let quoted: Vec<String> = paragraphs
+                .iter()
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| {
+                    p.trim()
+                        .lines()
+                        .map(|line| format!("> {}", line).trim_end().to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .collect();
+            if quoted.is_empty() {
+                return String::new();
+            }
+            let marker = match role {
+                StyleRole::Callout(kind) => format!("> [!{}]\n", kind),
+                _ => String::new(),
+            };
+            format!("{}{}\n\n", marker, quoted.join("\n>\n"))
+        }
+        _ => String::new(),
+    }
+}
+
+/// Header/footer parts ordered by their number (`header2.xml` before `header10.xml`)
+fn sorted_parts<T>(parts: &HashMap<String, T>) -> Vec<&T> {
+    let mut names: Vec<&String> = parts.keys().collect();
+    names.sort_by_key(|name| {
+        let number: String = name.chars().filter(|c| c.is_ascii_digit()).collect();
+        (number.parse::<usize>().unwrap_or(0), name.to_string())
+    });
+    names.into_iter().map(|name| &parts[name]).collect()
+}
+
+fn process_distinct_parts(
+    parts: &[&Vec<BodyContent>],
+    images: &HashMap<String, Vec<u8>>,
+    style_map: &StyleMap,
+) -> Result<String, String> {
+    let mut seen: Vec<String> = Vec::new();
+    let mut markdown = String::new();
+
+    for part in parts {
+        let part_md = process_body(part, images, style_map)?;
+        if part_md.trim().is_empty() || seen.contains(&part_md) {
+            continue;
+        }
+        markdown.push_str(&part_md);
+        seen.push(part_md);
+    }
+
+    Ok(markdown)
+}
+
+/// Rewrites the document, header and footer parts into a form docx_rust can keep
+/// (see `preprocess::rewrite_part`), copying every other archive entry unchanged.
+/// Only parts holding math, text boxes or content controls are rewritten, and a part
+/// that fails to rewrite is kept as it is.
+fn preprocess_document(file_stream: &[u8]) -> Result<Vec<u8>, String> {
+    let cursor = Cursor::new(file_stream);
+    let mut archive = ZipArchive::new(cursor)
+        .map_err(|e| format!("Failed to open DOCX archive: {}", e))?;
+
+    let mut rewritten: HashMap<String, String> = HashMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to access file in ZIP archive: {}", e))?;
+        let name = file.name().to_string();
+        if !is_rewritten_part(&name) {
+            continue;
+        }
+
+        let mut xml = String::new();
+        if file.read_to_string(&mut xml).is_err() || !needs_rewrite(&xml) {
+            continue;
+        }
+        if let Ok(part) = preprocess::rewrite_part(&xml) {
+            rewritten.insert(name, part);
+        }
+    }
+    if rewritten.is_empty() {
+        return Ok(file_stream.to_vec());
+    }
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..archive.len() {
+        let file = archive
+            .by_index_raw(i)
+            .map_err(|e| format!("Failed to access file in ZIP archive: {}", e))?;
+        let name = file.name().to_string();
+
+        match rewritten.get(&name) {
+            Some(part) => {
+                drop(file);
+                writer
+                    .start_file(name.as_str(), FileOptions::default())
+                    .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+                writer
+                    .write_all(part.as_bytes())
+                    .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+            }
+            None => {
+                writer
+                    .raw_copy_file(file)
+                    .map_err(|e| format!("Failed to copy file in ZIP archive: {}", e))?;
+            }
+        }
+    }
+
+    let cursor = writer
+        .finish()
+        .map_err(|e| format!("Failed to finish DOCX archive: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+/// Whether a part holds anything `preprocess::rewrite_part` changes
+fn needs_rewrite(xml: &str) -> bool {
+    ["<m:oMath", "<w:txbxContent", "<w:sdt"]
+        .iter()
+        .any(|marker| xml.contains(marker))
+}
+
+fn is_rewritten_part(name: &str) -> bool {
+    name == "word/document.xml"
+        || ((name.starts_with("word/header") || name.starts_with("word/footer"))
+            && name.ends_with(".xml"))
+}
+
+fn process_paragraph(
+    paragraph: &docx_rust::document::Paragraph,
+    images: &HashMap<String, Vec<u8>>,
+    style_map: &StyleMap,
+) -> Result<String, String> {
+    let mut text_content = String::new();
+    let mut is_heading = false;
+    let mut heading_level = 1;
+
+    // Check paragraph style (configured map, then built-in names) for heading detection
+    let role = paragraph_role(paragraph, style_map);
+    if let Some(StyleRole::Heading(level)) = role {
+        is_heading = true;
+        heading_level = level;
+    }
+
+    // Extract text content and check for formatting-based headings
+    let mut has_bold = false;
+    let mut font_size: Option<f32> = None;
+
+    for content in &paragraph.content {
+        match content {
+            ParagraphContent::Run(run) => {
+                // Check run properties for formatting
+                if let Some(props) = &run.property {
+                    if props.bold.is_some() {
+                        has_bold = true;
+                    }
+                    if let Some(size) = &props.size {
+                        font_size = Some(size.value as f32 / 2.0); // Convert half-points to points
+                    }
+                }
+
+                // Character styles mapped to inline code
+                let is_inline_code = run
+                    .property
+                    .as_ref()
+                    .and_then(|props| props.style_id.as_ref())
+                    .and_then(|style_id| style_map.resolve(&style_id.value))
+                    == Some(StyleRole::InlineCode);
+
+                // Extract text from run
+                for run_content in &run.content {
+                    match run_content {
+                        docx_rust::document::RunContent::Text(text) if is_inline_code => {
+                            text_content.push_str(&format!("`{}`", text.text));
+                        }
+                        docx_rust::document::RunContent::Text(text) => {
+                            text_content.push_str(&text.text);
+                        }
+                        docx_rust::document::RunContent::Break(_) => {
+                            text_content.push('\n');
+                        }
+                        docx_rust::document::RunContent::Drawing(_drawing) => {
+                            // Process embedded images in drawings with proper mode
+                            if let Some(image_md) = process_drawing_images_with_mode(images)? {
+                                text_content.push_str(&image_md);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Block roles are wrapped by process_body, which groups consecutive paragraphs
+    if role.as_ref().is_some_and(StyleRole::is_block) {
+        return Ok(text_content);
+    }
+
+    // Determine final heading status
+    let (final_is_heading, final_level) = determine_heading_status(
+        is_heading,
+        heading_level,
+        has_bold,
+        font_size,
+        &text_content
+    );
+
+    if final_is_heading && !text_content.trim().is_empty() {
+        let heading_prefix = "#".repeat(final_level.min(6));
+        Ok(format!("{} {}", heading_prefix, text_content.trim()))
+    } else {
+        Ok(text_content)
+    }
+}
+
+fn process_drawing_images_with_mode(images: &HashMap<String, Vec<u8>>) -> Result<Option<String>, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    
+    // Determine processing mode based on configuration
+    let mode = if cfg.image_path.as_os_str().is_empty() {
+        ImageProcessingMode::Base64
+    } else {
+        ImageProcessingMode::SaveToFile
+    };
+    
+    // Process the first available image (simplified approach)
+    for (filename, image_data) in images {
+        if filename.ends_with(".png") || 
+           filename.ends_with(".jpg") || 
+           filename.ends_with(".jpeg") ||
+           filename.ends_with(".gif") ||
+           filename.ends_with(".webp") {
+            
+            let image_md = image2md::run_with_mode(image_data, mode)?;
+            
+            // Handle relative paths if needed
+            let final_md = if !cfg.image_path.as_os_str().is_empty() {
+                adjust_image_path_in_markdown(image_md)?
+            } else {
+                image_md
+            };
+            
+            return Ok(Some(format!("\n\n{}\n\n", final_md)));
+        }
+    }
+    Ok(None)
+}
+
+fn adjust_image_path_in_markdown(markdown: String) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    
+    // If we have an output path, try to make image paths relative
+    if let Some(output_path) = &cfg.output_path {
+        if !output_path.as_os_str().is_empty() {
+            // Calculate relative path from output file's directory to image directory
+            let output_dir = output_path.parent().unwrap_or(Path::new("."));
+            
+            if let Ok(relative_path) = cfg.image_path.strip_prefix(output_dir) {
+                // Replace absolute image paths with relative ones
+                let relative_str = relative_path.to_string_lossy();
+                return Ok(markdown.replace(
+                    &format!("]({})", cfg.image_path.to_string_lossy()),
+                    &format!("](./{})", relative_str)
+                ));
+            }
+        }
+    }
+    
+    // If no output path configured or can't make relative, return as-is
+    Ok(markdown)
+}
+
+fn check_style_for_heading(style_name: &str) -> Option<(bool, usize)> {
+    let style_lower = style_name.to_lowercase();
+    
+    // Check for various heading patterns
+    if style_lower.starts_with("heading") || style_lower.starts_with("title") {
+        let level = style_name.chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse::<usize>()
+            .unwrap_or(1);
+        return Some((true, level));
+    }
+    
+    // Check for title styles
+    if style_lower == "title" {
+        return Some((true, 1));
+    }
+    
+    // Check for subtitle styles
+    if style_lower.contains("subtitle") {
+        return Some((true, 2));
+    }
+    
+    // Check for other common heading patterns
+    if style_lower.contains("header") {
+        let level = style_name.chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse::<usize>()
+            .unwrap_or(3);
+        return Some((true, level));
+    }
+
+    None
+}
+
+fn determine_heading_status(
+    style_is_heading: bool,
+    style_level: usize,
+    has_bold: bool,
+    font_size: Option<f32>,
+    content: &str
+) -> (bool, usize) {
+    // If explicitly marked as heading by style, use that
+    if style_is_heading {
+        return (true, style_level);
+    }
+    
+    // Check font size for heading detection
+    if let Some(size) = font_size {
+        let level = match size as u32 {
+            s if s >= 18 => 1, // 18pt+ = H1
+            s if s >= 16 => 2, // 16pt+ = H2
+            s if s >= 14 => 3, // 14pt+ = H3
+            s if s >= 13 => 4, // 13pt+ = H4
+            s if s >= 12 => 5, // 12pt+ = H5
+            _ => return (false, 1), // Normal text
+        };
+        
+        // Additional check: short lines are more likely to be headings
+        if content.trim().len() < 100 && !content.trim().ends_with('.') {
+            return (true, level);
+        }
+    }
+    
+    // Heuristic: short, bold lines without periods might be headings
+    let trimmed = content.trim();
+    if has_bold && 
+       trimmed.len() > 0 && 
+       trimmed.len() < 80 && 
+       !trimmed.ends_with('.') && 
+       !trimmed.ends_with('!') && 
+       !trimmed.ends_with('?') &&
+       !trimmed.contains('\n') &&
+       trimmed.chars().any(|c| c.is_alphabetic()) {
+        
+        // Guess level based on length
+        if trimmed.len() < 30 {
+            return (true, 2); // Short titles are likely H2
+        } else if trimmed.len() < 50 {
+            return (true, 3); // Medium titles are likely H3
+        } else {
+            return (true, 4); // Longer titles are likely H4
+        }
+    }
+    
+    (false, 1)
+}
+
+fn process_table(table: &docx_rust::document::Table) -> Result<String, String> {
+    if table.rows.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut markdown = String::new();
+
+    // Header row
+    markdown.push_str("|");
+    if let Some(first_row) = table.rows.first() {
+        for cell in &first_row.cells {
+            match cell {
+                TableRowContent::TableCell(tc) => {
+                    let cell_text = extract_cell_text(tc);
+                    markdown.push_str(&format!(" {} |", cell_text));
+                }
+                _ => {
+                    markdown.push_str(" |");
+                }
+            }
+        }
+        markdown.push_str("\n");
+
+        // Separator row
+        markdown.push_str("|");
+        for _ in &first_row.cells {
+            markdown.push_str("---|");
+        }
+        markdown.push_str("\n");
+    }
+
+    // Data rows
+    for row in table.rows.iter().skip(1) {
+        markdown.push_str("|");
+        for cell in &row.cells {
+            match cell {
+                TableRowContent::TableCell(tc) => {
+                    let cell_text = extract_cell_text(tc);
+                    markdown.push_str(&format!(" {} |", cell_text));
+                }
+                _ => {
+                    markdown.push_str(" |");
+                }
+            }
+        }
+        markdown.push_str("\n");
+    }
+
+    Ok(markdown)
+}
+
+fn extract_cell_text(cell: &docx_rust::document::TableCell) -> String {
+    let mut text = String::new();
+    
+    for content in &cell.content {
+        match content {
+            TableCellContent::Paragraph(paragraph) => {
+                for para_content in &paragraph.content {
+                    if let ParagraphContent::Run(run) = para_content {
+                        for run_content in &run.content {
+                            match run_content {
+                                docx_rust::document::RunContent::Text(text_elem) => {
+                                    text.push_str(&text_elem.text);
+                                }
+                                docx_rust::document::RunContent::Break(_) => text.push(' '),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                if !text.is_empty() && !text.ends_with(' ') {
+                    text.push(' ');
+                }
+            }
+        }
+    }
+    
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docx(document: &str) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in [("[Content_Types].xml", "<Types/>"), ("word/document.xml", document)] {
+            writer.start_file(name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn document_part(file_stream: &[u8]) -> String {
+        let mut archive = ZipArchive::new(Cursor::new(file_stream)).unwrap();
+        let mut xml = String::new();
+        archive.by_name("word/document.xml").unwrap().read_to_string(&mut xml).unwrap();
+        xml
+    }
+
+    #[test]
+    fn test_plain_document_is_not_rewritten() {
+        let file_stream = docx("<w:document><w:body><w:p><w:r><w:t>a &amp; b</w:t></w:r></w:p></w:body></w:document>");
+        assert_eq!(preprocess_document(&file_stream).unwrap(), file_stream);
+    }
+
+    #[test]
+    fn test_part_that_fails_to_rewrite_is_kept() {
+        let document = "<w:document><w:body><w:sdt><w:sdtContent></w:p></w:body></w:document>";
+        let file_stream = docx(document);
+        assert_eq!(preprocess_document(&file_stream).unwrap(), file_stream);
+    }
+
+    #[test]
+    fn test_document_with_math_is_rewritten() {
+        let document =
+            "<w:document><w:body><w:p><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath></w:p></w:body></w:document>";
+        let rewritten = preprocess_document(&docx(document)).unwrap();
+        assert!(document_part(&rewritten).contains("$x$"));
+    }
+}
@@ -0,0 +1,651 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// Minimal element tree for an Office Math (OMML) fragment
+#[derive(Debug, Default, Clone)]
+pub struct MathNode {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<MathNode>,
+    pub text: String,
+}
+
+impl MathNode {
+    fn from_start(element: &BytesStart) -> Self {
+        let attrs = element
+            .attributes()
+            .filter_map(|a| a.ok())
+            .map(|a| {
+                (
+                    String::from_utf8_lossy(a.key.as_ref()).to_string(),
+                    a.unescape_value().unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+        MathNode {
+            name: String::from_utf8_lossy(element.name().as_ref()).to_string(),
+            attrs,
+            ..Default::default()
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&MathNode> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a MathNode> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Reads `m:val` of `<prop_name><val_name m:val="..."/></prop_name>`
+    fn prop(&self, prop_name: &str, val_name: &str) -> Option<&str> {
+        self.child(prop_name)?.child(val_name)?.attr("m:val")
+    }
+
+    fn flag(&self, prop_name: &str, val_name: &str) -> bool {
+        match self.child(prop_name).and_then(|p| p.child(val_name)) {
+            // a bare <m:degHide/> means "on"
+            Some(node) => !matches!(node.attr("m:val"), Some("0") | Some("off") | Some("false")),
+            None => false,
+        }
+    }
+
+    /// Concatenated `m:t` text of the subtree, without any translation
+    fn plain_text(&self) -> String {
+        let mut text = self.text.clone();
+        for child in &self.children {
+            text.push_str(&child.plain_text());
+        }
+        text
+    }
+}
+
+/// Reads the OMML element opened by `start` (and all of its descendants) into a tree
+pub fn read_math_node(reader: &mut Reader<&[u8]>, start: &BytesStart) -> Result<MathNode, String> {
+    let mut stack = vec![MathNode::from_start(start)];
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                stack.push(MathNode::from_start(&element));
+            }
+            Ok(Event::Empty(element)) => {
+                let node = MathNode::from_start(&element);
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Ok(Event::Text(e)) => {
+                let current = stack
+                    .last_mut()
+                    .filter(|node| node.name == "m:t" || node.name == "w:t");
+                if let Some(current) = current {
+                    current.text.push_str(&e.unescape().unwrap_or_default());
+                }
+            }
+            Ok(Event::End(_)) => {
+                let node = stack.pop().ok_or("Unbalanced math element")?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Ok(node),
+                }
+            }
+            Ok(Event::Eof) => return Err("Unexpected end of document inside math".to_string()),
+            Err(e) => return Err(format!("Error parsing math XML: {}", e)),
+            _ => {}
+        }
+    }
+}
+
+/// Translates an `m:oMathPara` or `m:oMath` tree into LaTeX (without `$` delimiters)
+pub fn omml_to_latex(node: &MathNode) -> String {
+    let latex = if node.name == "m:oMathPara" {
+        node.children_named("m:oMath")
+            .map(|m| children_to_latex(m).trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" \\\\ ")
+    } else {
+        children_to_latex(node)
+    };
+    latex.trim().to_string()
+}
+
+fn children_to_latex(node: &MathNode) -> String {
+    node.children.iter().map(node_to_latex).collect()
+}
+
+/// LaTeX of a named argument (`m:e`, `m:num`, `m:sub`, ...), empty when missing
+fn arg(node: &MathNode, name: &str) -> String {
+    node.child(name)
+        .map(|c| children_to_latex(c).trim().to_string())
+        .unwrap_or_default()
+}
+
+fn node_to_latex(node: &MathNode) -> String {
+    match node.name.as_str() {
+        "m:r" => run_to_latex(node),
+        "m:f" => {
+            let num = arg(node, "m:num");
+            let den = arg(node, "m:den");
+            match node.prop("m:fPr", "m:type") {
+                Some("lin") => format!("{{{}}}/{{{}}}", num, den),
+                Some("noBar") => format!("\\genfrac{{}}{{}}{{0pt}}{{}}{{{}}}{{{}}}", num, den),
+                _ => format!("\\frac{{{}}}{{{}}}", num, den),
+            }
+        }
+        "m:rad" => {
+            let deg = arg(node, "m:deg");
+            let e = arg(node, "m:e");
+            if deg.is_empty() || node.flag("m:radPr", "m:degHide") {
+                format!("\\sqrt{{{}}}", e)
+            } else {
+                format!("\\sqrt[{}]{{{}}}", deg, e)
+            }
+        }
+        "m:nary" => {
+            let chr = node.prop("m:naryPr", "m:chr").unwrap_or("∫");
+            let mut latex = nary_operator(chr);
+            let sub = arg(node, "m:sub");
+            let sup = arg(node, "m:sup");
+            if !sub.is_empty() && !node.flag("m:naryPr", "m:subHide") {
+                latex.push_str(&format!("_{{{}}}", sub));
+            }
+            if !sup.is_empty() && !node.flag("m:naryPr", "m:supHide") {
+                latex.push_str(&format!("^{{{}}}", sup));
+            }
+            format!("{}{{{}}}", latex, arg(node, "m:e"))
+        }
+        "m:sSub" => format!("{{{}}}_{{{}}}", arg(node, "m:e"), arg(node, "m:sub")),
+        "m:sSup" => format!("{{{}}}^{{{}}}", arg(node, "m:e"), arg(node, "m:sup")),
+        "m:sSubSup" => format!(
+            "{{{}}}_{{{}}}^{{{}}}",
+            arg(node, "m:e"),
+            arg(node, "m:sub"),
+            arg(node, "m:sup")
+        ),
+        "m:sPre" => format!(
+            "{{}}_{{{}}}^{{{}}}{{{}}}",
+            arg(node, "m:sub"),
+            arg(node, "m:sup"),
+            arg(node, "m:e")
+        ),
+        "m:d" => {
+            let beg = delimiter(node.prop("m:dPr", "m:begChr").unwrap_or("("));
+            let end = delimiter(node.prop("m:dPr", "m:endChr").unwrap_or(")"));
+            let sep = match node.prop("m:dPr", "m:sepChr").unwrap_or("|") {
+                "|" => " \\mid ".to_string(),
+                other => format!("{} ", map_math_text(other)),
+            };
+            let items: Vec<String> = node
+                .children_named("m:e")
+                .map(|e| children_to_latex(e).trim().to_string())
+                .collect();
+            format!("\\left{} {} \\right{}", beg, items.join(&sep), end)
+        }
+        "m:m" => {
+            let rows: Vec<String> = node
+                .children_named("m:mr")
+                .map(|row| {
+                    row.children_named("m:e")
+                        .map(|e| children_to_latex(e).trim().to_string())
+                        .collect::<Vec<_>>()
+                        .join(" & ")
+                })
+                .collect();
+            format!("\\begin{{matrix}} {} \\end{{matrix}}", rows.join(" \\\\ "))
+        }
+        "m:eqArr" => {
+            let rows: Vec<String> = node
+                .children_named("m:e")
+                .map(|e| children_to_latex(e).trim().to_string())
+                .collect();
+            format!("\\begin{{aligned}} {} \\end{{aligned}}", rows.join(" \\\\ "))
+        }
+        "m:acc" => {
+            let chr = node.prop("m:accPr", "m:chr").unwrap_or("\u{0302}");
+            format!("{}{{{}}}", accent_command(chr), arg(node, "m:e"))
+        }
+        "m:bar" => {
+            let command = match node.prop("m:barPr", "m:pos") {
+                Some("top") => "\\overline",
+                _ => "\\underline",
+            };
+            format!("{}{{{}}}", command, arg(node, "m:e"))
+        }
+        "m:groupChr" => {
+            let chr = node.prop("m:groupChrPr", "m:chr").unwrap_or("\u{23DF}");
+            let top = node.prop("m:groupChrPr", "m:pos") == Some("top");
+            let e = arg(node, "m:e");
+            match chr {
+                "\u{23DF}" => format!("\\underbrace{{{}}}", e),
+                "\u{23DE}" => format!("\\overbrace{{{}}}", e),
+                _ if top => format!("\\overset{{{}}}{{{}}}", map_math_text(chr), e),
+                _ => format!("\\underset{{{}}}{{{}}}", map_math_text(chr), e),
+            }
+        }
+        "m:func" => {
+            let name = node.child("m:fName");
+            let name_latex = match name {
+                Some(n) if n.children.iter().all(|c| c.name == "m:r") => {
+                    function_name(n.plain_text().trim())
+                }
+                Some(n) => children_to_latex(n).trim().to_string(),
+                None => String::new(),
+            };
+            format!("{}{{{}}}", name_latex, arg(node, "m:e"))
+        }
+        "m:limLow" | "m:limUpp" => {
+            let lower = node.name == "m:limLow";
+            let lim = arg(node, "m:lim");
+            let base = node.child("m:e");
+            let base_text = base.map(|b| b.plain_text()).unwrap_or_default();
+            let is_function = base
+                .map(|b| b.children.iter().all(|c| c.name == "m:r"))
+                .unwrap_or(false)
+                && is_limit_like(base_text.trim());
+            let is_group = base
+                .map(|b| b.children.len() == 1 && b.children[0].name == "m:groupChr")
+                .unwrap_or(false);
+
+            if is_function || is_group {
+                let base_latex = if is_function {
+                    format!("\\{}", base_text.trim())
+                } else {
+                    arg(node, "m:e")
+                };
+                let script = if lower { "_" } else { "^" };
+                format!("{}{}{{{}}}", base_latex, script, lim)
+            } else if lower {
+                format!("\\underset{{{}}}{{{}}}", lim, arg(node, "m:e"))
+            } else {
+                format!("\\overset{{{}}}{{{}}}", lim, arg(node, "m:e"))
+            }
+        }
+        "m:borderBox" => format!("\\boxed{{{}}}", arg(node, "m:e")),
+        "m:phant" => {
+            let e = arg(node, "m:e");
+            match node.prop("m:phantPr", "m:show") {
+                Some("0") | Some("off") => format!("\\phantom{{{}}}", e),
+                _ => e,
+            }
+        }
+        // Office math sometimes embeds ordinary Word runs
+        "w:r" => map_math_text(&node.plain_text()),
+        // property bags carry no content of their own
+        name if name.ends_with("Pr") => String::new(),
+        // m:box, m:e, m:oMath and anything unknown: just translate the children
+        _ => children_to_latex(node),
+    }
+}
+
+fn run_to_latex(node: &MathNode) -> String {
+    let text = node.plain_text();
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let properties = node.child("m:rPr");
+    let is_normal_text = properties.and_then(|p| p.child("m:nor")).is_some();
+    let is_plain = properties
+        .and_then(|p| p.child("m:sty"))
+        .and_then(|s| s.attr("m:val"))
+        == Some("p");
+
+    if is_normal_text {
+        return format!("\\text{{{}}}", escape_latex(&text));
+    }
+    if is_plain && text.chars().filter(|c| c.is_alphabetic()).count() > 1 {
+        if text.chars().all(|c| c.is_ascii_alphabetic()) && is_function_name(&text) {
+            return format!("\\{} ", text);
+        }
+        return format!("\\mathrm{{{}}}", map_math_text(&text));
+    }
+    map_math_text(&text)
+}
+
+/// Escapes the characters LaTeX treats as special, e.g. `&` which would end a matrix cell
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '{' | '}' | '%' | '#' | '$' | '_' | '&' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '\\' => escaped.push_str("\\backslash "),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Maps the characters of an `m:t` to LaTeX, translating Unicode math symbols
+fn map_math_text(text: &str) -> String {
+    let mut latex = String::new();
+    for c in text.chars() {
+        match symbol_command(c) {
+            Some(command) => {
+                latex.push_str(command);
+                latex.push(' ');
+            }
+            None => latex.push_str(&escape_latex(&c.to_string())),
+        }
+    }
+    latex
+}
+
+fn symbol_command(c: char) -> Option<&'static str> {
+    let command = match c {
+        // greek
+        'α' => "\\alpha",
+        'β' => "\\beta",
+        'γ' => "\\gamma",
+        'δ' => "\\delta",
+        'ε' => "\\varepsilon",
+        'ϵ' => "\\epsilon",
+        'ζ' => "\\zeta",
+        'η' => "\\eta",
+        'θ' => "\\theta",
+        'ϑ' => "\\vartheta",
+        'ι' => "\\iota",
+        'κ' => "\\kappa",
+        'λ' => "\\lambda",
+        'μ' => "\\mu",
+        'ν' => "\\nu",
+        'ξ' => "\\xi",
+        'π' => "\\pi",
+        'ρ' => "\\rho",
+        'σ' => "\\sigma",
+        'ς' => "\\varsigma",
+        'τ' => "\\tau",
+        'υ' => "\\upsilon",
+        'φ' => "\\varphi",
+        'ϕ' => "\\phi",
+        'χ' => "\\chi",
+        'ψ' => "\\psi",
+        'ω' => "\\omega",
+        'Γ' => "\\Gamma",
+        'Δ' => "\\Delta",
+        'Θ' => "\\Theta",
+        'Λ' => "\\Lambda",
+        'Ξ' => "\\Xi",
+        'Π' => "\\Pi",
+        'Σ' => "\\Sigma",
+        'Υ' => "\\Upsilon",
+        'Φ' => "\\Phi",
+        'Ψ' => "\\Psi",
+        'Ω' => "\\Omega",
+        // operators and relations
+        '∞' => "\\infty",
+        '≤' => "\\leq",
+        '≥' => "\\geq",
+        '≠' => "\\neq",
+        '≈' => "\\approx",
+        '≡' => "\\equiv",
+        '∝' => "\\propto",
+        '∼' => "\\sim",
+        '≅' => "\\cong",
+        '≪' => "\\ll",
+        '≫' => "\\gg",
+        '±' => "\\pm",
+        '∓' => "\\mp",
+        '×' => "\\times",
+        '÷' => "\\div",
+        '·' | '⋅' => "\\cdot",
+        '∘' => "\\circ",
+        '−' => "-",
+        '∗' => "\\ast",
+        // literal carets and tildes, which are not text-mode commands in math
+        '^' => "\\hat{}",
+        '~' => "\\sim",
+        '→' => "\\rightarrow",
+        '←' => "\\leftarrow",
+        '↔' => "\\leftrightarrow",
+        '⇒' => "\\Rightarrow",
+        '⇐' => "\\Leftarrow",
+        '⇔' => "\\Leftrightarrow",
+        '↦' => "\\mapsto",
+        '∈' => "\\in",
+        '∉' => "\\notin",
+        '∋' => "\\ni",
+        '⊂' => "\\subset",
+        '⊃' => "\\supset",
+        '⊆' => "\\subseteq",
+        '⊇' => "\\supseteq",
+        '∪' => "\\cup",
+        '∩' => "\\cap",
+        '∅' => "\\emptyset",
+        '∀' => "\\forall",
+        '∃' => "\\exists",
+        '¬' => "\\neg",
+        '∧' => "\\wedge",
+        '∨' => "\\vee",
+        '⊕' => "\\oplus",
+        '⊗' => "\\otimes",
+        '∂' => "\\partial",
+        '∇' => "\\nabla",
+        '…' => "\\ldots",
+        '⋯' => "\\cdots",
+        '⋮' => "\\vdots",
+        '⋱' => "\\ddots",
+        '′' => "'",
+        '″' => "''",
+        '°' => "^{\\circ}",
+        'ℏ' => "\\hbar",
+        'ℓ' => "\\ell",
+        'ℝ' => "\\mathbb{R}",
+        'ℕ' => "\\mathbb{N}",
+        'ℤ' => "\\mathbb{Z}",
+        'ℚ' => "\\mathbb{Q}",
+        'ℂ' => "\\mathbb{C}",
+        '⟨' | '〈' => "\\langle",
+        '⟩' | '〉' => "\\rangle",
+        // Word uses these as (invisible) operators between factors
+        '\u{2061}' | '\u{2062}' | '\u{2063}' | '\u{2064}' => "",
+        _ => return None,
+    };
+    Some(command)
+}
+
+fn nary_operator(chr: &str) -> String {
+    let command = match chr {
+        "∑" => "\\sum",
+        "∏" => "\\prod",
+        "∐" => "\\coprod",
+        "∫" => "\\int",
+        "∬" => "\\iint",
+        "∭" => "\\iiint",
+        "∮" => "\\oint",
+        "∯" => "\\oiint",
+        "⋃" => "\\bigcup",
+        "⋂" => "\\bigcap",
+        "⋁" => "\\bigvee",
+        "⋀" => "\\bigwedge",
+        "⨁" => "\\bigoplus",
+        "⨂" => "\\bigotimes",
+        other => return map_math_text(other).trim().to_string(),
+    };
+    command.to_string()
+}
+
+fn delimiter(chr: &str) -> String {
+    let latex = match chr {
+        "" => ".",
+        "{" => "\\{",
+        "}" => "\\}",
+        "⟨" | "〈" => "\\langle",
+        "⟩" | "〉" => "\\rangle",
+        "‖" => "\\|",
+        "⌊" => "\\lfloor",
+        "⌋" => "\\rfloor",
+        "⌈" => "\\lceil",
+        "⌉" => "\\rceil",
+        other => other,
+    };
+    latex.to_string()
+}
+
+fn accent_command(chr: &str) -> &'static str {
+    match chr {
+        "\u{0303}" | "~" => "\\tilde",
+        "\u{0304}" | "\u{0305}" | "¯" => "\\bar",
+        "\u{0307}" | "˙" => "\\dot",
+        "\u{0308}" | "¨" => "\\ddot",
+        "\u{20D7}" | "\u{20D1}" | "→" => "\\vec",
+        "\u{0301}" | "´" => "\\acute",
+        "\u{0300}" | "`" => "\\grave",
+        "\u{0306}" | "˘" => "\\breve",
+        "\u{030C}" | "ˇ" => "\\check",
+        _ => "\\hat",
+    }
+}
+
+fn is_function_name(name: &str) -> bool {
+    matches!(
+        name,
+        "sin" | "cos" | "tan" | "cot" | "sec" | "csc" | "arcsin" | "arccos" | "arctan"
+            | "sinh" | "cosh" | "tanh" | "coth" | "log" | "ln" | "lg" | "exp" | "det"
+            | "dim" | "ker" | "deg" | "gcd" | "arg" | "hom" | "Pr"
+    ) || is_limit_like(name)
+}
+
+fn is_limit_like(name: &str) -> bool {
+    matches!(name, "lim" | "max" | "min" | "sup" | "inf" | "liminf" | "limsup")
+}
+
+fn function_name(name: &str) -> String {
+    if is_function_name(name) {
+        format!("\\{}", name)
+    } else if !name.is_empty() && name.chars().all(|c| c.is_alphabetic()) {
+        format!("\\operatorname{{{}}}", name)
+    } else {
+        map_math_text(name).trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str) -> String {
+        format!("<m:r><m:t>{}</m:t></m:r>", text)
+    }
+
+    /// LaTeX of the first element of `xml`
+    fn latex(xml: &str) -> String {
+        let mut reader = Reader::from_str(xml);
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(start) => {
+                    let node = read_math_node(&mut reader, &start).unwrap();
+                    return omml_to_latex(&node);
+                }
+                Event::Eof => panic!("no math element"),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_fraction() {
+        let fraction = format!("<m:num>{}</m:num><m:den>{}</m:den>", run("a"), run("b"));
+        assert_eq!(latex(&format!("<m:oMath><m:f>{}</m:f></m:oMath>", fraction)), "\\frac{a}{b}");
+        let linear = format!(r#"<m:oMath><m:f><m:fPr><m:type m:val="lin"/></m:fPr>{}</m:f></m:oMath>"#, fraction);
+        assert_eq!(latex(&linear), "{a}/{b}");
+    }
+
+    #[test]
+    fn test_radical() {
+        let xml = format!("<m:oMath><m:rad><m:deg>{}</m:deg><m:e>{}</m:e></m:rad></m:oMath>", run("3"), run("x"));
+        assert_eq!(latex(&xml), "\\sqrt[3]{x}");
+        let xml = format!(
+            "<m:oMath><m:rad><m:radPr><m:degHide/></m:radPr><m:deg/><m:e>{}</m:e></m:rad></m:oMath>",
+            run("x")
+        );
+        assert_eq!(latex(&xml), "\\sqrt{x}");
+    }
+
+    #[test]
+    fn test_nary() {
+        let xml = format!(
+            r#"<m:oMath><m:nary><m:naryPr><m:chr m:val="∑"/></m:naryPr><m:sub>{}</m:sub><m:sup>{}</m:sup><m:e>{}</m:e></m:nary></m:oMath>"#,
+            run("i=1"),
+            run("n"),
+            run("i")
+        );
+        assert_eq!(latex(&xml), "\\sum_{i=1}^{n}{i}");
+        // no m:chr is an integral
+        let xml = format!(
+            "<m:oMath><m:nary><m:sub>{}</m:sub><m:sup>{}</m:sup><m:e>{}</m:e></m:nary></m:oMath>",
+            run("0"),
+            run("∞"),
+            run("f")
+        );
+        assert_eq!(latex(&xml), "\\int_{0}^{\\infty}{f}");
+    }
+
+    #[test]
+    fn test_matrix() {
+        let row = |a: &str, b: &str| format!("<m:mr><m:e>{}</m:e><m:e>{}</m:e></m:mr>", run(a), run(b));
+        let xml = format!("<m:oMath><m:m>{}{}</m:m></m:oMath>", row("a", "b"), row("c", "d"));
+        assert_eq!(latex(&xml), "\\begin{matrix} a & b \\\\ c & d \\end{matrix}");
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let xml = format!(
+            r#"<m:oMath><m:d><m:dPr><m:begChr m:val="{{"/><m:endChr m:val="⟩"/></m:dPr><m:e>{}</m:e></m:d></m:oMath>"#,
+            run("x")
+        );
+        assert_eq!(latex(&xml), "\\left\\{ x \\right\\rangle");
+        let xml = format!(
+            r#"<m:oMath><m:d><m:dPr><m:begChr m:val="["/><m:endChr m:val=""/></m:dPr><m:e>{}</m:e><m:e>{}</m:e></m:d></m:oMath>"#,
+            run("a"),
+            run("b")
+        );
+        assert_eq!(latex(&xml), "\\left[ a \\mid b \\right.");
+    }
+
+    #[test]
+    fn test_accent() {
+        let xml = format!(
+            r#"<m:oMath><m:acc><m:accPr><m:chr m:val="&#x20D7;"/></m:accPr><m:e>{}</m:e></m:acc></m:oMath>"#,
+            run("v")
+        );
+        assert_eq!(latex(&xml), "\\vec{v}");
+        // no m:chr is a circumflex
+        let xml = format!("<m:oMath><m:acc><m:e>{}</m:e></m:acc></m:oMath>", run("x"));
+        assert_eq!(latex(&xml), "\\hat{x}");
+    }
+
+    #[test]
+    fn test_math_paragraph() {
+        let xml = format!(
+            "<m:oMathPara><m:oMath>{}</m:oMath><m:oMath>{}</m:oMath></m:oMathPara>",
+            run("α"),
+            run("β")
+        );
+        assert_eq!(latex(&xml), "\\alpha \\\\ \\beta");
+    }
+
+    #[test]
+    fn test_special_characters_are_escaped() {
+        let row = format!("<m:mr><m:e>{}</m:e><m:e>{}</m:e></m:mr>", run("a&amp;b"), run("#1"));
+        let xml = format!("<m:oMath><m:m>{}</m:m></m:oMath>", row);
+        assert_eq!(latex(&xml), "\\begin{matrix} a\\&b & \\#1 \\end{matrix}");
+        assert_eq!(latex(&format!("<m:oMath>{}</m:oMath>", run("x~y^z"))), "x\\sim y\\hat{} z");
+
+        let text = "<m:oMath><m:r><m:rPr><m:nor/></m:rPr><m:t>R&amp;D ^~</m:t></m:r></m:oMath>";
+        assert_eq!(latex(text), "\\text{R\\&D \\textasciicircum{}\\textasciitilde{}}");
+    }
+}
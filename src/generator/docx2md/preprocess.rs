@@ -0,0 +1,407 @@
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+
+use super::omml;
+
+/// Elements whose subtree may hold a text box (`w:txbxContent`)
+const TEXT_BOX_HOSTS: [&[u8]; 3] = [b"mc:AlternateContent", b"w:pict", b"w:drawing"];
+
+/// A structured document tag (`w:sdt`) being unwrapped
+struct ContentControl {
+    label: Option<String>,
+    inline: bool,
+    labelled: bool,
+}
+
+impl ContentControl {
+    fn visible_label(&self) -> Option<&str> {
+        self.label.as_deref().filter(|_| self.labelled)
+    }
+}
+
+/// Rewrites a WordprocessingML part (`document.xml`, `header*.xml`, `footer*.xml`) into the
+/// subset docx_rust models, keeping content it would otherwise drop:
+/// - Office Math becomes a text run holding its LaTeX
+/// - text boxes become runs at their anchor, one line per text box paragraph
+/// - content controls are unwrapped, with their title/tag written in front of the content
+pub fn rewrite_part(xml: &str) -> Result<String, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::new());
+    // names of the currently open elements, including the unwrapped ones
+    let mut open: Vec<Vec<u8>> = Vec::new();
+    let mut controls: Vec<ContentControl> = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Error parsing document XML: {}", e))?;
+        match event {
+            Event::Start(element) => match element.name().as_ref() {
+                b"m:oMathPara" | b"m:oMath" => {
+                    let node = omml::read_math_node(&mut reader, &element)?;
+                    let latex = omml::omml_to_latex(&node);
+                    if !latex.is_empty() {
+                        let text = if node.name == "m:oMathPara" {
+                            format!("$${}$$", latex)
+                        } else {
+                            format!("${}$", latex)
+                        };
+                        write_runs(&mut writer, &open, &[text])?;
+                    }
+                }
+                name if TEXT_BOX_HOSTS.contains(&name) => {
+                    let events = read_subtree(&mut reader, Event::Start(element))?;
+                    match text_box_lines(&events) {
+                        Some(lines) => {
+                            write_runs(&mut writer, &open, &lines)?;
+                            // a drawing group can hold pictures next to its text boxes
+                            let rest = without_text_boxes(events);
+                            if rest.iter().any(is_picture) {
+                                for event in rest {
+                                    emit(&mut writer, event)?;
+                                }
+                            }
+                        }
+                        None => {
+                            for event in events {
+                                emit(&mut writer, event)?;
+                            }
+                        }
+                    }
+                }
+                b"w:sdt" => {
+                    let parent = open.last().map(|n| n.as_slice());
+                    let inline = open.iter().any(|n| n == b"w:p");
+                    // labels cannot be placed between table rows or cells
+                    let labelled = inline || !matches!(parent, Some(b"w:tbl") | Some(b"w:tr"));
+                    controls.push(ContentControl {
+                        label: None,
+                        inline,
+                        labelled,
+                    });
+                    open.push(b"w:sdt".to_vec());
+                }
+                b"w:sdtPr" => {
+                    let label = read_content_control_label(&mut reader)?;
+                    if let Some(control) = controls.last_mut() {
+                        control.label = label;
+                    }
+                }
+                b"w:sdtEndPr" => {
+                    read_subtree(&mut reader, Event::Start(element))?;
+                }
+                b"w:sdtContent" => {
+                    open.push(b"w:sdtContent".to_vec());
+                    if let Some(control) = controls.last() {
+                        match (control.visible_label(), control.inline) {
+                            (Some(label), true) => {
+                                write_runs(&mut writer, &open, &[format!("[{}: ", label)])?
+                            }
+                            (Some(label), false) => {
+                                write_runs(&mut writer, &open, &[format!("**{}:**", label)])?
+                            }
+                            (None, _) => {}
+                        }
+                    }
+                }
+                name => {
+                    open.push(name.to_vec());
+                    emit(&mut writer, Event::Start(element))?;
+                }
+            },
+            Event::End(element) => {
+                open.pop();
+                match element.name().as_ref() {
+                    b"w:sdtContent" => {
+                        let closes_label = controls
+                            .last()
+                            .is_some_and(|c| c.inline && c.visible_label().is_some());
+                        if closes_label {
+                            write_runs(&mut writer, &open, &["]".to_string()])?;
+                        }
+                    }
+                    b"w:sdt" => {
+                        controls.pop();
+                    }
+                    _ => emit(&mut writer, Event::End(element))?,
+                }
+            }
+            Event::Empty(element) => match element.name().as_ref() {
+                b"m:oMathPara" | b"m:oMath" | b"w:sdtPr" | b"w:sdtEndPr" | b"w:sdtContent" => {}
+                _ => emit(&mut writer, Event::Empty(element))?,
+            },
+            Event::Eof => break,
+            event => emit(&mut writer, event)?,
+        }
+    }
+
+    String::from_utf8(writer.into_inner())
+        .map_err(|e| format!("Rewritten document XML is not UTF-8: {}", e))
+}
+
+fn emit(writer: &mut Writer<Vec<u8>>, event: Event) -> Result<(), String> {
+    writer
+        .write_event(event)
+        .map_err(|e| format!("Error writing document XML: {}", e))
+}
+
+/// Collects `start` and every event up to its matching end tag
+fn read_subtree<'a>(
+    reader: &mut Reader<&'a [u8]>,
+    start: Event<'a>,
+) -> Result<Vec<Event<'a>>, String> {
+    let mut events = vec![start];
+    let mut depth = 1;
+
+    while depth > 0 {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Error parsing document XML: {}", e))?;
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof => return Err("Unexpected end of document XML".to_string()),
+            _ => {}
+        }
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+/// The subtree without its `w:txbxContent` elements
+fn without_text_boxes(events: Vec<Event>) -> Vec<Event> {
+    let mut kept = Vec::new();
+    let mut depth = 0;
+    for event in events {
+        let is_text_box = |e: &BytesStart| e.name().as_ref() == b"w:txbxContent";
+        match &event {
+            Event::Start(element) if depth > 0 || is_text_box(element) => depth += 1,
+            Event::End(_) if depth > 0 => depth -= 1,
+            Event::Empty(element) if is_text_box(element) => {}
+            _ if depth > 0 => {}
+            _ => kept.push(event),
+        }
+    }
+    kept
+}
+
+/// A DrawingML or VML picture
+fn is_picture(event: &Event) -> bool {
+    match event {
+        Event::Start(element) | Event::Empty(element) => {
+            matches!(element.name().as_ref(), b"pic:pic" | b"v:imagedata")
+        }
+        _ => false,
+    }
+}
+
+/// Text of the first `w:txbxContent` in the subtree, one entry per non-empty paragraph.
+/// Returns `None` when the subtree holds no text box at all.
+fn text_box_lines(events: &[Event]) -> Option<Vec<String>> {
+    let start = events.iter().position(
+        |e| matches!(e, Event::Start(element) if element.name().as_ref() == b"w:txbxContent"),
+    )?;
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut depth = 0;
+    let mut in_text = false;
+
+    for event in &events[start..] {
+        match event {
+            Event::Start(element) => {
+                depth += 1;
+                in_text = element.name().as_ref() == b"w:t";
+            }
+            Event::End(element) => {
+                depth -= 1;
+                in_text = false;
+                if element.name().as_ref() == b"w:p" && !line.trim().is_empty() {
+                    lines.push(line.trim().to_string());
+                    line.clear();
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+            Event::Empty(element) if element.name().as_ref() == b"w:tab" => line.push(' '),
+            Event::Text(text) if in_text => {
+                line.push_str(&text.unescape().unwrap_or_default());
+            }
+            _ => {}
+        }
+    }
+
+    Some(lines)
+}
+
+/// Reads a `w:sdtPr` (after its start tag) and returns "title (tag)" for the control
+fn read_content_control_label(reader: &mut Reader<&[u8]>) -> Result<Option<String>, String> {
+    let mut alias = None;
+    let mut tag = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Error parsing document XML: {}", e))?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                let value = element
+                    .try_get_attribute("w:val")
+                    .ok()
+                    .flatten()
+                    .and_then(|a| a.unescape_value().ok())
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+                match element.name().as_ref() {
+                    b"w:alias" => alias = value,
+                    b"w:tag" => tag = value,
+                    _ => {}
+                }
+            }
+            Event::End(element) if element.name().as_ref() == b"w:sdtPr" => break,
+            Event::Eof => return Err("Unexpected end of document XML".to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(match (alias, tag) {
+        (Some(alias), Some(tag)) if alias != tag => Some(format!("{} ({})", alias, tag)),
+        (Some(alias), _) => Some(alias),
+        (None, tag) => tag,
+    })
+}
+
+/// Writes `lines` as text separated by line breaks, wrapped in whatever
+/// `w:p`/`w:r` elements the current position needs to be valid
+fn write_runs(writer: &mut Writer<Vec<u8>>, open: &[Vec<u8>], lines: &[String]) -> Result<(), String> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let parent = open.last().map(|n| n.as_slice());
+    let in_run = parent == Some(b"w:r");
+    let in_paragraph = open.iter().any(|n| n == b"w:p");
+
+    if !in_paragraph {
+        emit(writer, Event::Start(BytesStart::new("w:p")))?;
+    }
+    if !in_run {
+        emit(writer, Event::Start(BytesStart::new("w:r")))?;
+    }
+
+    // multi-line content (text boxes) starts and ends on its own line
+    let multi_line = lines.len() > 1 || in_run;
+    for line in lines {
+        if multi_line {
+            emit(writer, Event::Empty(BytesStart::new("w:br")))?;
+        }
+        emit(
+            writer,
+            Event::Start(BytesStart::new("w:t").with_attributes([("xml:space", "preserve")])),
+        )?;
+        emit(writer, Event::Text(BytesText::new(line)))?;
+        emit(writer, Event::End(BytesEnd::new("w:t")))?;
+    }
+    if multi_line {
+        emit(writer, Event::Empty(BytesStart::new("w:br")))?;
+    }
+
+    if !in_run {
+        emit(writer, Event::End(BytesEnd::new("w:r")))?;
+    }
+    if !in_paragraph {
+        emit(writer, Event::End(BytesEnd::new("w:p")))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_math() {
+        let xml = "<w:p><w:r><w:t>x</w:t></w:r><m:oMath><m:r><m:t>a</m:t></m:r></m:oMath></w:p>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            r#"<w:p><w:r><w:t>x</w:t></w:r><w:r><w:t xml:space="preserve">$a$</w:t></w:r></w:p>"#
+        );
+    }
+
+    #[test]
+    fn test_display_math() {
+        let xml = "<w:body><m:oMathPara><m:oMath><m:r><m:t>a</m:t></m:r></m:oMath></m:oMathPara></w:body>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            r#"<w:body><w:p><w:r><w:t xml:space="preserve">$$a$$</w:t></w:r></w:p></w:body>"#
+        );
+    }
+
+    #[test]
+    fn test_text_box_becomes_runs() {
+        let xml = "<w:p><w:r><w:drawing><wps:txbx><w:txbxContent>\
+            <w:p><w:r><w:t>Boxed</w:t></w:r></w:p><w:p><w:r><w:t>text</w:t></w:r></w:p>\
+            </w:txbxContent></wps:txbx></w:drawing></w:r></w:p>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            concat!(
+                r#"<w:p><w:r><w:br/><w:t xml:space="preserve">Boxed</w:t><w:br/>"#,
+                r#"<w:t xml:space="preserve">text</w:t><w:br/></w:r></w:p>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_picture_next_to_text_box_is_kept() {
+        let xml = "<w:p><w:r><w:drawing><wpg:wgp>\
+            <pic:pic><a:blip r:embed=\"rId5\"/></pic:pic>\
+            <wps:txbx><w:txbxContent><w:p><w:r><w:t>Caption</w:t></w:r></w:p></w:txbxContent></wps:txbx>\
+            </wpg:wgp></w:drawing></w:r></w:p>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            concat!(
+                r#"<w:p><w:r><w:br/><w:t xml:space="preserve">Caption</w:t><w:br/>"#,
+                r#"<w:drawing><wpg:wgp><pic:pic><a:blip r:embed="rId5"/></pic:pic><wps:txbx></wps:txbx></wpg:wgp>"#,
+                "</w:drawing></w:r></w:p>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_block_content_control_is_labelled() {
+        let xml = "<w:body><w:sdt><w:sdtPr><w:alias w:val=\"Summary\"/><w:tag w:val=\"sum\"/></w:sdtPr>\
+            <w:sdtContent><w:p><w:r><w:t>Body</w:t></w:r></w:p></w:sdtContent></w:sdt></w:body>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            concat!(
+                r#"<w:body><w:p><w:r><w:t xml:space="preserve">**Summary (sum):**</w:t></w:r></w:p>"#,
+                "<w:p><w:r><w:t>Body</w:t></w:r></w:p></w:body>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_inline_content_control_is_bracketed() {
+        let xml = "<w:p><w:r><w:t>Name: </w:t></w:r><w:sdt><w:sdtPr><w:tag w:val=\"name\"/></w:sdtPr>\
+            <w:sdtContent><w:r><w:t>Ada</w:t></w:r></w:sdtContent></w:sdt></w:p>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            concat!(
+                r#"<w:p><w:r><w:t>Name: </w:t></w:r><w:r><w:t xml:space="preserve">[name: </w:t></w:r>"#,
+                r#"<w:r><w:t>Ada</w:t></w:r><w:r><w:t xml:space="preserve">]</w:t></w:r></w:p>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_table_content_control_is_unwrapped_without_label() {
+        let xml = "<w:tbl><w:sdt><w:sdtPr><w:alias w:val=\"Rows\"/></w:sdtPr>\
+            <w:sdtContent><w:tr><w:tc><w:p/></w:tc></w:tr></w:sdtContent></w:sdt></w:tbl>";
+        assert_eq!(
+            rewrite_part(xml).unwrap(),
+            "<w:tbl><w:tr><w:tc><w:p/></w:tc></w:tr></w:tbl>"
+        );
+    }
+}
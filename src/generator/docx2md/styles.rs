@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use docx_rust::styles::Styles;
+
+use super::check_style_for_heading;
+
+/// Markdown construct a DOCX paragraph or character style is rendered as
+#[derive(Debug, Clone, PartialEq)]
+pub enum StyleRole {
+    Heading(usize),
+    CodeBlock,
+    Quote,
+    /// GitHub alert kind, e.g. `NOTE` or `WARNING`
+    Callout(String),
+    InlineCode,
+}
+
+impl StyleRole {
+    /// Parses a `docx_style_map` value:
+    /// `h1`-`h6`, `code`, `quote`, `callout`, `callout:<kind>` or `inline_code`
+    pub fn parse(value: &str) -> Option<StyleRole> {
+        let value = value.trim().to_lowercase();
+        match value.as_str() {
+            "code" => Some(StyleRole::CodeBlock),
+            "quote" => Some(StyleRole::Quote),
+            "callout" => Some(StyleRole::Callout("NOTE".to_string())),
+            "inline_code" => Some(StyleRole::InlineCode),
+            _ => {
+                if let Some(kind) = value.strip_prefix("callout:") {
+                    return Some(StyleRole::Callout(kind.trim().to_uppercase()));
+                }
+                let level = value.strip_prefix('h')?.parse::<usize>().ok()?;
+                (1..=6).contains(&level).then_some(StyleRole::Heading(level))
+            }
+        }
+    }
+
+    pub fn is_block(&self) -> bool {
+        matches!(
+            self,
+            StyleRole::CodeBlock | StyleRole::Quote | StyleRole::Callout(_)
+        )
+    }
+}
+
+struct StyleInfo {
+    names: Vec<String>,
+    base: Option<String>,
+}
+
+/// Resolves style IDs to roles from the configured `docx_style_map`,
+/// following `basedOn` inheritance declared in `styles.xml`
+pub struct StyleMap {
+    roles: HashMap<String, StyleRole>,
+    styles: HashMap<String, StyleInfo>,
+}
+
+impl StyleMap {
+    pub fn new(config: &HashMap<String, String>, styles: &Styles) -> Result<Self, String> {
+        let mut roles = HashMap::new();
+        for (key, value) in config {
+            let role = StyleRole::parse(value).ok_or_else(|| {
+                format!(
+                    "Unsupported docx_style_map value '{}' for style '{}', expected one of: \
+                     h1-h6, code, quote, callout, callout:<kind>, inline_code",
+                    value, key
+                )
+            })?;
+            roles.insert(normalize(key), role);
+        }
+
+        let styles = styles
+            .styles
+            .iter()
+            .map(|style| {
+                let mut names = Vec::new();
+                if let Some(name) = &style.name {
+                    names.push(name.value.to_string());
+                }
+                if let Some(aliases) = &style.aliases {
+                    names.extend(aliases.value.split(',').map(|a| a.trim().to_string()));
+                }
+                let info = StyleInfo {
+                    names,
+                    base: style.base.as_ref().map(|b| b.value.to_string()),
+                };
+                (style.style_id.to_string(), info)
+            })
+            .collect();
+
+        Ok(StyleMap { roles, styles })
+    }
+
+    /// Role of a style, checked from the style itself up through its `basedOn` ancestors.
+    /// At each level the configured map wins over the built-in heading names.
+    pub fn resolve(&self, style_id: &str) -> Option<StyleRole> {
+        let mut current = Some(style_id.to_string());
+        let mut visited = Vec::new();
+
+        while let Some(id) = current {
+            if visited.contains(&id) {
+                break;
+            }
+            let info = self.styles.get(&id);
+            let names = info.map(|i| i.names.as_slice()).unwrap_or_default();
+
+            let configured = std::iter::once(&id)
+                .chain(names)
+                .find_map(|key| self.roles.get(&normalize(key)));
+            if let Some(role) = configured {
+                return Some(role.clone());
+            }
+
+            let builtin = std::iter::once(&id)
+                .chain(names)
+                .find_map(|key| check_style_for_heading(key));
+            if let Some((true, level)) = builtin {
+                return Some(StyleRole::Heading(level));
+            }
+
+            current = info.and_then(|i| i.base.clone());
+            visited.push(id);
+        }
+
+        None
+    }
+}
+
+/// Style IDs drop the spaces of their names ("Code Block" -> "CodeBlock"),
+/// and config keys are case-insensitive, so compare on a normalized form
+fn normalize(key: &str) -> String {
+    key.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_style_roles() {
+        assert_eq!(StyleRole::parse(" H2 "), Some(StyleRole::Heading(2)));
+        assert_eq!(StyleRole::parse("callout:warning"), Some(StyleRole::Callout("WARNING".to_string())));
+        assert_eq!(StyleRole::parse("inline_code"), Some(StyleRole::InlineCode));
+        assert_eq!(StyleRole::parse("h7"), None);
+        assert_eq!(StyleRole::parse("heading"), None);
+    }
+
+    #[test]
+    fn test_unknown_style_map_value_is_an_error() {
+        let config = HashMap::from([("Source Code".to_string(), "cod".to_string())]);
+        let error = StyleMap::new(&config, &Styles::new()).err().unwrap();
+        assert!(error.contains("'cod'") && error.contains("'Source Code'"), "{}", error);
+
+        let config = HashMap::from([("Source Code".to_string(), "code".to_string())]);
+        let map = StyleMap::new(&config, &Styles::new()).unwrap();
+        assert_eq!(map.resolve("SourceCode"), Some(StyleRole::CodeBlock));
+    }
+}
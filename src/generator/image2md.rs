@@ -1,6 +1,7 @@
 use crate::config::SETTINGS;
 use base64::Engine;
 use std::fs;
+use std::path::Path;
 
 pub enum ImageProcessingMode {
     Base64,
@@ -61,7 +62,7 @@ pub fn run_with_mode(file_stream: &[u8], mode: ImageProcessingMode) -> Result<St
         }
         ImageProcessingMode::SaveToFile => {
             // Save image to file and return markdown reference
-            let filename = format!("{}.{}", image_name, extension);
+            let filename = unique_file_name(&cfg.image_path, &image_name, extension);
             let file_path = cfg.image_path.join(&filename);
             
             // Ensure the directory exists
@@ -81,6 +82,18 @@ pub fn run_with_mode(file_stream: &[u8], mode: ImageProcessingMode) -> Result<St
     }
 }
 
+/// `stem.extension`, or `stem-2.extension`, `stem-3.extension`... when a file of that name is
+/// already in `dir`. Names are per second, so a document with several images gets numbered
+/// ones instead of overwriting.
+pub fn unique_file_name(dir: &Path, stem: &str, extension: &str) -> String {
+    let mut filename = format!("{}.{}", stem, extension);
+    let mut counter = 1;
+    while dir.join(&filename).exists() {
+        counter += 1;
+        filename = format!("{}-{}.{}", stem, counter, extension);
+    }
+    filename
+}
 
 fn ai_generate_name_from_bytes(file_stream: &[u8], mime_type: &str) -> String {
     let encoded = base64::engine::general_purpose::STANDARD.encode(file_stream);
@@ -171,3 +184,21 @@ fn call_doubao_api(encoded_image: &str, mime_type: &str) -> Result<String, Box<d
     
     Ok(generated_name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_file_name_numbers_taken_names() {
+        let dir = std::env::temp_dir().join(format!("markitup-unique-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(unique_file_name(&dir, "pic-1", "png"), "pic-1.png");
+        fs::write(dir.join("pic-1.png"), b"").unwrap();
+        assert_eq!(unique_file_name(&dir, "pic-1", "png"), "pic-1-2.png");
+        fs::write(dir.join("pic-1-2.png"), b"").unwrap();
+        assert_eq!(unique_file_name(&dir, "pic-1", "png"), "pic-1-3.png");
+        assert_eq!(unique_file_name(&dir, "pic-1", "svg"), "pic-1.svg");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,414 @@
+use std::ops::Range;
+
+use super::pdf_element::{PdfTable, PdfText};
+
+/// Whitespace between runs of a line at least this many font sizes wide separates cells
+const CELL_GAP: f32 = 1.0;
+/// Rows of a table are at most this many font sizes apart, baseline to baseline
+const MAX_ROW_GAP: f32 = 2.5;
+/// A table has at least this many rows with a cell in every column
+const MIN_FULL_ROWS: usize = 3;
+/// Lines of prose have at least this many words, as a median
+const PROSE_WORDS: usize = 4;
+
+/// Runs of a line between gutters
+struct Segment {
+    left: f32,
+    right: f32,
+    texts: Vec<PdfText>,
+}
+
+/// A line of text split into segments
+struct TextRow {
+    baseline: f32,
+    height: f32,
+    segments: Vec<Segment>,
+}
+
+/// A row of a table being built: the runs of each column
+struct GridRow {
+    top: f32,
+    bottom: f32,
+    cells: Vec<Vec<PdfText>>,
+}
+
+impl Segment {
+    fn text(&self) -> String {
+        self.texts.iter().map(|t| t.text.trim()).collect::<Vec<&str>>().join(" ")
+    }
+}
+
+impl GridRow {
+    fn text(&self, column: usize) -> String {
+        self.cells[column].iter().map(|t| t.text.trim()).collect::<Vec<&str>>().join(" ")
+    }
+
+    fn filled(&self) -> Vec<usize> {
+        (0..self.cells.len()).filter(|&c| !self.text(c).is_empty()).collect()
+    }
+
+    fn merge(&mut self, other: GridRow) {
+        self.top = self.top.max(other.top);
+        self.bottom = self.bottom.min(other.bottom);
+        for (cell, texts) in self.cells.iter_mut().zip(other.cells) {
+            cell.extend(texts);
+        }
+    }
+}
+
+/// Finds tables drawn without ruling lines from text aligned in columns over several
+/// consecutive lines. Only confident finds become tables; the runs that aren't in one
+/// are returned.
+///
+/// Lines are split into segments at gutters; a run of lines with several segments each
+/// is a table when enough of them have the same number of segments lined up in the same
+/// columns, and the segments are short, unlike prose set in columns. Lines that continue
+/// a cell are merged into the row above, and leading bold rows make up the header.
+pub fn detect(texts: Vec<PdfText>) -> (Vec<PdfTable>, Vec<PdfText>) {
+    let (texts, mut rest): (Vec<PdfText>, Vec<PdfText>) =
+        texts.into_iter().partition(|t| !t.text.trim().is_empty() && !t.rotated);
+    let rows = text_rows(texts);
+
+    let mut tables = Vec::new();
+    let mut in_table = vec![false; rows.len()];
+    let mut start = 0;
+    while start < rows.len() {
+        let end = region_end(&rows, start);
+        if end == start {
+            start += 1;
+            continue;
+        }
+        if let Some(table) = table_from_rows(&rows, start..end) {
+            tables.push(table);
+            in_table[start..end].fill(true);
+        }
+        start = end;
+    }
+    rest.extend(
+        rows.into_iter()
+            .zip(in_table)
+            .filter(|(_, in_table)| !in_table)
+            .flat_map(|(row, _)| row.segments.into_iter().flat_map(|s| s.texts)),
+    );
+
+    (tables, rest)
+}
+
+/// Whether two consecutive rows are close enough to be rows of one table
+fn close(above: &TextRow, below: &TextRow) -> bool {
+    above.baseline - below.baseline <= above.height.max(below.height) * MAX_ROW_GAP
+}
+
+/// End of the run of close rows from `start` that has several segments on each row,
+/// or single-segment rows between such rows, e.g. a wrapped cell. `start` if there is none.
+fn region_end(rows: &[TextRow], start: usize) -> usize {
+    if rows[start].segments.len() < 2 {
+        return start;
+    }
+    let mut end = start + 1;
+    while end < rows.len()
+        && close(&rows[end - 1], &rows[end])
+        && (rows[end].segments.len() >= 2 || rows[end - 1].segments.len() >= 2)
+    {
+        end += 1;
+    }
+    while rows[end - 1].segments.len() < 2 {
+        end -= 1;
+    }
+    end
+}
+
+/// Groups runs into lines, top to bottom, and splits the lines at gutters
+fn text_rows(mut texts: Vec<PdfText>) -> Vec<TextRow> {
+    texts.sort_by(|a, b| b.y.partial_cmp(&a.y).unwrap_or(std::cmp::Ordering::Equal));
+    let mut lines: Vec<Vec<PdfText>> = Vec::new();
+    for text in texts {
+        match lines.last_mut() {
+            Some(line) if (line[0].y - text.y).abs() <= line[0].height.max(1.0) * 0.3 => line.push(text),
+            _ => lines.push(vec![text]),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|mut line| {
+            line.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
+            let baseline = line[0].y;
+            let height = line.iter().map(|t| t.height).fold(1.0, f32::max);
+            let mut segments: Vec<Segment> = Vec::new();
+            for text in line {
+                let right = text.x + text.width;
+                match segments.last_mut() {
+                    Some(segment) if text.x - segment.right < text.height.max(1.0) * CELL_GAP => {
+                        segment.right = segment.right.max(right);
+                        segment.texts.push(text);
+                    }
+                    _ => segments.push(Segment { left: text.x, right, texts: vec![text] }),
+                }
+            }
+            TextRow { baseline, height, segments }
+        })
+        .collect()
+}
+
+fn table_from_rows(all: &[TextRow], region: Range<usize>) -> Option<PdfTable> {
+    let rows = &all[region.clone()];
+    // the number of columns is the most common number of segments
+    let mut counts: Vec<usize> = rows.iter().map(|row| row.segments.len()).filter(|&n| n >= 2).collect();
+    counts.sort_unstable();
+    let columns = counts
+        .chunk_by(|a, b| a == b)
+        .max_by_key(|run| (run.len(), run[0]))
+        .map(|run| run[0])?;
+    let full: Vec<&TextRow> = rows.iter().filter(|row| row.segments.len() == columns).collect();
+    if full.len() < MIN_FULL_ROWS || full.len() * 2 < rows.len() {
+        return None;
+    }
+
+    // the columns of the full rows must line up without overlapping
+    let bands: Vec<(f32, f32)> = (0..columns)
+        .map(|c| {
+            full.iter()
+                .map(|row| (row.segments[c].left, row.segments[c].right))
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(l, r), (left, right)| (l.min(left), r.max(right)))
+        })
+        .collect();
+    if bands.windows(2).any(|pair| pair[0].1 >= pair[1].0) {
+        return None;
+    }
+
+    // prose in columns, a list with its markers set apart, or labels and their values
+    if (0..columns).any(|c| is_prose(&full, c, bands[c])) {
+        return None;
+    }
+    let first_column: Vec<String> = full.iter().map(|row| row.segments[0].text()).collect();
+    if first_column.iter().all(|text| is_list_marker(text) || text.ends_with(':')) {
+        return None;
+    }
+    // text right next to the region in a column of its own: the region is part of a
+    // page laid out in columns, which goes on above or below it
+    let above = region.start.checked_sub(1).map(|i| &all[i]).filter(|row| close(row, &rows[0]));
+    let below = all.get(region.end).filter(|row| close(&rows[rows.len() - 1], row));
+    let in_later_column = |row: &TextRow| row.segments.iter().all(|segment| segment.left > bands[0].1);
+    if above.into_iter().chain(below).any(in_later_column) {
+        return None;
+    }
+
+    // each segment goes in the column it overlaps most
+    let mut grid: Vec<GridRow> = Vec::new();
+    for row in rows {
+        let mut cells: Vec<Vec<PdfText>> = (0..columns).map(|_| Vec::new()).collect();
+        for segment in &row.segments {
+            let overlap = |band: &(f32, f32)| segment.right.min(band.1) - segment.left.max(band.0);
+            let column = (0..columns)
+                .max_by(|&a, &b| overlap(&bands[a]).partial_cmp(&overlap(&bands[b])).unwrap_or(std::cmp::Ordering::Equal))
+                .unwrap_or_default();
+            cells[column].extend(segment.texts.iter().cloned());
+        }
+        grid.push(GridRow {
+            top: row.baseline + row.height * 0.8,
+            bottom: row.baseline - row.height * 0.2,
+            cells,
+        });
+    }
+
+    let grid = merge_continuations(grid);
+    let header_rows = header_rows(&grid);
+
+    // cells reach halfway into the gutters
+    let edges: Vec<(f32, f32)> = (0..columns)
+        .map(|c| {
+            let left = if c == 0 { bands[c].0 } else { (bands[c - 1].1 + bands[c].0) / 2.0 };
+            let right = if c + 1 == columns { bands[c].1 } else { (bands[c].1 + bands[c + 1].0) / 2.0 };
+            (left, right)
+        })
+        .collect();
+    let cells = grid
+        .into_iter()
+        .map(|row| {
+            row.cells
+                .into_iter()
+                .zip(&edges)
+                .map(|(texts, (left, right))| ((*left, *right, row.bottom, row.top), texts))
+                .collect()
+        })
+        .collect();
+    Some(PdfTable::from_cells(cells, header_rows))
+}
+
+/// Merges lines that continue the cells of the row above: lines without a first cell
+/// that aren't numbers (a total row), and first cells carrying on in lower case
+fn merge_continuations(grid: Vec<GridRow>) -> Vec<GridRow> {
+    let mut merged: Vec<GridRow> = Vec::new();
+    for row in grid {
+        let filled = row.filled();
+        let continues = merged.last().is_some_and(|previous| {
+            let previous_filled = previous.filled();
+            let wrapped = !filled.contains(&0) && filled.iter().any(|&c| !is_number(&row.text(c)));
+            let label_wrapped = row.text(0).starts_with(char::is_lowercase)
+                && (filled == [0] || previous_filled == [0]);
+            wrapped || label_wrapped
+        });
+        match merged.last_mut() {
+            Some(previous) if continues => previous.merge(row),
+            _ => merged.push(row),
+        }
+    }
+    merged
+}
+
+/// Leading rows set in bold, when the rows below them aren't
+fn header_rows(grid: &[GridRow]) -> usize {
+    let bold = |row: &GridRow| row.cells.iter().flatten().all(|t| t.bold);
+    let leading = grid.iter().take_while(|row| bold(row)).count();
+    match leading {
+        0 => 1,
+        n if n == grid.len() => 1,
+        n => n,
+    }
+}
+
+/// Whether column `c` reads like a column of prose: lines of several words that mostly
+/// fill the column's width, where table cells are short or ragged
+fn is_prose(rows: &[&TextRow], c: usize, (left, right): (f32, f32)) -> bool {
+    let mut words: Vec<usize> = rows
+        .iter()
+        .map(|row| row.segments[c].text().split_whitespace().count())
+        .collect();
+    words.sort_unstable();
+    let filling = rows
+        .iter()
+        .filter(|row| row.segments[c].right - row.segments[c].left >= (right - left) * 0.85)
+        .count();
+    words[words.len() / 2] >= PROSE_WORDS && filling * 2 > rows.len()
+}
+
+fn is_number(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && text.chars().any(|c| c.is_ascii_digit())
+        && text.chars().all(|c| c.is_ascii_digit() || " .,%()-–+$€£¥".contains(c))
+}
+
+/// Bullets and short numbering such as "1." or "a)"
+fn is_list_marker(text: &str) -> bool {
+    let text = text.trim();
+    match text.chars().count() {
+        1 => !text.chars().all(char::is_alphanumeric),
+        2..=4 => text.ends_with(['.', ')']) && text[..text.len() - 1].chars().all(char::is_alphanumeric),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10pt run, 5 units per character
+    fn text(text: &str, x: f32, y: f32) -> PdfText {
+        PdfText {
+            text: text.to_string(),
+            x,
+            y,
+            width: 5.0 * text.chars().count() as f32,
+            height: 10.0,
+            space_width: 2.5,
+            ..Default::default()
+        }
+    }
+
+    /// One run per cell, columns at x = 72, 200 and 330, rows 14 units apart
+    fn grid(rows: &[&[&str]]) -> Vec<PdfText> {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(r, cells)| {
+                cells
+                    .iter()
+                    .zip([72.0, 200.0, 330.0])
+                    .filter(|(cell, _)| !cell.is_empty())
+                    .map(move |(cell, x)| text(cell, x, 700.0 - 14.0 * r as f32))
+            })
+            .collect()
+    }
+
+    /// Texts of the cells of `table`, row by row
+    fn cells(table: &mut PdfTable) -> Vec<Vec<String>> {
+        table
+            .get_sorted_elements()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.iter().map(|t| t.text.as_str()).collect::<Vec<_>>().join(" "))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_three_by_three_table() {
+        let mut texts = grid(&[&["Item", "Qty", "Price"], &["Apple", "3", "1.20"], &["Pear", "12", "0.80"]]);
+        for header in texts.iter_mut().take(3) {
+            header.bold = true;
+        }
+        texts.push(text("A caption well below the table.", 72.0, 600.0));
+
+        let (mut tables, rest) = detect(texts);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].header_rows(), 1);
+        assert_eq!(
+            cells(&mut tables[0]),
+            vec![vec!["Item", "Qty", "Price"], vec!["Apple", "3", "1.20"], vec!["Pear", "12", "0.80"]]
+        );
+        let rest: Vec<&str> = rest.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(rest, vec!["A caption well below the table."]);
+    }
+
+    #[test]
+    fn test_wrapped_cells_join_the_row_above() {
+        let texts = grid(&[
+            &["Item", "Qty", "Price"],
+            &["Apple", "3", "1.20"],
+            &["", "", "per kilo"],
+            &["Pear", "12", "0.80"],
+        ]);
+        let (mut tables, _) = detect(texts);
+        assert_eq!(tables.len(), 1);
+        let cells = cells(&mut tables[0]);
+        assert_eq!(cells.len(), 3, "{:?}", cells);
+        assert_eq!(cells[1], vec!["Apple", "3", "1.20 per kilo"]);
+    }
+
+    #[test]
+    fn test_two_columns_of_prose_are_not_a_table() {
+        let left = [
+            "The quick brown fox jumps over",
+            "the lazy dog and keeps running",
+            "until the field comes to an end.",
+            "Then it rests under a tall tree",
+        ];
+        let right = [
+            "Meanwhile the dog wakes up and",
+            "looks around for the fox that has",
+            "long since left the farm behind",
+            "and wonders where it has gone to.",
+        ];
+        let mut texts = Vec::new();
+        for (i, (l, r)) in left.iter().zip(right).enumerate() {
+            let y = 700.0 - 14.0 * i as f32;
+            texts.push(PdfText { width: 200.0, ..text(l, 72.0, y) });
+            texts.push(PdfText { width: 200.0, ..text(r, 320.0, y) });
+        }
+
+        let (tables, rest) = detect(texts);
+        assert!(tables.is_empty());
+        assert_eq!(rest.len(), 8);
+    }
+
+    #[test]
+    fn test_labels_and_values_are_not_a_table() {
+        let texts = grid(&[&["Name:", "Ada Lovelace"], &["Born:", "1815"], &["Field:", "Mathematics"]]);
+        let (tables, rest) = detect(texts);
+        assert!(tables.is_empty());
+        assert_eq!(rest.len(), 6);
+    }
+}
@@ -0,0 +1,197 @@
+use lopdf::encryption::crypt_filters::{CryptFilter, Rc4CryptFilter};
+use lopdf::encryption::PasswordAlgorithm;
+use lopdf::{Document, Object};
+use md5::{Digest, Md5};
+
+use super::PdfOpenError;
+
+/// The padding string of the standard security handler
+const PAD_BYTES: [u8; 32] = [
+    0x28, 0xBF, 0x4E, 0x5E, 0x4E, 0x75, 0x8A, 0x41, 0x64, 0x00, 0x4E, 0x56, 0xFF, 0xFA, 0x01, 0x08, 0x2E, 0x2E, 0x00,
+    0xB6, 0xD0, 0x68, 0x3E, 0x80, 0x2F, 0x0C, 0xA9, 0xFE, 0x64, 0x53, 0x69, 0x7A,
+];
+
+/// Decrypts a document with its user or owner password.
+///
+/// lopdf derives the file key from whatever password it is given, which is only right
+/// for the user password before revision 5; an owner password of an older handler is
+/// turned into the user password first.
+pub fn decrypt(document: &mut Document, password: &str) -> Result<(), PdfOpenError> {
+    let invalid = |e: lopdf::encryption::DecryptionError| PdfOpenError::Invalid(format!("Failed to decrypt: {}", e));
+    let algorithm = PasswordAlgorithm::try_from(&*document).map_err(|e| PdfOpenError::Invalid(e.to_string()))?;
+    let password = algorithm.sanitize_password(password).map_err(invalid)?;
+
+    let password = if algorithm.authenticate_user_password(document, &password).is_ok() {
+        password
+    } else if algorithm.authenticate_owner_password(document, &password).is_ok() {
+        user_password(document, &password).unwrap_or(password)
+    } else {
+        return Err(PdfOpenError::IncorrectPassword);
+    };
+
+    document
+        .decrypt_raw(&password)
+        .map_err(|e| PdfOpenError::Invalid(format!("Failed to decrypt: {}", e)))
+}
+
+/// Recovers the padded user password from the owner password (Algorithm 7 of ISO 32000-2);
+/// `None` from revision 5 on, where the owner password opens the file itself
+fn user_password(document: &Document, owner_password: &[u8]) -> Option<Vec<u8>> {
+    let encrypt = document.get_encrypted().ok()?;
+    let revision = encrypt.get(b"R").and_then(Object::as_i64).ok()?;
+    if !(2..=4).contains(&revision) {
+        return None;
+    }
+    let length = encrypt.get(b"Length").and_then(Object::as_i64).unwrap_or(40);
+    let owner_value = encrypt.get(b"O").and_then(Object::as_str).ok()?;
+
+    let len = owner_password.len().min(32);
+    let mut hasher = Md5::new();
+    hasher.update(&owner_password[..len]);
+    hasher.update(&PAD_BYTES[..32 - len]);
+    let mut hash = hasher.finalize();
+    let n = if revision >= 3 {
+        for _ in 0..50 {
+            hash = Md5::digest(hash);
+        }
+        (length / 8).clamp(5, 16) as usize
+    } else {
+        5
+    };
+
+    let rounds = if revision >= 3 { 19 } else { 0 };
+    let mut user = owner_value.to_vec();
+    for i in (0..=rounds).rev() {
+        let key: Vec<u8> = hash[..n].iter().map(|b| b ^ i).collect();
+        user = Rc4CryptFilter.decrypt(&key, &user).ok()?;
+    }
+    Some(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::encryption::crypt_filters::{Aes128CryptFilter, Aes256CryptFilter};
+    use lopdf::{dictionary, EncryptionState, EncryptionVersion, Permissions, Stream, StringFormat};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    const CONTENT: &[u8] = b"BT /F1 12 Tf (Secret) Tj ET";
+    /// Revisions of the standard security handler the tests encrypt with
+    const REVISIONS: [i64; 4] = [2, 3, 4, 6];
+
+    fn plain_document() -> Document {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let content_id = doc.add_object(Stream::new(dictionary! {}, CONTENT.to_vec()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_id.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        let id = Object::String(b"0123456789abcdef".to_vec(), StringFormat::Hexadecimal);
+        doc.trailer.set("ID", vec![id.clone(), id]);
+        doc
+    }
+
+    /// A document encrypted with the handler of `revision` 2, 3, 4 (AES-128) or 6 (AES-256), loaded back
+    fn encrypted_document(revision: i64) -> Document {
+        let mut doc = plain_document();
+        let (owner_password, user_password, permissions) = ("owner", "user", Permissions::PRINTABLE);
+        let version = match revision {
+            2 => EncryptionVersion::V1 { document: &doc, owner_password, user_password, permissions },
+            3 => EncryptionVersion::V2 { document: &doc, owner_password, user_password, key_length: 128, permissions },
+            4 => {
+                let filter: Arc<dyn CryptFilter> = Arc::new(Aes128CryptFilter);
+                EncryptionVersion::V4 {
+                    document: &doc,
+                    encrypt_metadata: true,
+                    crypt_filters: BTreeMap::from([(b"StdCF".to_vec(), filter)]),
+                    stream_filter: b"StdCF".to_vec(),
+                    string_filter: b"StdCF".to_vec(),
+                    owner_password,
+                    user_password,
+                    permissions,
+                }
+            }
+            _ => {
+                let filter: Arc<dyn CryptFilter> = Arc::new(Aes256CryptFilter);
+                EncryptionVersion::V5 {
+                    encrypt_metadata: true,
+                    crypt_filters: BTreeMap::from([(b"StdCF".to_vec(), filter)]),
+                    file_encryption_key: &[7; 32],
+                    stream_filter: b"StdCF".to_vec(),
+                    string_filter: b"StdCF".to_vec(),
+                    owner_password,
+                    user_password,
+                    permissions,
+                }
+            }
+        };
+        let state = EncryptionState::try_from(version).unwrap();
+        doc.encrypt(&state).unwrap();
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        let doc = Document::load_mem(&bytes).unwrap();
+        assert!(doc.is_encrypted());
+        doc
+    }
+
+    fn page_content(doc: &Document) -> Vec<u8> {
+        let page_id = doc.page_iter().next().unwrap();
+        doc.get_page_content(page_id).unwrap()
+    }
+
+    #[test]
+    fn test_decrypt_with_user_password() {
+        for revision in REVISIONS {
+            let mut doc = encrypted_document(revision);
+            decrypt(&mut doc, "user").unwrap();
+            assert_eq!(page_content(&doc), CONTENT, "revision {}", revision);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_with_owner_password() {
+        for revision in REVISIONS {
+            let mut doc = encrypted_document(revision);
+            decrypt(&mut doc, "owner").unwrap();
+            assert_eq!(page_content(&doc), CONTENT, "revision {}", revision);
+        }
+    }
+
+    #[test]
+    fn test_owner_password_recovers_user_password() {
+        for revision in 2..=4 {
+            let doc = encrypted_document(revision);
+            let algorithm = PasswordAlgorithm::try_from(&doc).unwrap();
+            let owner = algorithm.sanitize_password("owner").unwrap();
+            let user = user_password(&doc, &owner).unwrap();
+            assert!(algorithm.authenticate_user_password(&doc, &user).is_ok(), "revision {}", revision);
+            assert!(user.starts_with(b"user"));
+        }
+        // from revision 5 on the owner password opens the file itself
+        let doc = encrypted_document(6);
+        assert_eq!(user_password(&doc, b"owner"), None);
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_password() {
+        for revision in REVISIONS {
+            let mut doc = encrypted_document(revision);
+            assert_eq!(decrypt(&mut doc, "wrong"), Err(PdfOpenError::IncorrectPassword));
+            assert!(doc.is_encrypted());
+        }
+    }
+}
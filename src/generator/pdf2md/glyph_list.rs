@@ -0,0 +1,1063 @@
+/// Adobe Glyph List names of the Latin, Greek, punctuation, symbol and ligature glyphs,
+/// sorted by name, for fonts whose `/Differences` name glyphs without a `/ToUnicode` map
+pub static GLYPH_NAMES: [(&str, char); 1059] = [
+    ("A", '\u{0041}'),
+    ("AE", '\u{00C6}'),
+    ("AEacute", '\u{01FC}'),
+    ("AEmacron", '\u{01E2}'),
+    ("Aacute", '\u{00C1}'),
+    ("Abreve", '\u{0102}'),
+    ("Acaron", '\u{01CD}'),
+    ("Acircumflex", '\u{00C2}'),
+    ("Adblgrave", '\u{0200}'),
+    ("Adieresis", '\u{00C4}'),
+    ("Adieresismacron", '\u{01DE}'),
+    ("Adotmacron", '\u{01E0}'),
+    ("Agrave", '\u{00C0}'),
+    ("Ainvertedbreve", '\u{0202}'),
+    ("Alpha", '\u{0391}'),
+    ("Alphatonos", '\u{0386}'),
+    ("Amacron", '\u{0100}'),
+    ("Aogonek", '\u{0104}'),
+    ("Aring", '\u{00C5}'),
+    ("Aringacute", '\u{01FA}'),
+    ("Atilde", '\u{00C3}'),
+    ("B", '\u{0042}'),
+    ("Beta", '\u{0392}'),
+    ("Bhook", '\u{0181}'),
+    ("Btopbar", '\u{0182}'),
+    ("C", '\u{0043}'),
+    ("Cacute", '\u{0106}'),
+    ("Ccaron", '\u{010C}'),
+    ("Ccedilla", '\u{00C7}'),
+    ("Ccircumflex", '\u{0108}'),
+    ("Cdot", '\u{010A}'),
+    ("Cdotaccent", '\u{010A}'),
+    ("Chi", '\u{03A7}'),
+    ("Chook", '\u{0187}'),
+    ("D", '\u{0044}'),
+    ("DZ", '\u{01F1}'),
+    ("DZcaron", '\u{01C4}'),
+    ("Dafrican", '\u{0189}'),
+    ("Dbar", '\u{0110}'),
+    ("Dcaron", '\u{010E}'),
+    ("Dcroat", '\u{0110}'),
+    ("Deicoptic", '\u{03EE}'),
+    ("Delta", '\u{2206}'),
+    ("Deltagreek", '\u{0394}'),
+    ("Dhook", '\u{018A}'),
+    ("Digammagreek", '\u{03DC}'),
+    ("Dslash", '\u{0110}'),
+    ("Dtopbar", '\u{018B}'),
+    ("Dz", '\u{01F2}'),
+    ("Dzcaron", '\u{01C5}'),
+    ("E", '\u{0045}'),
+    ("Eacute", '\u{00C9}'),
+    ("Ebreve", '\u{0114}'),
+    ("Ecaron", '\u{011A}'),
+    ("Ecircumflex", '\u{00CA}'),
+    ("Edblgrave", '\u{0204}'),
+    ("Edieresis", '\u{00CB}'),
+    ("Edot", '\u{0116}'),
+    ("Edotaccent", '\u{0116}'),
+    ("Egrave", '\u{00C8}'),
+    ("Einvertedbreve", '\u{0206}'),
+    ("Emacron", '\u{0112}'),
+    ("Eng", '\u{014A}'),
+    ("Eogonek", '\u{0118}'),
+    ("Eopen", '\u{0190}'),
+    ("Epsilon", '\u{0395}'),
+    ("Epsilontonos", '\u{0388}'),
+    ("Ereversed", '\u{018E}'),
+    ("Esh", '\u{01A9}'),
+    ("Eta", '\u{0397}'),
+    ("Etatonos", '\u{0389}'),
+    ("Eth", '\u{00D0}'),
+    ("Euro", '\u{20AC}'),
+    ("Ezh", '\u{01B7}'),
+    ("Ezhcaron", '\u{01EE}'),
+    ("Ezhreversed", '\u{01B8}'),
+    ("F", '\u{0046}'),
+    ("Feicoptic", '\u{03E4}'),
+    ("Fhook", '\u{0191}'),
+    ("G", '\u{0047}'),
+    ("Gacute", '\u{01F4}'),
+    ("Gamma", '\u{0393}'),
+    ("Gammaafrican", '\u{0194}'),
+    ("Gangiacoptic", '\u{03EA}'),
+    ("Gbreve", '\u{011E}'),
+    ("Gcaron", '\u{01E6}'),
+    ("Gcedilla", '\u{0122}'),
+    ("Gcircumflex", '\u{011C}'),
+    ("Gcommaaccent", '\u{0122}'),
+    ("Gdot", '\u{0120}'),
+    ("Gdotaccent", '\u{0120}'),
+    ("Germandbls", '\u{0053}'),
+    ("Ghook", '\u{0193}'),
+    ("Gstroke", '\u{01E4}'),
+    ("H", '\u{0048}'),
+    ("H18533", '\u{25CF}'),
+    ("H18543", '\u{25AA}'),
+    ("H18551", '\u{25AB}'),
+    ("H22073", '\u{25A1}'),
+    ("Hbar", '\u{0126}'),
+    ("Hcircumflex", '\u{0124}'),
+    ("Horicoptic", '\u{03E8}'),
+    ("I", '\u{0049}'),
+    ("IJ", '\u{0132}'),
+    ("Iacute", '\u{00CD}'),
+    ("Ibreve", '\u{012C}'),
+    ("Icaron", '\u{01CF}'),
+    ("Icircumflex", '\u{00CE}'),
+    ("Idblgrave", '\u{0208}'),
+    ("Idieresis", '\u{00CF}'),
+    ("Idot", '\u{0130}'),
+    ("Idotaccent", '\u{0130}'),
+    ("Ifractur", '\u{2111}'),
+    ("Ifraktur", '\u{2111}'),
+    ("Igrave", '\u{00CC}'),
+    ("Iinvertedbreve", '\u{020A}'),
+    ("Imacron", '\u{012A}'),
+    ("Iogonek", '\u{012E}'),
+    ("Iota", '\u{0399}'),
+    ("Iotaafrican", '\u{0196}'),
+    ("Iotadieresis", '\u{03AA}'),
+    ("Iotatonos", '\u{038A}'),
+    ("Istroke", '\u{0197}'),
+    ("Itilde", '\u{0128}'),
+    ("J", '\u{004A}'),
+    ("Jcircumflex", '\u{0134}'),
+    ("K", '\u{004B}'),
+    ("Kappa", '\u{039A}'),
+    ("Kcaron", '\u{01E8}'),
+    ("Kcedilla", '\u{0136}'),
+    ("Kcommaaccent", '\u{0136}'),
+    ("Kheicoptic", '\u{03E6}'),
+    ("Khook", '\u{0198}'),
+    ("Koppagreek", '\u{03DE}'),
+    ("L", '\u{004C}'),
+    ("LJ", '\u{01C7}'),
+    ("Lacute", '\u{0139}'),
+    ("Lambda", '\u{039B}'),
+    ("Lcaron", '\u{013D}'),
+    ("Lcedilla", '\u{013B}'),
+    ("Lcommaaccent", '\u{013B}'),
+    ("Ldot", '\u{013F}'),
+    ("Ldotaccent", '\u{013F}'),
+    ("Lj", '\u{01C8}'),
+    ("Lslash", '\u{0141}'),
+    ("M", '\u{004D}'),
+    ("Mturned", '\u{019C}'),
+    ("Mu", '\u{039C}'),
+    ("N", '\u{004E}'),
+    ("NJ", '\u{01CA}'),
+    ("Nacute", '\u{0143}'),
+    ("Ncaron", '\u{0147}'),
+    ("Ncedilla", '\u{0145}'),
+    ("Ncommaaccent", '\u{0145}'),
+    ("Ng", '\u{014A}'),
+    ("Nhookleft", '\u{019D}'),
+    ("Nj", '\u{01CB}'),
+    ("Ntilde", '\u{00D1}'),
+    ("Nu", '\u{039D}'),
+    ("O", '\u{004F}'),
+    ("OE", '\u{0152}'),
+    ("Oacute", '\u{00D3}'),
+    ("Obreve", '\u{014E}'),
+    ("Ocaron", '\u{01D1}'),
+    ("Ocenteredtilde", '\u{019F}'),
+    ("Ocircumflex", '\u{00D4}'),
+    ("Odblacute", '\u{0150}'),
+    ("Odblgrave", '\u{020C}'),
+    ("Odieresis", '\u{00D6}'),
+    ("Ograve", '\u{00D2}'),
+    ("Ohm", '\u{2126}'),
+    ("Ohorn", '\u{01A0}'),
+    ("Ohungarumlaut", '\u{0150}'),
+    ("Oi", '\u{01A2}'),
+    ("Oinvertedbreve", '\u{020E}'),
+    ("Omacron", '\u{014C}'),
+    ("Omega", '\u{2126}'),
+    ("Omegagreek", '\u{03A9}'),
+    ("Omegatonos", '\u{038F}'),
+    ("Omicron", '\u{039F}'),
+    ("Omicrontonos", '\u{038C}'),
+    ("Oogonek", '\u{01EA}'),
+    ("Oogonekmacron", '\u{01EC}'),
+    ("Oopen", '\u{0186}'),
+    ("Oslash", '\u{00D8}'),
+    ("Oslashacute", '\u{01FE}'),
+    ("Ostrokeacute", '\u{01FE}'),
+    ("Otilde", '\u{00D5}'),
+    ("P", '\u{0050}'),
+    ("Phi", '\u{03A6}'),
+    ("Phook", '\u{01A4}'),
+    ("Pi", '\u{03A0}'),
+    ("Psi", '\u{03A8}'),
+    ("Q", '\u{0051}'),
+    ("R", '\u{0052}'),
+    ("Racute", '\u{0154}'),
+    ("Rcaron", '\u{0158}'),
+    ("Rcedilla", '\u{0156}'),
+    ("Rcommaaccent", '\u{0156}'),
+    ("Rdblgrave", '\u{0210}'),
+    ("Rfractur", '\u{211C}'),
+    ("Rfraktur", '\u{211C}'),
+    ("Rho", '\u{03A1}'),
+    ("Rinvertedbreve", '\u{0212}'),
+    ("S", '\u{0053}'),
+    ("SS", '\u{0053}'),
+    ("Sacute", '\u{015A}'),
+    ("Sampigreek", '\u{03E0}'),
+    ("Scaron", '\u{0160}'),
+    ("Scedilla", '\u{015E}'),
+    ("Schwa", '\u{018F}'),
+    ("Scircumflex", '\u{015C}'),
+    ("Scommaaccent", '\u{0218}'),
+    ("Sheicoptic", '\u{03E2}'),
+    ("Shimacoptic", '\u{03EC}'),
+    ("Sigma", '\u{03A3}'),
+    ("Stigmagreek", '\u{03DA}'),
+    ("T", '\u{0054}'),
+    ("Tau", '\u{03A4}'),
+    ("Tbar", '\u{0166}'),
+    ("Tcaron", '\u{0164}'),
+    ("Tcedilla", '\u{0162}'),
+    ("Tcommaaccent", '\u{0162}'),
+    ("Theta", '\u{0398}'),
+    ("Thook", '\u{01AC}'),
+    ("Thorn", '\u{00DE}'),
+    ("Tonefive", '\u{01BC}'),
+    ("Tonesix", '\u{0184}'),
+    ("Tonetwo", '\u{01A7}'),
+    ("Tretroflexhook", '\u{01AE}'),
+    ("U", '\u{0055}'),
+    ("Uacute", '\u{00DA}'),
+    ("Ubreve", '\u{016C}'),
+    ("Ucaron", '\u{01D3}'),
+    ("Ucircumflex", '\u{00DB}'),
+    ("Udblacute", '\u{0170}'),
+    ("Udblgrave", '\u{0214}'),
+    ("Udieresis", '\u{00DC}'),
+    ("Udieresisacute", '\u{01D7}'),
+    ("Udieresiscaron", '\u{01D9}'),
+    ("Udieresisgrave", '\u{01DB}'),
+    ("Udieresismacron", '\u{01D5}'),
+    ("Ugrave", '\u{00D9}'),
+    ("Uhorn", '\u{01AF}'),
+    ("Uhungarumlaut", '\u{0170}'),
+    ("Uinvertedbreve", '\u{0216}'),
+    ("Umacron", '\u{016A}'),
+    ("Uogonek", '\u{0172}'),
+    ("Upsilon", '\u{03A5}'),
+    ("Upsilon1", '\u{03D2}'),
+    ("Upsilonacutehooksymbolgreek", '\u{03D3}'),
+    ("Upsilonafrican", '\u{01B1}'),
+    ("Upsilondieresis", '\u{03AB}'),
+    ("Upsilondieresishooksymbolgreek", '\u{03D4}'),
+    ("Upsilonhooksymbol", '\u{03D2}'),
+    ("Upsilontonos", '\u{038E}'),
+    ("Uring", '\u{016E}'),
+    ("Utilde", '\u{0168}'),
+    ("V", '\u{0056}'),
+    ("Vhook", '\u{01B2}'),
+    ("W", '\u{0057}'),
+    ("Wcircumflex", '\u{0174}'),
+    ("X", '\u{0058}'),
+    ("Xi", '\u{039E}'),
+    ("Y", '\u{0059}'),
+    ("Yacute", '\u{00DD}'),
+    ("Ycircumflex", '\u{0176}'),
+    ("Ydieresis", '\u{0178}'),
+    ("Yhook", '\u{01B3}'),
+    ("Z", '\u{005A}'),
+    ("Zacute", '\u{0179}'),
+    ("Zcaron", '\u{017D}'),
+    ("Zdot", '\u{017B}'),
+    ("Zdotaccent", '\u{017B}'),
+    ("Zeta", '\u{0396}'),
+    ("Zstroke", '\u{01B5}'),
+    ("a", '\u{0061}'),
+    ("aacute", '\u{00E1}'),
+    ("abreve", '\u{0103}'),
+    ("acaron", '\u{01CE}'),
+    ("acircumflex", '\u{00E2}'),
+    ("acute", '\u{00B4}'),
+    ("adblgrave", '\u{0201}'),
+    ("adieresis", '\u{00E4}'),
+    ("adieresismacron", '\u{01DF}'),
+    ("adotmacron", '\u{01E1}'),
+    ("ae", '\u{00E6}'),
+    ("aeacute", '\u{01FD}'),
+    ("aemacron", '\u{01E3}'),
+    ("afii00208", '\u{2015}'),
+    ("afii08941", '\u{20A4}'),
+    ("afii299", '\u{200E}'),
+    ("afii300", '\u{200F}'),
+    ("afii301", '\u{200D}'),
+    ("afii57636", '\u{20AA}'),
+    ("afii61248", '\u{2105}'),
+    ("afii61289", '\u{2113}'),
+    ("afii61352", '\u{2116}'),
+    ("afii61573", '\u{202C}'),
+    ("afii61574", '\u{202D}'),
+    ("afii61575", '\u{202E}'),
+    ("afii61664", '\u{200C}'),
+    ("agrave", '\u{00E0}'),
+    ("ainvertedbreve", '\u{0203}'),
+    ("aleph", '\u{2135}'),
+    ("allequal", '\u{224C}'),
+    ("alpha", '\u{03B1}'),
+    ("alphatonos", '\u{03AC}'),
+    ("amacron", '\u{0101}'),
+    ("ampersand", '\u{0026}'),
+    ("angle", '\u{2220}'),
+    ("angstrom", '\u{212B}'),
+    ("anoteleia", '\u{0387}'),
+    ("aogonek", '\u{0105}'),
+    ("approaches", '\u{2250}'),
+    ("approxequal", '\u{2248}'),
+    ("approxequalorimage", '\u{2252}'),
+    ("approximatelyequal", '\u{2245}'),
+    ("aring", '\u{00E5}'),
+    ("aringacute", '\u{01FB}'),
+    ("arrowboth", '\u{2194}'),
+    ("arrowbothv", '\u{2195}'),
+    ("arrowbt", '\u{2193}'),
+    ("arrowdashdown", '\u{21E3}'),
+    ("arrowdashleft", '\u{21E0}'),
+    ("arrowdashright", '\u{21E2}'),
+    ("arrowdashup", '\u{21E1}'),
+    ("arrowdblboth", '\u{21D4}'),
+    ("arrowdblbothv", '\u{21D5}'),
+    ("arrowdbldown", '\u{21D3}'),
+    ("arrowdblleft", '\u{21D0}'),
+    ("arrowdblright", '\u{21D2}'),
+    ("arrowdbltp", '\u{21D1}'),
+    ("arrowdblup", '\u{21D1}'),
+    ("arrowdblvertex", '\u{21D5}'),
+    ("arrowdown", '\u{2193}'),
+    ("arrowdownleft", '\u{2199}'),
+    ("arrowdownright", '\u{2198}'),
+    ("arrowdownwhite", '\u{21E9}'),
+    ("arrowleft", '\u{2190}'),
+    ("arrowleftbothalf", '\u{21BD}'),
+    ("arrowleftdbl", '\u{21D0}'),
+    ("arrowleftdblstroke", '\u{21CD}'),
+    ("arrowleftoverright", '\u{21C6}'),
+    ("arrowlefttophalf", '\u{21BC}'),
+    ("arrowleftwhite", '\u{21E6}'),
+    ("arrownortheast", '\u{2197}'),
+    ("arrownorthwest", '\u{2196}'),
+    ("arrowright", '\u{2192}'),
+    ("arrowrightbothalf", '\u{21C1}'),
+    ("arrowrightdblstroke", '\u{21CF}'),
+    ("arrowrightoverleft", '\u{21C4}'),
+    ("arrowrighttophalf", '\u{21C0}'),
+    ("arrowrightwhite", '\u{21E8}'),
+    ("arrowsoutheast", '\u{2198}'),
+    ("arrowsouthwest", '\u{2199}'),
+    ("arrowtableft", '\u{21E4}'),
+    ("arrowtabright", '\u{21E5}'),
+    ("arrowtp", '\u{2191}'),
+    ("arrowup", '\u{2191}'),
+    ("arrowupdn", '\u{2195}'),
+    ("arrowupdnbse", '\u{21A8}'),
+    ("arrowupdownbase", '\u{21A8}'),
+    ("arrowupleft", '\u{2196}'),
+    ("arrowupleftofdown", '\u{21C5}'),
+    ("arrowupright", '\u{2197}'),
+    ("arrowupwhite", '\u{21E7}'),
+    ("arrowvertex", '\u{2195}'),
+    ("asciicircum", '\u{005E}'),
+    ("asciitilde", '\u{007E}'),
+    ("asterisk", '\u{002A}'),
+    ("asteriskcentered", '\u{2217}'),
+    ("asteriskmath", '\u{2217}'),
+    ("asterism", '\u{2042}'),
+    ("asymptoticallyequal", '\u{2243}'),
+    ("at", '\u{0040}'),
+    ("atilde", '\u{00E3}'),
+    ("b", '\u{0062}'),
+    ("backslash", '\u{005C}'),
+    ("backslashBig", '\u{005C}'),
+    ("backslashBigg", '\u{005C}'),
+    ("backslashbig", '\u{005C}'),
+    ("backslashbigg", '\u{005C}'),
+    ("bar", '\u{007C}'),
+    ("bardbl", '\u{2225}'),
+    ("bardblex", '\u{2016}'),
+    ("barex", '\u{007C}'),
+    ("because", '\u{2235}'),
+    ("beta", '\u{03B2}'),
+    ("betasymbolgreek", '\u{03D0}'),
+    ("blackcircle", '\u{25CF}'),
+    ("blackdiamond", '\u{25C6}'),
+    ("blackdownpointingtriangle", '\u{25BC}'),
+    ("blackleftpointingpointer", '\u{25C4}'),
+    ("blackleftpointingtriangle", '\u{25C0}'),
+    ("blacklowerlefttriangle", '\u{25E3}'),
+    ("blacklowerrighttriangle", '\u{25E2}'),
+    ("blackrectangle", '\u{25AC}'),
+    ("blackrightpointingpointer", '\u{25BA}'),
+    ("blackrightpointingtriangle", '\u{25B6}'),
+    ("blacksmallsquare", '\u{25AA}'),
+    ("blacksquare", '\u{25A0}'),
+    ("blackupperlefttriangle", '\u{25E4}'),
+    ("blackupperrighttriangle", '\u{25E5}'),
+    ("blackuppointingsmalltriangle", '\u{25B4}'),
+    ("blackuppointingtriangle", '\u{25B2}'),
+    ("braceex", '\u{007C}'),
+    ("braceleft", '\u{007B}'),
+    ("braceleftBig", '\u{007B}'),
+    ("braceleftBigg", '\u{007B}'),
+    ("braceleftbig", '\u{007B}'),
+    ("braceleftbigg", '\u{007B}'),
+    ("braceleftmid", '\u{007C}'),
+    ("braceright", '\u{007D}'),
+    ("bracerightBig", '\u{007D}'),
+    ("bracerightBigg", '\u{007D}'),
+    ("bracerightbig", '\u{007D}'),
+    ("bracerightbigg", '\u{007D}'),
+    ("bracerightmid", '\u{2016}'),
+    ("bracketleft", '\u{005B}'),
+    ("bracketleftBig", '\u{005B}'),
+    ("bracketleftBigg", '\u{005B}'),
+    ("bracketleftbig", '\u{005B}'),
+    ("bracketleftbigg", '\u{005B}'),
+    ("bracketright", '\u{005D}'),
+    ("bracketrightBig", '\u{005D}'),
+    ("bracketrightBigg", '\u{005D}'),
+    ("bracketrightbig", '\u{005D}'),
+    ("bracketrightbigg", '\u{005D}'),
+    ("brokenbar", '\u{00A6}'),
+    ("bstroke", '\u{0180}'),
+    ("btopbar", '\u{0183}'),
+    ("bullet", '\u{2022}'),
+    ("bulletinverse", '\u{25D8}'),
+    ("bulletoperator", '\u{2219}'),
+    ("bullseye", '\u{25CE}'),
+    ("c", '\u{0063}'),
+    ("cacute", '\u{0107}'),
+    ("capslock", '\u{21EA}'),
+    ("careof", '\u{2105}'),
+    ("carriagereturn", '\u{21B5}'),
+    ("ccaron", '\u{010D}'),
+    ("ccedilla", '\u{00E7}'),
+    ("ccircumflex", '\u{0109}'),
+    ("cdot", '\u{010B}'),
+    ("cdotaccent", '\u{010B}'),
+    ("cedilla", '\u{00B8}'),
+    ("cent", '\u{00A2}'),
+    ("centigrade", '\u{2103}'),
+    ("chi", '\u{03C7}'),
+    ("chook", '\u{0188}'),
+    ("circle", '\u{25CB}'),
+    ("circledivide", '\u{2298}'),
+    ("circledot", '\u{2299}'),
+    ("circledotdisplay", '\u{2299}'),
+    ("circledottext", '\u{2299}'),
+    ("circleminus", '\u{2296}'),
+    ("circlemultiply", '\u{2297}'),
+    ("circlemultiplydisplay", '\u{2297}'),
+    ("circlemultiplytext", '\u{2297}'),
+    ("circleot", '\u{2299}'),
+    ("circleplus", '\u{2295}'),
+    ("circleplusdisplay", '\u{2295}'),
+    ("circleplustext", '\u{2295}'),
+    ("circlewithlefthalfblack", '\u{25D0}'),
+    ("circlewithrighthalfblack", '\u{25D1}'),
+    ("clickalveolar", '\u{01C2}'),
+    ("clickdental", '\u{01C0}'),
+    ("clicklateral", '\u{01C1}'),
+    ("clickretroflex", '\u{01C3}'),
+    ("colon", '\u{003A}'),
+    ("colonmonetary", '\u{20A1}'),
+    ("colonsign", '\u{20A1}'),
+    ("comma", '\u{002C}'),
+    ("compwordmark", '\u{200C}'),
+    ("congruent", '\u{2245}'),
+    ("contintegraldisplay", '\u{222E}'),
+    ("contintegraltext", '\u{222E}'),
+    ("contourintegral", '\u{222E}'),
+    ("controlDEL", '\u{007F}'),
+    ("coproductdisplay", '\u{2210}'),
+    ("coproducttext", '\u{2210}'),
+    ("copyright", '\u{00A9}'),
+    ("cruzeiro", '\u{20A2}'),
+    ("curlyand", '\u{22CF}'),
+    ("curlyor", '\u{22CE}'),
+    ("currency", '\u{00A4}'),
+    ("cwm", '\u{200C}'),
+    ("d", '\u{0064}'),
+    ("dagger", '\u{2020}'),
+    ("daggerdbl", '\u{2021}'),
+    ("dbar", '\u{0111}'),
+    ("dblarrowleft", '\u{21D4}'),
+    ("dblarrowright", '\u{21D2}'),
+    ("dblintegral", '\u{222C}'),
+    ("dbllowline", '\u{2017}'),
+    ("dblverticalbar", '\u{2016}'),
+    ("dcaron", '\u{010F}'),
+    ("dcroat", '\u{0111}'),
+    ("degree", '\u{00B0}'),
+    ("deicoptic", '\u{03EF}'),
+    ("delta", '\u{03B4}'),
+    ("deltaturned", '\u{018D}'),
+    ("dialytikatonos", '\u{0385}'),
+    ("diamondmath", '\u{22C4}'),
+    ("dieresis", '\u{00A8}'),
+    ("dieresistonos", '\u{0385}'),
+    ("divide", '\u{00F7}'),
+    ("divides", '\u{2223}'),
+    ("divisionslash", '\u{2215}'),
+    ("dmacron", '\u{0111}'),
+    ("dollar", '\u{0024}'),
+    ("dong", '\u{20AB}'),
+    ("dotlessi", '\u{0131}'),
+    ("dotlessj", '\u{0237}'),
+    ("dotmath", '\u{22C5}'),
+    ("dottedcircle", '\u{25CC}'),
+    ("dtopbar", '\u{018C}'),
+    ("dz", '\u{01F3}'),
+    ("dzcaron", '\u{01C6}'),
+    ("e", '\u{0065}'),
+    ("eacute", '\u{00E9}'),
+    ("ebreve", '\u{0115}'),
+    ("ecaron", '\u{011B}'),
+    ("ecircumflex", '\u{00EA}'),
+    ("edblgrave", '\u{0205}'),
+    ("edieresis", '\u{00EB}'),
+    ("edot", '\u{0117}'),
+    ("edotaccent", '\u{0117}'),
+    ("egrave", '\u{00E8}'),
+    ("eight", '\u{0038}'),
+    ("einvertedbreve", '\u{0207}'),
+    ("element", '\u{2208}'),
+    ("ellipsis", '\u{2026}'),
+    ("ellipsisvertical", '\u{22EE}'),
+    ("emacron", '\u{0113}'),
+    ("emdash", '\u{2014}'),
+    ("emptyset", '\u{2205}'),
+    ("endash", '\u{2013}'),
+    ("eng", '\u{014B}'),
+    ("enspace", '\u{2002}'),
+    ("eogonek", '\u{0119}'),
+    ("epsilon", '\u{03B5}'),
+    ("epsilon1", '\u{03F5}'),
+    ("epsilontonos", '\u{03AD}'),
+    ("equal", '\u{003D}'),
+    ("equivalence", '\u{2261}'),
+    ("equivasymptotic", '\u{224D}'),
+    ("eshreversedloop", '\u{01AA}'),
+    ("estimated", '\u{212E}'),
+    ("eta", '\u{03B7}'),
+    ("etatonos", '\u{03AE}'),
+    ("eth", '\u{00F0}'),
+    ("eturned", '\u{01DD}'),
+    ("euro", '\u{20AC}'),
+    ("exclam", '\u{0021}'),
+    ("exclamdbl", '\u{203C}'),
+    ("exclamdown", '\u{00A1}'),
+    ("existential", '\u{2203}'),
+    ("ezhcaron", '\u{01EF}'),
+    ("ezhreversed", '\u{01B9}'),
+    ("ezhtail", '\u{01BA}'),
+    ("f", '\u{0066}'),
+    ("f_f", '\u{FB00}'),
+    ("f_f_i", '\u{FB03}'),
+    ("f_f_l", '\u{FB04}'),
+    ("f_i", '\u{FB01}'),
+    ("f_l", '\u{FB02}'),
+    ("fahrenheit", '\u{2109}'),
+    ("feicoptic", '\u{03E5}'),
+    ("ff", '\u{FB00}'),
+    ("ffi", '\u{FB03}'),
+    ("ffl", '\u{FB04}'),
+    ("fi", '\u{FB01}'),
+    ("figuredash", '\u{2012}'),
+    ("filledbox", '\u{25A0}'),
+    ("filledrect", '\u{25AC}'),
+    ("fisheye", '\u{25C9}'),
+    ("five", '\u{0035}'),
+    ("fl", '\u{FB02}'),
+    ("florin", '\u{0192}'),
+    ("follows", '\u{227B}'),
+    ("followsequal", '\u{227D}'),
+    ("forall", '\u{2200}'),
+    ("four", '\u{0034}'),
+    ("fraction", '\u{2044}'),
+    ("franc", '\u{20A3}'),
+    ("g", '\u{0067}'),
+    ("gacute", '\u{01F5}'),
+    ("gamma", '\u{03B3}'),
+    ("gangiacoptic", '\u{03EB}'),
+    ("gbreve", '\u{011F}'),
+    ("gcaron", '\u{01E7}'),
+    ("gcedilla", '\u{0123}'),
+    ("gcircumflex", '\u{011D}'),
+    ("gcommaaccent", '\u{0123}'),
+    ("gdot", '\u{0121}'),
+    ("gdotaccent", '\u{0121}'),
+    ("geometricallyequal", '\u{2251}'),
+    ("germandbls", '\u{00DF}'),
+    ("glottalinvertedstroke", '\u{01BE}'),
+    ("gradient", '\u{2207}'),
+    ("grave", '\u{0060}'),
+    ("greater", '\u{003E}'),
+    ("greaterequal", '\u{2265}'),
+    ("greaterequalorless", '\u{22DB}'),
+    ("greatermuch", '\u{226B}'),
+    ("greaterorequivalent", '\u{2273}'),
+    ("greaterorless", '\u{2277}'),
+    ("greateroverequal", '\u{2267}'),
+    ("gstroke", '\u{01E5}'),
+    ("guillemotleft", '\u{00AB}'),
+    ("guillemotright", '\u{00BB}'),
+    ("guilsinglleft", '\u{2039}'),
+    ("guilsinglright", '\u{203A}'),
+    ("h", '\u{0068}'),
+    ("harpoonleftbarbup", '\u{21BC}'),
+    ("harpoonleftdown", '\u{21BD}'),
+    ("harpoonleftup", '\u{21BC}'),
+    ("harpoonrightbarbup", '\u{21C0}'),
+    ("harpoonrightdown", '\u{21C1}'),
+    ("harpoonrightup", '\u{21C0}'),
+    ("hbar", '\u{0127}'),
+    ("hcircumflex", '\u{0125}'),
+    ("hookleftchar", '\u{21A9}'),
+    ("hookrightchar", '\u{21AA}'),
+    ("horicoptic", '\u{03E9}'),
+    ("horizontalbar", '\u{2015}'),
+    ("hv", '\u{0195}'),
+    ("hyphen", '\u{002D}'),
+    ("hyphen_alt", '\u{2010}'),
+    ("hyphenchar", '\u{002D}'),
+    ("hyphentwo", '\u{2010}'),
+    ("i", '\u{0069}'),
+    ("iacute", '\u{00ED}'),
+    ("ibreve", '\u{012D}'),
+    ("icaron", '\u{01D0}'),
+    ("icircumflex", '\u{00EE}'),
+    ("idblgrave", '\u{0209}'),
+    ("idieresis", '\u{00EF}'),
+    ("igrave", '\u{00EC}'),
+    ("iinvertedbreve", '\u{020B}'),
+    ("ij", '\u{0133}'),
+    ("imacron", '\u{012B}'),
+    ("imageorapproximatelyequal", '\u{2253}'),
+    ("increment", '\u{2206}'),
+    ("infinity", '\u{221E}'),
+    ("integral", '\u{222B}'),
+    ("integraldisplay", '\u{222B}'),
+    ("integraltext", '\u{222B}'),
+    ("interrobang", '\u{203D}'),
+    ("intersection", '\u{2229}'),
+    ("intersectiondisplay", '\u{22C2}'),
+    ("intersectionsq", '\u{2293}'),
+    ("intersectiontext", '\u{22C2}'),
+    ("invbullet", '\u{25D8}'),
+    ("invcircle", '\u{25D9}'),
+    ("iogonek", '\u{012F}'),
+    ("iota", '\u{03B9}'),
+    ("iotadieresis", '\u{03CA}'),
+    ("iotadieresistonos", '\u{0390}'),
+    ("iotatonos", '\u{03AF}'),
+    ("itilde", '\u{0129}'),
+    ("j", '\u{006A}'),
+    ("jcaron", '\u{01F0}'),
+    ("jcircumflex", '\u{0135}'),
+    ("k", '\u{006B}'),
+    ("kappa", '\u{03BA}'),
+    ("kappasymbolgreek", '\u{03F0}'),
+    ("kcaron", '\u{01E9}'),
+    ("kcedilla", '\u{0137}'),
+    ("kcommaaccent", '\u{0137}'),
+    ("kgreenlandic", '\u{0138}'),
+    ("kheicoptic", '\u{03E7}'),
+    ("khook", '\u{0199}'),
+    ("l", '\u{006C}'),
+    ("lacute", '\u{013A}'),
+    ("lambda", '\u{03BB}'),
+    ("lambdastroke", '\u{019B}'),
+    ("largecircle", '\u{25EF}'),
+    ("latticetop", '\u{22A4}'),
+    ("lbar", '\u{019A}'),
+    ("lcaron", '\u{013E}'),
+    ("lcedilla", '\u{013C}'),
+    ("lcommaaccent", '\u{013C}'),
+    ("ldot", '\u{0140}'),
+    ("ldotaccent", '\u{0140}'),
+    ("less", '\u{003C}'),
+    ("lessequal", '\u{2264}'),
+    ("lessequalorgreater", '\u{22DA}'),
+    ("lessmuch", '\u{226A}'),
+    ("lessorequivalent", '\u{2272}'),
+    ("lessorgreater", '\u{2276}'),
+    ("lessoverequal", '\u{2266}'),
+    ("lira", '\u{20A4}'),
+    ("lj", '\u{01C9}'),
+    ("logicaland", '\u{2227}'),
+    ("logicalanddisplay", '\u{22C0}'),
+    ("logicalandtext", '\u{22C0}'),
+    ("logicalnot", '\u{00AC}'),
+    ("logicalor", '\u{2228}'),
+    ("logicalordisplay", '\u{22C1}'),
+    ("logicalortext", '\u{22C1}'),
+    ("longs", '\u{017F}'),
+    ("lozenge", '\u{25CA}'),
+    ("lscript", '\u{2113}'),
+    ("lslash", '\u{0142}'),
+    ("lsquare", '\u{2113}'),
+    ("m", '\u{006D}'),
+    ("macron", '\u{00AF}'),
+    ("middot", '\u{00B7}'),
+    ("minus", '\u{2212}'),
+    ("minuscircle", '\u{2296}'),
+    ("minusplus", '\u{2213}'),
+    ("minute", '\u{2032}'),
+    ("mu", '\u{00B5}'),
+    ("mu1", '\u{00B5}'),
+    ("muchgreater", '\u{226B}'),
+    ("muchless", '\u{226A}'),
+    ("mugreek", '\u{03BC}'),
+    ("multiply", '\u{00D7}'),
+    ("n", '\u{006E}'),
+    ("nabla", '\u{2207}'),
+    ("nacute", '\u{0144}'),
+    ("napostrophe", '\u{0149}'),
+    ("nbspace", '\u{00A0}'),
+    ("ncaron", '\u{0148}'),
+    ("ncedilla", '\u{0146}'),
+    ("ncommaaccent", '\u{0146}'),
+    ("newsheqelsign", '\u{20AA}'),
+    ("ng", '\u{014B}'),
+    ("nine", '\u{0039}'),
+    ("nj", '\u{01CC}'),
+    ("nlegrightlong", '\u{019E}'),
+    ("nonbreakingspace", '\u{00A0}'),
+    ("notcontains", '\u{220C}'),
+    ("notelement", '\u{2209}'),
+    ("notelementof", '\u{2209}'),
+    ("notequal", '\u{2260}'),
+    ("notgreater", '\u{226F}'),
+    ("notgreaternorequal", '\u{2271}'),
+    ("notgreaternorless", '\u{2279}'),
+    ("notidentical", '\u{2262}'),
+    ("notless", '\u{226E}'),
+    ("notlessnorequal", '\u{2270}'),
+    ("notparallel", '\u{2226}'),
+    ("notprecedes", '\u{2280}'),
+    ("notsubset", '\u{2284}'),
+    ("notsucceeds", '\u{2281}'),
+    ("notsuperset", '\u{2285}'),
+    ("ntilde", '\u{00F1}'),
+    ("nu", '\u{03BD}'),
+    ("numbersign", '\u{0023}'),
+    ("numeralsigngreek", '\u{0374}'),
+    ("numeralsignlowergreek", '\u{0375}'),
+    ("numero", '\u{2116}'),
+    ("o", '\u{006F}'),
+    ("oacute", '\u{00F3}'),
+    ("obreve", '\u{014F}'),
+    ("ocaron", '\u{01D2}'),
+    ("ocircumflex", '\u{00F4}'),
+    ("odblacute", '\u{0151}'),
+    ("odblgrave", '\u{020D}'),
+    ("odieresis", '\u{00F6}'),
+    ("oe", '\u{0153}'),
+    ("ograve", '\u{00F2}'),
+    ("ohorn", '\u{01A1}'),
+    ("ohungarumlaut", '\u{0151}'),
+    ("oi", '\u{01A3}'),
+    ("oinvertedbreve", '\u{020F}'),
+    ("omacron", '\u{014D}'),
+    ("omega", '\u{03C9}'),
+    ("omega1", '\u{03D6}'),
+    ("omegatonos", '\u{03CE}'),
+    ("omicron", '\u{03BF}'),
+    ("omicrontonos", '\u{03CC}'),
+    ("one", '\u{0031}'),
+    ("onedotenleader", '\u{2024}'),
+    ("onehalf", '\u{00BD}'),
+    ("onequarter", '\u{00BC}'),
+    ("onesuperior", '\u{00B9}'),
+    ("oogonek", '\u{01EB}'),
+    ("oogonekmacron", '\u{01ED}'),
+    ("openbullet", '\u{25E6}'),
+    ("ordfeminine", '\u{00AA}'),
+    ("ordmasculine", '\u{00BA}'),
+    ("orthogonal", '\u{221F}'),
+    ("oslash", '\u{00F8}'),
+    ("oslashacute", '\u{01FF}'),
+    ("ostrokeacute", '\u{01FF}'),
+    ("otilde", '\u{00F5}'),
+    ("overline", '\u{203E}'),
+    ("overscore", '\u{00AF}'),
+    ("owner", '\u{220B}'),
+    ("p", '\u{0070}'),
+    ("pagedown", '\u{21DF}'),
+    ("pageup", '\u{21DE}'),
+    ("paragraph", '\u{00B6}'),
+    ("parallel", '\u{2225}'),
+    ("parenleft", '\u{0028}'),
+    ("parenleftBig", '\u{0028}'),
+    ("parenleftBigg", '\u{0028}'),
+    ("parenleftbig", '\u{0028}'),
+    ("parenleftbigg", '\u{0028}'),
+    ("parenleftex", '\u{007C}'),
+    ("parenright", '\u{0029}'),
+    ("parenrightBig", '\u{0029}'),
+    ("parenrightBigg", '\u{0029}'),
+    ("parenrightbig", '\u{0029}'),
+    ("parenrightbigg", '\u{0029}'),
+    ("parenrightex", '\u{007C}'),
+    ("partialdiff", '\u{2202}'),
+    ("percent", '\u{0025}'),
+    ("period", '\u{002E}'),
+    ("periodcentered", '\u{00B7}'),
+    ("perpendicular", '\u{22A5}'),
+    ("pertenthousand", '\u{2031}'),
+    ("perthousand", '\u{2030}'),
+    ("peseta", '\u{20A7}'),
+    ("phi", '\u{03C6}'),
+    ("phi2", '\u{03D5}'),
+    ("phisymbolgreek", '\u{03D5}'),
+    ("phook", '\u{01A5}'),
+    ("pi", '\u{03C0}'),
+    ("pi1", '\u{03D6}'),
+    ("pisymbolgreek", '\u{03D6}'),
+    ("plus", '\u{002B}'),
+    ("pluscircle", '\u{2295}'),
+    ("plusminus", '\u{00B1}'),
+    ("precedes", '\u{227A}'),
+    ("precedesequal", '\u{227C}'),
+    ("prescription", '\u{211E}'),
+    ("prime", '\u{2032}'),
+    ("primereversed", '\u{2035}'),
+    ("product", '\u{220F}'),
+    ("productdisplay", '\u{220F}'),
+    ("producttext", '\u{220F}'),
+    ("propersubset", '\u{2282}'),
+    ("propersuperset", '\u{2283}'),
+    ("proportion", '\u{2237}'),
+    ("proportional", '\u{221D}'),
+    ("psi", '\u{03C8}'),
+    ("punctdash", '\u{2014}'),
+    ("q", '\u{0071}'),
+    ("question", '\u{003F}'),
+    ("questiondown", '\u{00BF}'),
+    ("questiongreek", '\u{037E}'),
+    ("quotedbl", '\u{0022}'),
+    ("quotedblbase", '\u{201E}'),
+    ("quotedblleft", '\u{201C}'),
+    ("quotedblright", '\u{201D}'),
+    ("quoteleft", '\u{2018}'),
+    ("quoteleftreversed", '\u{201B}'),
+    ("quotereversed", '\u{201B}'),
+    ("quoteright", '\u{2019}'),
+    ("quoterightn", '\u{0149}'),
+    ("quotesinglbase", '\u{201A}'),
+    ("quotesingle", '\u{0027}'),
+    ("r", '\u{0072}'),
+    ("racute", '\u{0155}'),
+    ("radical", '\u{221A}'),
+    ("radicalBig", '\u{221A}'),
+    ("radicalBigg", '\u{221A}'),
+    ("radicalbig", '\u{221A}'),
+    ("radicalbigg", '\u{221A}'),
+    ("radicalbt", '\u{221A}'),
+    ("rangedash", '\u{2013}'),
+    ("ratio", '\u{2236}'),
+    ("rcaron", '\u{0159}'),
+    ("rcedilla", '\u{0157}'),
+    ("rcommaaccent", '\u{0157}'),
+    ("rdblgrave", '\u{0211}'),
+    ("referencemark", '\u{203B}'),
+    ("reflexsubset", '\u{2286}'),
+    ("reflexsuperset", '\u{2287}'),
+    ("registered", '\u{00AE}'),
+    ("reversedtilde", '\u{223D}'),
+    ("rho", '\u{03C1}'),
+    ("rho1", '\u{03F1}'),
+    ("rhosymbolgreek", '\u{03F1}'),
+    ("rightangle", '\u{221F}'),
+    ("righttriangle", '\u{22BF}'),
+    ("rinvertedbreve", '\u{0213}'),
+    ("s", '\u{0073}'),
+    ("sacute", '\u{015B}'),
+    ("scaron", '\u{0161}'),
+    ("scedilla", '\u{015F}'),
+    ("scircumflex", '\u{015D}'),
+    ("scommaaccent", '\u{0219}'),
+    ("second", '\u{2033}'),
+    ("section", '\u{00A7}'),
+    ("semicolon", '\u{003B}'),
+    ("seven", '\u{0037}'),
+    ("sfthyphen", '\u{00AD}'),
+    ("sheicoptic", '\u{03E3}'),
+    ("sheqel", '\u{20AA}'),
+    ("sheqelhebrew", '\u{20AA}'),
+    ("shimacoptic", '\u{03ED}'),
+    ("sigma", '\u{03C3}'),
+    ("sigma1", '\u{03C2}'),
+    ("sigmafinal", '\u{03C2}'),
+    ("sigmalunatesymbolgreek", '\u{03F2}'),
+    ("similar", '\u{223C}'),
+    ("similarequal", '\u{2243}'),
+    ("six", '\u{0036}'),
+    ("slash", '\u{002F}'),
+    ("slashBig", '\u{2215}'),
+    ("slashBigg", '\u{2215}'),
+    ("slashbig", '\u{2215}'),
+    ("slashbigg", '\u{2215}'),
+    ("slong", '\u{017F}'),
+    ("softhyphen", '\u{00AD}'),
+    ("space", '\u{0020}'),
+    ("spacehackarabic", '\u{0020}'),
+    ("squarediagonalcrosshatchfill", '\u{25A9}'),
+    ("squarehorizontalfill", '\u{25A4}'),
+    ("squareorthogonalcrosshatchfill", '\u{25A6}'),
+    ("squareupperlefttolowerrightfill", '\u{25A7}'),
+    ("squareupperrighttolowerleftfill", '\u{25A8}'),
+    ("squareverticalfill", '\u{25A5}'),
+    ("squarewhitewithsmallblack", '\u{25A3}'),
+    ("star", '\u{22C6}'),
+    ("sterling", '\u{00A3}'),
+    ("subset", '\u{2282}'),
+    ("subsetnotequal", '\u{228A}'),
+    ("subsetorequal", '\u{2286}'),
+    ("subsetsqequal", '\u{2291}'),
+    ("succeeds", '\u{227B}'),
+    ("suchthat", '\u{220B}'),
+    ("summation", '\u{2211}'),
+    ("summationdisplay", '\u{2211}'),
+    ("summationtext", '\u{2211}'),
+    ("superset", '\u{2283}'),
+    ("supersetnotequal", '\u{228B}'),
+    ("supersetorequal", '\u{2287}'),
+    ("supersetsqequal", '\u{2292}'),
+    ("t", '\u{0074}'),
+    ("tackdown", '\u{22A4}'),
+    ("tackleft", '\u{22A3}'),
+    ("tau", '\u{03C4}'),
+    ("tbar", '\u{0167}'),
+    ("tcaron", '\u{0165}'),
+    ("tcedilla", '\u{0163}'),
+    ("tcommaaccent", '\u{0163}'),
+    ("telephone", '\u{2121}'),
+    ("thereexists", '\u{2203}'),
+    ("therefore", '\u{2234}'),
+    ("theta", '\u{03B8}'),
+    ("theta1", '\u{03D1}'),
+    ("thetasymbolgreek", '\u{03D1}'),
+    ("thook", '\u{01AD}'),
+    ("thorn", '\u{00FE}'),
+    ("three", '\u{0033}'),
+    ("threequarters", '\u{00BE}'),
+    ("threesuperior", '\u{00B3}'),
+    ("tie", '\u{2040}'),
+    ("tildeoperator", '\u{223C}'),
+    ("timescircle", '\u{2297}'),
+    ("tonefive", '\u{01BD}'),
+    ("tonesix", '\u{0185}'),
+    ("tonetwo", '\u{01A8}'),
+    ("tonos", '\u{0384}'),
+    ("tpalatalhook", '\u{01AB}'),
+    ("trademark", '\u{2122}'),
+    ("triagdn", '\u{25BC}'),
+    ("triaglf", '\u{25C4}'),
+    ("triagrt", '\u{25BA}'),
+    ("triagup", '\u{25B2}'),
+    ("triangle", '\u{25B3}'),
+    ("triangleinv", '\u{25BD}'),
+    ("triangleleft", '\u{25B9}'),
+    ("triangleright", '\u{25C3}'),
+    ("turnstileleft", '\u{22A2}'),
+    ("turnstileright", '\u{22A3}'),
+    ("two", '\u{0032}'),
+    ("twodotenleader", '\u{2025}'),
+    ("twodotleader", '\u{2025}'),
+    ("twostroke", '\u{01BB}'),
+    ("twosuperior", '\u{00B2}'),
+    ("u", '\u{0075}'),
+    ("uacute", '\u{00FA}'),
+    ("ubreve", '\u{016D}'),
+    ("ucaron", '\u{01D4}'),
+    ("ucircumflex", '\u{00FB}'),
+    ("udblacute", '\u{0171}'),
+    ("udblgrave", '\u{0215}'),
+    ("udieresis", '\u{00FC}'),
+    ("udieresisacute", '\u{01D8}'),
+    ("udieresiscaron", '\u{01DA}'),
+    ("udieresisgrave", '\u{01DC}'),
+    ("udieresismacron", '\u{01D6}'),
+    ("ugrave", '\u{00F9}'),
+    ("uhorn", '\u{01B0}'),
+    ("uhungarumlaut", '\u{0171}'),
+    ("uinvertedbreve", '\u{0217}'),
+    ("umacron", '\u{016B}'),
+    ("underscore", '\u{005F}'),
+    ("underscoredbl", '\u{2017}'),
+    ("union", '\u{222A}'),
+    ("uniondisplay", '\u{22C3}'),
+    ("unionmulti", '\u{228E}'),
+    ("unionmultidisplay", '\u{228E}'),
+    ("unionmultitext", '\u{228E}'),
+    ("unionsq", '\u{2294}'),
+    ("unionsqdisplay", '\u{2294}'),
+    ("unionsqtext", '\u{2294}'),
+    ("uniontext", '\u{22C3}'),
+    ("universal", '\u{2200}'),
+    ("uogonek", '\u{0173}'),
+    ("upsilon", '\u{03C5}'),
+    ("upsilondieresis", '\u{03CB}'),
+    ("upsilondieresistonos", '\u{03B0}'),
+    ("upsilontonos", '\u{03CD}'),
+    ("uring", '\u{016F}'),
+    ("utilde", '\u{0169}'),
+    ("v", '\u{0076}'),
+    ("verticalbar", '\u{007C}'),
+    ("w", '\u{0077}'),
+    ("wcircumflex", '\u{0175}'),
+    ("weierstrass", '\u{2118}'),
+    ("whitebullet", '\u{25E6}'),
+    ("whitecircle", '\u{25CB}'),
+    ("whitecircleinverse", '\u{25D9}'),
+    ("whitediamond", '\u{25C7}'),
+    ("whitediamondcontainingblacksmalldiamond", '\u{25C8}'),
+    ("whitedownpointingsmalltriangle", '\u{25BF}'),
+    ("whitedownpointingtriangle", '\u{25BD}'),
+    ("whiteleftpointingsmalltriangle", '\u{25C3}'),
+    ("whiteleftpointingtriangle", '\u{25C1}'),
+    ("whiterightpointingsmalltriangle", '\u{25B9}'),
+    ("whiterightpointingtriangle", '\u{25B7}'),
+    ("whitesmallsquare", '\u{25AB}'),
+    ("whitesquare", '\u{25A1}'),
+    ("whiteuppointingsmalltriangle", '\u{25B5}'),
+    ("whiteuppointingtriangle", '\u{25B3}'),
+    ("won", '\u{20A9}'),
+    ("wreathproduct", '\u{2240}'),
+    ("wynn", '\u{01BF}'),
+    ("x", '\u{0078}'),
+    ("xi", '\u{03BE}'),
+    ("y", '\u{0079}'),
+    ("yacute", '\u{00FD}'),
+    ("ycircumflex", '\u{0177}'),
+    ("ydieresis", '\u{00FF}'),
+    ("yen", '\u{00A5}'),
+    ("yhook", '\u{01B4}'),
+    ("yotgreek", '\u{03F3}'),
+    ("ypogegrammeni", '\u{037A}'),
+    ("yr", '\u{01A6}'),
+    ("z", '\u{007A}'),
+    ("zacute", '\u{017A}'),
+    ("zcaron", '\u{017E}'),
+    ("zdot", '\u{017C}'),
+    ("zdotaccent", '\u{017C}'),
+    ("zero", '\u{0030}'),
+    ("zerowidthnonjoiner", '\u{200C}'),
+    ("zerowidthspace", '\u{200B}'),
+    ("zeta", '\u{03B6}'),
+    ("zstroke", '\u{01B6}'),
+];
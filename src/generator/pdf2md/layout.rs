@@ -0,0 +1,272 @@
+use super::pdf_element::{elements_into_matrix, sort_transform_elements, PdfElement};
+
+/// Whitespace between columns must be at least this many times the median font size
+const GUTTER_WIDTH: f32 = 1.0;
+/// Columns narrower than this fraction of their region are not split off; this keeps
+/// short labels (e.g. "Name:") together with the values next to them
+const MIN_COLUMN_FRACTION: f32 = 0.15;
+/// Vertical whitespace of at least this many times the median font size separates blocks
+const BLOCK_GAP: f32 = 0.5;
+/// Height, in median font sizes, of the bands above and below a horizontal cut
+/// that are compared when deciding whether the cut runs through columns
+const BAND_HEIGHT: f32 = 3.0;
+
+/// Bounding box in PDF user space (y grows upwards)
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+}
+
+impl Rect {
+    fn of(element: &PdfElement) -> Rect {
+        match element {
+            PdfElement::Text(text) => {
+                let size = text.height.max(1.0);
+                Rect {
+                    left: text.x,
+                    right: text.x + text.width,
+                    bottom: text.y - size * 0.2,
+                    top: text.y + size * 0.8,
+                }
+            }
+            PdfElement::Table(table) => {
+                let (left, right, bottom, top) = table.bounds();
+                Rect { left, right, bottom, top }
+            }
+            PdfElement::Image(image) => Rect {
+                left: image.x,
+                right: image.x + image.width,
+                bottom: image.y - image.height,
+                top: image.y,
+            },
+        }
+    }
+}
+
+/// Orders the elements of a page for reading and groups them into rows.
+///
+/// The page is cut recursively at whitespace (XY-cut): a region is split into columns
+/// at gutters that run through all of it, otherwise into blocks at horizontal gaps.
+/// Full-width headings therefore come before the columns beneath them, columns and
+/// sidebars are read one after the other, left to right, and each block is split into
+/// rows. Blocks are separated by an empty row.
+pub fn reading_order(elements: Vec<PdfElement>, median_font_size: Option<f32>) -> Vec<Vec<PdfElement>> {
+    let unit = median_font_size.unwrap_or(12.0).max(1.0);
+    let items: Vec<(Rect, PdfElement)> = elements.into_iter().map(|e| (Rect::of(&e), e)).collect();
+
+    let mut blocks = Vec::new();
+    xy_cut(items, unit, &mut blocks);
+
+    let mut matrix: Vec<Vec<PdfElement>> = Vec::new();
+    for block in blocks {
+        if !matrix.is_empty() {
+            matrix.push(Vec::new());
+        }
+        let mut rows = elements_into_matrix(block);
+        for row in rows.iter_mut() {
+            sort_transform_elements(row);
+        }
+        matrix.extend(rows);
+    }
+    matrix
+}
+
+fn xy_cut(items: Vec<(Rect, PdfElement)>, unit: f32, blocks: &mut Vec<Vec<PdfElement>>) {
+    if items.len() <= 1 {
+        if !items.is_empty() {
+            blocks.push(items.into_iter().map(|(_, e)| e).collect());
+        }
+        return;
+    }
+
+    // columns, left to right
+    let gutters = gutters(&items, unit);
+    if !gutters.is_empty() {
+        for column in partition(items, &gutters, |rect| (rect.left + rect.right) / 2.0) {
+            xy_cut(column, unit, blocks);
+        }
+        return;
+    }
+
+    // blocks, top to bottom
+    if let Some(cut) = horizontal_cut(&items, unit) {
+        let (above, below): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|(rect, _)| (rect.bottom + rect.top) / 2.0 > cut);
+        xy_cut(above, unit, blocks);
+        xy_cut(below, unit, blocks);
+        return;
+    }
+
+    blocks.push(items.into_iter().map(|(_, e)| e).collect());
+}
+
+/// Splits items at the given cut positions, by the coordinate `key` picks
+fn partition(
+    items: Vec<(Rect, PdfElement)>,
+    cuts: &[f32],
+    key: impl Fn(&Rect) -> f32,
+) -> Vec<Vec<(Rect, PdfElement)>> {
+    let mut parts: Vec<Vec<(Rect, PdfElement)>> = (0..=cuts.len()).map(|_| Vec::new()).collect();
+    for item in items {
+        let index = cuts.iter().filter(|cut| key(&item.0) > **cut).count();
+        parts[index].push(item);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Gaps between the merged projections of `intervals` that are at least `min_gap` wide,
+/// as (start, end) pairs in ascending order
+fn projection_gaps(mut intervals: Vec<(f32, f32)>, min_gap: f32) -> Vec<(f32, f32)> {
+    intervals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut gaps = Vec::new();
+    let mut end = f32::NEG_INFINITY;
+    for (start, stop) in intervals {
+        if end != f32::NEG_INFINITY && start - end >= min_gap {
+            gaps.push((end, start));
+        }
+        end = end.max(stop);
+    }
+    gaps
+}
+
+/// Middles of the vertical gutters that run through the whole region and leave
+/// columns of a reasonable width on both sides
+fn gutters(items: &[(Rect, PdfElement)], unit: f32) -> Vec<f32> {
+    let intervals: Vec<(f32, f32)> = items.iter().map(|(r, _)| (r.left, r.right)).collect();
+    let left = intervals.iter().map(|i| i.0).fold(f32::INFINITY, f32::min);
+    let right = intervals.iter().map(|i| i.1).fold(f32::NEG_INFINITY, f32::max);
+    let min_width = (right - left) * MIN_COLUMN_FRACTION;
+
+    let gaps = projection_gaps(intervals, unit * GUTTER_WIDTH);
+    let mut edges = vec![left];
+    edges.extend(gaps.iter().flat_map(|(start, end)| [*start, *end]));
+    edges.push(right);
+    // edges alternate column start and end
+    let narrow = edges.chunks(2).any(|column| column[1] - column[0] < min_width);
+    if narrow {
+        return Vec::new();
+    }
+    gaps.iter().map(|(start, end)| (start + end) / 2.0).collect()
+}
+
+/// The widest horizontal gap that doesn't run through columns: a gap with columns at the
+/// same place just above and just below it is a paragraph break inside those columns
+fn horizontal_cut(items: &[(Rect, PdfElement)], unit: f32) -> Option<f32> {
+    let intervals: Vec<(f32, f32)> = items.iter().map(|(r, _)| (r.bottom, r.top)).collect();
+    let mut gaps = projection_gaps(intervals, unit * BLOCK_GAP);
+    gaps.sort_by(|a, b| (b.1 - b.0).partial_cmp(&(a.1 - a.0)).unwrap_or(std::cmp::Ordering::Equal));
+
+    let band = unit * BAND_HEIGHT;
+    gaps.into_iter().find_map(|(bottom, top)| {
+        let above: Vec<(Rect, PdfElement)> = items
+            .iter()
+            .filter(|(r, _)| r.bottom >= top && r.bottom <= top + band)
+            .cloned()
+            .collect();
+        let below: Vec<(Rect, PdfElement)> = items
+            .iter()
+            .filter(|(r, _)| r.top <= bottom && r.top >= bottom - band)
+            .cloned()
+            .collect();
+
+        let (above, below) = (gutters(&above, unit), gutters(&below, unit));
+        let through_columns = above.iter().any(|a| below.iter().any(|b| (a - b).abs() < unit * 2.0));
+        (!through_columns).then_some((bottom + top) / 2.0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pdf2md::pdf_element::PdfText;
+
+    fn text(text: &str, x: f32, y: f32, width: f32) -> PdfElement {
+        PdfElement::Text(PdfText {
+            text: text.to_string(),
+            x,
+            y,
+            width,
+            height: 10.0,
+            space_width: 2.5,
+            ..Default::default()
+        })
+    }
+
+    /// Texts of the rows in reading order, "" for the break between blocks
+    fn rows(elements: Vec<PdfElement>) -> Vec<String> {
+        reading_order(elements, Some(10.0))
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter_map(|element| match element {
+                        PdfElement::Text(text) => Some(text.text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    /// A column of `lines` starting at `x`, 14 units apart from `top` down
+    fn column(lines: &[&str], x: f32, top: f32) -> Vec<PdfElement> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| text(line, x, top - 14.0 * i as f32, 220.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_heading_then_two_columns() {
+        let mut elements = vec![text("A heading across both columns", 72.0, 740.0, 468.0)];
+        // the right column first, as some producers draw it
+        elements.extend(column(&["Right one", "Right two", "Right three"], 320.0, 700.0));
+        elements.extend(column(&["Left one", "Left two", "Left three"], 72.0, 700.0));
+
+        assert_eq!(
+            rows(elements),
+            vec![
+                "A heading across both columns",
+                "",
+                "Left one",
+                "Left two",
+                "Left three",
+                "",
+                "Right one",
+                "Right two",
+                "Right three",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paragraph_breaks_inside_columns_do_not_cut_across() {
+        let mut elements = column(&["Left one", "Left two"], 72.0, 700.0);
+        elements.extend(column(&["Left three", "Left four"], 72.0, 650.0));
+        elements.extend(column(&["Right one", "Right two"], 320.0, 700.0));
+        elements.extend(column(&["Right three", "Right four"], 320.0, 650.0));
+
+        let rows = rows(elements);
+        let left = rows.iter().position(|row| row == "Left four").unwrap();
+        let right = rows.iter().position(|row| row == "Right one").unwrap();
+        assert!(left < right, "{:?}", rows);
+    }
+
+    #[test]
+    fn test_narrow_labels_stay_with_their_values() {
+        let elements = vec![
+            text("Name:", 72.0, 700.0, 30.0),
+            text("Ada Lovelace", 200.0, 700.0, 340.0),
+            text("Born:", 72.0, 686.0, 28.0),
+            text("10 December 1815", 200.0, 686.0, 340.0),
+        ];
+        assert_eq!(rows(elements), vec!["Name: Ada Lovelace", "Born: 10 December 1815"]);
+    }
+}
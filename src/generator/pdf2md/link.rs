@@ -0,0 +1,91 @@
+use lopdf::{Document, Object, ObjectId};
+
+use super::outline::{Destination, Destinations};
+use super::pdf_element::PdfUnit;
+use super::pdf_page::decode_text_string;
+
+/// Where a link annotation goes
+#[derive(Debug, Clone)]
+pub enum LinkTarget {
+    Uri(String),
+    Page(Destination),
+}
+
+/// A link annotation: a clickable rectangle of the page
+#[derive(Debug, Clone)]
+pub struct PdfLink {
+    left: f32,
+    bottom: f32,
+    right: f32,
+    top: f32,
+    pub target: LinkTarget,
+}
+
+/// The `/Link` annotations of a page with a URI or a destination in the document
+pub fn page_links(document: &Document, page_id: ObjectId, destinations: &Destinations) -> Vec<PdfLink> {
+    let Ok(annotations) = document.get_page_annotations(page_id) else {
+        return Vec::new();
+    };
+
+    annotations
+        .into_iter()
+        .filter(|annotation| annotation.get(b"Subtype").and_then(Object::as_name).is_ok_and(|s| s == b"Link"))
+        .filter_map(|annotation| {
+            let rect: Vec<f32> = annotation
+                .get(b"Rect")
+                .and_then(|o| document.dereference(o))
+                .and_then(|(_, o)| o.as_array())
+                .ok()?
+                .iter()
+                .filter_map(|n| n.as_float().ok())
+                .collect();
+            let [x1, y1, x2, y2] = rect[..] else {
+                return None;
+            };
+
+            let uri = annotation
+                .get(b"A")
+                .and_then(|o| document.dereference(o))
+                .and_then(|(_, o)| o.as_dict())
+                .ok()
+                .filter(|action| action.get(b"S").and_then(Object::as_name).is_ok_and(|s| s == b"URI"))
+                .and_then(|action| action.get(b"URI").ok())
+                .and_then(|uri| document.dereference(uri).ok())
+                .and_then(|(_, uri)| uri.as_str().ok())
+                .map(decode_text_string);
+            let target = match uri {
+                Some(uri) => LinkTarget::Uri(uri.trim().to_string()),
+                None => LinkTarget::Page(destinations.of(annotation)?),
+            };
+
+            Some(PdfLink {
+                left: x1.min(x2),
+                bottom: y1.min(y2),
+                right: x1.max(x2),
+                top: y1.max(y2),
+                target,
+            })
+        })
+        .collect()
+}
+
+/// Gives the text runs inside a link's rectangle its URL; `resolve` turns the link
+/// target into one and may leave internal links it can't point anywhere unlinked
+pub fn apply_links(units: &mut [PdfUnit], links: &[PdfLink], resolve: impl Fn(&LinkTarget) -> Option<String>) {
+    let urls: Vec<Option<String>> = links.iter().map(|link| resolve(&link.target)).collect();
+    for unit in units.iter_mut() {
+        let PdfUnit::Text(pdf_text) = unit else {
+            continue;
+        };
+        // the middle of the run, a little above the baseline
+        let x = pdf_text.x + pdf_text.width / 2.0;
+        let y = pdf_text.y + pdf_text.height * 0.3;
+        let inside = links
+            .iter()
+            .zip(&urls)
+            .find(|(link, url)| url.is_some() && link.left <= x && x <= link.right && link.bottom <= y && y <= link.top);
+        if let Some((_, url)) = inside {
+            pdf_text.link = url.clone();
+        }
+    }
+}
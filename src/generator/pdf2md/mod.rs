@@ -1,123 +1,566 @@
-use std::error::Error;
+use std::{error::Error, fmt, mem::take, path::Path};
 
 use lopdf::Document;
-use pdf_element::{PdfElement, PdfText, PdfUnit};
+
+use crate::config::SETTINGS;
+use crate::generator::image2md;
+use link::LinkTarget;
+use outline::{Destinations, OutlineItem};
+use paragraph::{Line, TextLine};
+use pdf_element::{PdfElement, PdfTable, PdfText, PdfUnit};
+use pdf_image::PdfImage;
 use pdf_page::PdfPage;
+use structure::StructTree;
 
 
+mod borderless;
+mod encryption;
+mod glyph_list;
+mod layout;
+mod link;
+mod outline;
+mod page_label;
+mod paragraph;
 mod pdf_element;
+mod pdf_font;
+mod pdf_image;
 mod pdf_page;
 mod pdf_state;
+mod running;
+mod structure;
+
+/// Page separators accepted for `pdf_page_marker`
+const PAGE_MARKERS: [&str; 4] = ["none", "comment", "rule", "heading"];
+
+/// Why a PDF couldn't be opened
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfOpenError {
+    /// Encrypted with a user password, and no password was given
+    PasswordRequired,
+    /// The password given is neither the user nor the owner password
+    IncorrectPassword,
+    /// Not a PDF that can be read, or encrypted with an unsupported method
+    Invalid(String),
+}
+
+impl fmt::Display for PdfOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdfOpenError::PasswordRequired => write!(f, "PDF is encrypted, password required"),
+            PdfOpenError::IncorrectPassword => write!(f, "PDF is encrypted, the password is incorrect"),
+            PdfOpenError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for PdfOpenError {}
+
+/// Why a PDF couldn't be converted
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfError {
+    /// The PDF couldn't be opened, e.g. for want of its password
+    Open(PdfOpenError),
+    /// A page or setting that couldn't be handled
+    Convert(String),
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdfError::Open(e) => write!(f, "Failed to load PDF: {}", e),
+            PdfError::Convert(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for PdfError {}
+
+impl From<PdfOpenError> for PdfError {
+    fn from(e: PdfOpenError) -> Self {
+        PdfError::Open(e)
+    }
+}
+
+impl From<String> for PdfError {
+    fn from(e: String) -> Self {
+        PdfError::Convert(e)
+    }
+}
+
+/// The Markdown of one page of a PDF
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfPageMarkdown {
+    /// 1 for the first page of the document
+    pub number: usize,
+    /// The page label, e.g. "iv" or "A-3", or the number when the PDF gives none
+    pub label: String,
+    pub markdown: String,
+}
 
 /// Convert PDF byte stream into markdown
-pub fn run(file_stream: &[u8]) -> Result<String, String> {
-    let pdf = Pdf::new_from_bytes(file_stream)
-        .map_err(|e| format!("Failed to load PDF: {}", e))?;
-    let mut result = String::new();
-    let mut i = 0;
-
-    for page in pdf.iter_pages() {
-        i += 1;
-        result.push_str(&format!("\n\n<!-- S-TITLE: Page number {} -->\n", i));
-        let mut page = page.map_err(|e| format!("Failed to process page {}: {}", i, e))?;
+pub fn run(file_stream: &[u8]) -> Result<String, PdfError> {
+    let cfg = SETTINGS.read().unwrap().clone();
+    let marker = cfg.pdf_page_marker.as_str();
+    if !PAGE_MARKERS.contains(&marker) {
+        return Err(PdfError::Convert(format!(
+            "Unsupported PDF page marker '{}', expected one of: {}",
+            marker,
+            PAGE_MARKERS.join(", ")
+        )));
+    }
+
+    let (contents, pages) = convert_pages(file_stream, marker)?;
+    let mut result = contents;
+    for (i, page) in pages.into_iter().enumerate() {
+        result.push_str(&page_marker(marker, page.number, i == 0));
+        result.push_str(&page.markdown);
+    }
+
+    Ok(result)
+}
+
+/// Convert PDF byte stream into one markdown document per page, without page markers
+/// or a table of contents
+pub fn run_pages(file_stream: &[u8]) -> Result<Vec<PdfPageMarkdown>, PdfError> {
+    let (_, pages) = convert_pages(file_stream, "none")?;
+    Ok(pages)
+}
+
+/// Converts the pages chosen by the `pdf_pages` setting, all of them when it is empty.
+/// Returns the table of contents, if one is asked for, and the pages.
+fn convert_pages(file_stream: &[u8], marker: &str) -> Result<(String, Vec<PdfPageMarkdown>), PdfError> {
+    let cfg = SETTINGS.read().unwrap().clone();
+    let password = cfg.pdf_password.as_deref().filter(|p| !p.is_empty());
+    let pdf = Pdf::new_from_bytes(file_stream, password)?;
+
+    let destinations = Destinations::new(&pdf.doc);
+    let outline = outline::read_outline(&pdf.doc, &destinations);
+    // tagged PDFs convert by their structure tree, the layout heuristics are for untagged ones
+    let structure = StructTree::read(&pdf.doc);
+
+    let page_count = pdf.doc.get_pages().len();
+    let selected = page_selection(&cfg.pdf_pages, page_count)?;
+    let labels = page_label::page_labels(&pdf.doc, page_count);
+
+    let mut indices = Vec::new();
+    let mut pages = Vec::new();
+    let mut links = Vec::new();
+    for (i, id) in pdf.doc.page_iter().enumerate().filter(|(i, _)| selected[*i]) {
+        let mut page = PdfPage::from_object_id(&pdf.doc, id)
+            .map_err(|e| format!("Failed to process page {}: {}", i + 1, e))?;
         let units = page.handle_stream(page.stream.clone())
-            .map_err(|e| format!("Failed to handle stream for page {}: {}", i, e))?;
+            .map_err(|e| format!("Failed to handle stream for page {}: {}", i + 1, e))?;
+        indices.push(i);
+        pages.push(units);
+        links.push(link::page_links(&pdf.doc, id, &destinations));
+    }
+    if !cfg.pdf_include_headers_footers && structure.is_none() {
+        running::remove_running_text(&mut pages);
+    }
+
+    let mut contents = String::new();
+    // bookmarks of the pages converted
+    let bookmarks: Vec<OutlineItem> = outline
+        .iter()
+        .filter(|item| item.page.is_some_and(|page| selected.get(page) == Some(&true)))
+        .cloned()
+        .collect();
+    if cfg.pdf_table_of_contents && !bookmarks.is_empty() {
+        contents.push_str(&outline::table_of_contents(&bookmarks));
+    }
+    let mut converted = Vec::new();
+    for ((i, mut units), links) in indices.into_iter().zip(pages).zip(links) {
+        let mut markdown = String::new();
+        // links to pages that aren't converted have nowhere to go
+        link::apply_links(&mut units, &links, |target| match target {
+            LinkTarget::Page((page, _)) if selected.get(*page) != Some(&true) => None,
+            _ => link_url(target, &outline, marker),
+        });
+        if mostly_monospace(&units) {
+            // a document set in a fixed-pitch font: only a different font would mark code
+            for unit in units.iter_mut() {
+                if let PdfUnit::Text(pdf_text) = unit {
+                    pdf_text.monospace = false;
+                }
+            }
+        }
         let font_sizes: Vec<f32> = units
             .iter()
             .filter_map(|u| match u {
                 PdfUnit::Text(pdf_text) => pdf_text.font_size,
-                PdfUnit::Line(_) => None,
+                _ => None,
             })
             .collect();
         let median_font_size = median(font_sizes);
-        let elements = Pdf::pdf_units_to_elements(units);
-        let mut pre_header_level = "";
-
-        for row in elements {
-            let mut current_header_level = "";
-            for e in row {
-                match e {
-                    pdf_element::PdfElement::Text(pdf_text) => {
-                        let (mut text, header_level) = pdftext_to_md(pdf_text, median_font_size);
-                        current_header_level = header_level;
-                        // connected headres
-                        if current_header_level != "" && pre_header_level == current_header_level {
-                            result.push_str("<br>");
-                            text = text.replace(header_level, "");
-                        }
-                        // used to be header, now different
-                        if pre_header_level != "" && current_header_level != pre_header_level {
-                            result.push_str("\n");
-                        }
-                        result.push_str(&format!("{} ", text));
-                    }
-                    pdf_element::PdfElement::Table(mut pdf_table) => {
-                        let elements = pdf_table.get_sorted_elements();
-                        let elements: Vec<Vec<String>> = elements
-                            .iter()
-                            .map(|row| {
-                                row.iter()
-                                    .map(|cell| {
-                                        cell.iter()
-                                            // never header
-                                            .map(|item| pdftext_to_md(item.clone(), Some(1000.0)).0)
-                                            .collect::<Vec<String>>()
-                                            .join(" ")
-                                    })
-                                    .collect()
-                            })
-                            .collect();
-
-                        let headers = elements.get(0);
-                        let rows = elements.get(1..);
-                        if rows.is_some() && headers.is_some() {
-                            let md = to_markdown_table(&headers.unwrap(), &rows.unwrap());
-                            result.push_str(&md);
-                        }
-                    }
-                }
+
+        let blocks = structure.as_ref().map_or(&[][..], |tree| tree.page(i));
+        let (tagged, mut units) = structure::tagged_page_to_md(units, blocks, median_font_size, cfg.pdf_include_headers_footers);
+        markdown.push_str(&tagged);
+        if !units.is_empty() {
+            // untagged pages, and content outside the structure tree
+            let rotated = take_rotated_text(&mut units);
+            let elements = Pdf::pdf_units_to_elements(units, median_font_size);
+            let mut lines: Vec<Line> = elements
+                .into_iter()
+                .flat_map(|row| row_to_lines(row, median_font_size))
+                .collect();
+            if tagged.is_empty() {
+                let bookmarks: Vec<&OutlineItem> = outline.iter().filter(|item| item.page == Some(i)).collect();
+                outline::apply_headings(&mut lines, &bookmarks);
             }
-            if current_header_level == "" {
-                result.push_str("\n");
+            markdown.push_str(&paragraph::reflow(lines));
+            for label in rotated {
+                markdown.push_str(&format!("{}\n\n", label));
             }
-            pre_header_level = current_header_level;
         }
+        converted.push(PdfPageMarkdown { number: i + 1, label: labels[i].clone(), markdown });
     }
 
-    Ok(result)
+    Ok((contents, converted))
 }
 
-fn pdftext_to_md(mut unit: PdfText, median_size: Option<f32>) -> (String, &'static str) {
-    let mut text = unit.text;
+/// Markdown link target of a link annotation. Links within the document go to the
+/// heading of the bookmark nearest their destination, or to the page heading; without
+/// either they are dropped.
+fn link_url(target: &LinkTarget, outline: &[OutlineItem], marker: &str) -> Option<String> {
+    let (page, top) = match target {
+        LinkTarget::Uri(uri) => return Some(uri.clone()).filter(|uri| !uri.is_empty()),
+        LinkTarget::Page(destination) => *destination,
+    };
+    let distance = |item: &OutlineItem| match (item.top, top) {
+        (Some(a), Some(b)) => (a - b).abs(),
+        _ => 0.0,
+    };
+    let bookmark = outline
+        .iter()
+        .filter(|item| item.page == Some(page) && !item.title.is_empty())
+        .min_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap_or(std::cmp::Ordering::Equal));
+    match bookmark {
+        Some(item) => Some(format!("#{}", outline::slug(&item.title))),
+        None if marker == "heading" => Some(format!("#page-{}", page + 1)),
+        None => None,
+    }
+}
 
-    if let Some(color) = unit.color {
-        if color != "#FFFFFF" {
-            text = format!("`{}` ", text);
+/// Whether most of the text on a page is in a fixed-pitch font
+fn mostly_monospace(units: &[PdfUnit]) -> bool {
+    let (monospace, total) = units
+        .iter()
+        .filter_map(|unit| match unit {
+            PdfUnit::Text(pdf_text) => Some((pdf_text.monospace, pdf_text.text.trim().chars().count())),
+            _ => None,
+        })
+        .fold((0, 0), |(monospace, total), (is_monospace, count)| {
+            (monospace + if is_monospace { count } else { 0 }, total + count)
+        });
+    monospace * 2 > total
+}
+
+/// Removes the runs with a rotated baseline from the page, which would otherwise be
+/// scattered over the rows they cross, and joins each sequence of them into a line of its own
+fn take_rotated_text(units: &mut Vec<PdfUnit>) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    // start, width and space width of the previous run, if it was rotated too
+    let mut previous: Option<(f32, f32, f32, f32)> = None;
+    units.retain(|unit| {
+        let PdfUnit::Text(pdf_text) = unit else {
+            return true;
+        };
+        if !pdf_text.rotated {
+            previous = None;
+            return true;
+        }
+
+        let text = pdf_text.text.trim();
+        match (previous, labels.last_mut()) {
+            (Some((x, y, width, space_width)), Some(label)) => {
+                // the distance between the starts along the baseline, less the previous run
+                let gap = (pdf_text.x - x).hypot(pdf_text.y - y) - width;
+                if gap > space_width * 0.5 || pdf_text.text.starts_with(char::is_whitespace) {
+                    label.push(' ');
+                }
+                label.push_str(text);
+            }
+            _ => labels.push(text.to_string()),
         }
+        previous = Some((pdf_text.x, pdf_text.y, pdf_text.width, pdf_text.space_width));
+        false
+    });
+    labels.retain(|label| !label.is_empty());
+    labels
+}
+
+/// Which of the `page_count` pages to convert, from page numbers and ranges of them
+/// such as `1-5,10,20-`; every page when `range` is empty
+fn page_selection(range: &str, page_count: usize) -> Result<Vec<bool>, String> {
+    if range.trim().is_empty() {
+        return Ok(vec![true; page_count]);
     }
-    if let Some(name) = unit.font_name {
-        let lwc = name.to_lowercase();
-        if lwc.contains("bold") {
-            text = format!("**{}** ", text.trim());
+
+    let number = |text: &str, default: usize| -> Result<usize, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(default);
+        }
+        text.parse::<usize>()
+            .ok()
+            .filter(|n| *n >= 1)
+            .ok_or_else(|| format!("Invalid PDF page range '{}': '{}' is not a page number", range, text))
+    };
+    let mut selected = vec![false; page_count];
+    for part in range.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (number(first, 1)?, number(last, usize::MAX)?),
+            None => (number(part, 0)?, number(part, 0)?),
+        };
+        if first > last {
+            return Err(format!("Invalid PDF page range '{}': '{}' ends before it starts", range, part));
         }
-        if lwc.contains("italic") {
-            unit.italic = true;
+        for page in first..=last.min(page_count) {
+            selected[page - 1] = true;
         }
     }
+    if !selected.contains(&true) {
+        return Err(format!("PDF page range '{}' selects none of the {} pages", range, page_count));
+    }
+    Ok(selected)
+}
+
+/// What goes in front of the page `number`, `first` of the pages converted
+fn page_marker(marker: &str, number: usize, first: bool) -> String {
+    match marker {
+        "comment" => format!("\n\n<!-- S-TITLE: Page number {} -->\n", number),
+        "rule" if !first => "\n\n---\n\n".to_string(),
+        "heading" => format!("\n\n## Page {}\n\n", number),
+        _ => String::new(),
+    }
+}
+
+/// Turns one row of the page into a text line, with tables as ready Markdown.
+/// An empty row is a break between blocks.
+fn row_to_lines(row: Vec<PdfElement>, median_font_size: Option<f32>) -> Vec<Line> {
+    if row.is_empty() {
+        return vec![Line::Break];
+    }
+
+    let mut lines = Vec::new();
+    let mut texts: Vec<PdfText> = Vec::new();
+    for element in row {
+        match element {
+            PdfElement::Text(pdf_text) => texts.push(pdf_text),
+            PdfElement::Table(pdf_table) => {
+                if !texts.is_empty() {
+                    lines.push(Line::Text(text_line(take(&mut texts), median_font_size)));
+                }
+                lines.push(Line::Block(table_to_md(pdf_table)));
+            }
+            PdfElement::Image(pdf_image) => {
+                if !texts.is_empty() {
+                    lines.push(Line::Text(text_line(take(&mut texts), median_font_size)));
+                }
+                // an image that can't be written is left out rather than failing the page
+                if let Ok(image_md) = image_to_md(&pdf_image) {
+                    lines.push(Line::Block(format!("{}\n", image_md)));
+                }
+            }
+        }
+    }
+    if !texts.is_empty() {
+        lines.push(Line::Text(text_line(texts, median_font_size)));
+    }
+    lines
+}
+
+fn text_line(texts: Vec<PdfText>, median_font_size: Option<f32>) -> TextLine {
+    let mut line = TextLine {
+        left: f32::INFINITY,
+        right: f32::NEG_INFINITY,
+        y: f32::NEG_INFINITY,
+        ..TextLine::default()
+    };
+    let mut longest = 0;
+    // end, space width and trailing whitespace of the previous run
+    let mut previous: Option<(f32, f32, bool)> = None;
+    // runs styled alike are formatted together, as (run, separated from the previous span)
+    let mut spans: Vec<(PdfText, bool)> = Vec::new();
+
+    for (i, pdf_text) in texts.into_iter().enumerate() {
+        let size = pdf_text.font_size.unwrap_or_default();
+        // runs closer than half a space are parts of one word, unless they bring their own space
+        let separate = pdf_text.text.starts_with(char::is_whitespace)
+            || previous.is_none_or(|(end, space_width, ends_with_space)| {
+                ends_with_space || (pdf_text.x - end).abs() > space_width * 0.5
+            });
+        previous = Some((
+            pdf_text.x + pdf_text.width,
+            pdf_text.space_width,
+            pdf_text.text.ends_with(char::is_whitespace),
+        ));
+        line.left = line.left.min(pdf_text.x);
+        line.right = line.right.max(pdf_text.x + pdf_text.width);
+        line.y = line.y.max(pdf_text.y);
+        line.font_size = line.font_size.max(size);
+        if pdf_text.text.len() > longest {
+            longest = pdf_text.text.len();
+            line.font_name = pdf_text.font_name.clone();
+        }
+        if separate && i > 0 {
+            line.plain.push(' ');
+        }
+        line.plain.push_str(pdf_text.text.trim());
+
+        match spans.last_mut() {
+            Some((span, _)) if same_style(span, &pdf_text) => {
+                if separate {
+                    span.text.push(' ');
+                }
+                span.text.push_str(pdf_text.text.trim());
+            }
+            _ => spans.push((PdfText { text: pdf_text.text.trim().to_string(), ..pdf_text }, separate && i > 0)),
+        }
+    }
+
+    for (i, (span, separate)) in spans.into_iter().enumerate() {
+        let (text, header_level) = pdftext_to_md(span, median_font_size);
+        if i == 0 {
+            line.header = header_level;
+        }
+        if separate {
+            line.markdown.push(' ');
+        }
+        line.markdown.push_str(text.trim());
+    }
+
+    line.plain = line.plain.trim().to_string();
+    line.markdown = line.markdown.trim().to_string();
+    line
+}
+
+fn image_to_md(pdf_image: &PdfImage) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    let image_md = image2md::run(&pdf_image.data)?;
+    if cfg.image_path.as_os_str().is_empty() {
+        return Ok(image_md);
+    }
+
+    // image2md links saved images by file name; make that relative to the output file
+    let output_dir = cfg
+        .output_path
+        .as_ref()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(|p| p.parent().unwrap_or(Path::new(".")).to_path_buf());
+    let image_dir = match output_dir.and_then(|dir| cfg.image_path.strip_prefix(&dir).ok().map(Path::to_path_buf)) {
+        Some(relative) => relative,
+        None => cfg.image_path.canonicalize().unwrap_or(cfg.image_path.clone()),
+    };
+    let Some(start) = image_md.find("](") else {
+        return Ok(image_md);
+    };
+    let file_name = image_md[start + 2..].trim_end_matches(')');
+    let link = image_dir.join(file_name);
+    Ok(format!("{}]({})", &image_md[..start], link.to_string_lossy()))
+}
+
+fn table_to_md(mut pdf_table: PdfTable) -> String {
+    let header_rows = pdf_table.header_rows().max(1);
+    let elements = pdf_table.get_sorted_elements();
+    let elements: Vec<Vec<String>> = elements
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| {
+                    cell.iter()
+                        // never header
+                        .map(|item| pdftext_to_md(item.clone(), Some(1000.0)).0)
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .collect()
+        })
+        .collect();
+
+    // a header over several rows becomes one row, its cells joined by column
+    let mut rows = elements.into_iter();
+    let headers = rows.by_ref().take(header_rows).fold(Vec::new(), |mut headers: Vec<String>, row| {
+        headers.resize(headers.len().max(row.len()), String::new());
+        for (header, cell) in headers.iter_mut().zip(row) {
+            // header cells are set in bold anyway
+            let cell = strip_bold(cell.trim());
+            if !cell.is_empty() {
+                if !header.is_empty() {
+                    header.push(' ');
+                }
+                header.push_str(cell);
+            }
+        }
+        headers
+    });
+    let rows: Vec<Vec<String>> = rows.collect();
+
+    match headers.is_empty() || rows.is_empty() {
+        true => String::new(),
+        false => to_markdown_table(&headers, &rows),
+    }
+}
+
+/// Text without the bold markers around all of it
+fn strip_bold(text: &str) -> &str {
+    match text.strip_prefix("**").and_then(|t| t.strip_suffix("**")) {
+        Some(inner) if !inner.contains("**") => inner,
+        _ => text,
+    }
+}
+
+/// Whether two runs get the same inline formatting
+fn same_style(a: &PdfText, b: &PdfText) -> bool {
+    let shift = |t: &PdfText| (t.rise > t.height * 0.1) as i8 - (t.rise < -t.height * 0.1) as i8;
+    a.bold == b.bold
+        && a.italic == b.italic
+        && a.monospace == b.monospace
+        && a.underlined == b.underlined
+        && shift(a) == shift(b)
+        && a.link == b.link
+}
+
+/// Inline Markdown of a text run and the heading level its size suggests
+fn pdftext_to_md(unit: PdfText, median_size: Option<f32>) -> (String, &'static str) {
+    let mut text = unit.text;
+
+    if unit.monospace && !text.trim().is_empty() {
+        text = format!("`{}` ", text.trim());
+    }
+    if unit.bold {
+        text = format!("**{}** ", text.trim());
+    }
     if unit.italic {
         text = format!("*{}* ", text.trim());
     }
     if unit.underlined {
         text = format!("<u>{}</u> ", text.trim());
     }
-
-    let mut is_header = "";
-    if let Some(header) = font_size_to_header(unit.font_size.unwrap_or_default(), median_size) {
-        text = format!("{header} {} ", text.trim());
-        is_header = header;
+    // raised or lowered by a tenth of the font size or more
+    if unit.rise > unit.height * 0.1 {
+        text = format!("<sup>{}</sup>", text.trim());
+    } else if unit.rise < -unit.height * 0.1 {
+        text = format!("<sub>{}</sub>", text.trim());
+    }
+    if let Some(url) = &unit.link {
+        text = format!("[{}]({})", text.trim(), link_destination(url));
     }
 
-    return (text, is_header);
+    let header = font_size_to_header(unit.font_size.unwrap_or_default(), median_size).unwrap_or("");
+
+    (text, header)
+}
+
+/// A link target as a Markdown link destination: one with spaces or parentheses, which
+/// would end a bare destination early, goes in angle brackets
+fn link_destination(url: &str) -> String {
+    if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
 }
 
 fn font_size_to_header(font_size: f32, median_size: Option<f32>) -> Option<&'static str> {
@@ -137,26 +580,24 @@ struct Pdf {
 }
 
 impl Pdf {
-    fn new_from_bytes(bytes: &[u8]) -> Result<Pdf, Box<dyn Error>> {
-        let doc = lopdf::Document::load_from(bytes)?;
+    /// Loads a PDF. lopdf decrypts files that open with an empty user password, e.g. ones
+    /// restricted only by an owner password, by itself; others need `password`, the user or
+    /// the owner password (RC4, AES-128 or AES-256).
+    fn new_from_bytes(bytes: &[u8], password: Option<&str>) -> Result<Pdf, PdfOpenError> {
+        let mut doc = lopdf::Document::load_from(bytes).map_err(|e| PdfOpenError::Invalid(e.to_string()))?;
+        if doc.is_encrypted() {
+            let password = password.ok_or(PdfOpenError::PasswordRequired)?;
+            encryption::decrypt(&mut doc, password)?;
+        }
         let pdf = Pdf { doc };
 
         Ok(pdf)
     }
 
-    pub fn iter_pages(&self) -> impl Iterator<Item = Result<PdfPage, Box<dyn Error>>> {
-        self.doc
-            .page_iter()
-            .map(|id| PdfPage::from_object_id(&self.doc, id))
-    }
-
-    pub fn pdf_units_to_elements(units: Vec<PdfUnit>) -> Vec<Vec<PdfElement>> {
+    /// Rows of the page in reading order, with empty rows between blocks
+    pub fn pdf_units_to_elements(units: Vec<PdfUnit>, median_font_size: Option<f32>) -> Vec<Vec<PdfElement>> {
         let elements = pdf_element::units_to_elements(units);
-        let mut matrix = pdf_element::elements_into_matrix(elements);
-        for row in matrix.iter_mut() {
-            pdf_element::sort_transform_elements(row);
-        }
-        matrix
+        layout::reading_order(elements, median_font_size)
     }
 }
 
@@ -185,4 +626,63 @@ fn to_markdown_table(headers: &[String], rows: &[Vec<String>]) -> String {
     }
 
     output
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pages(range: &str, page_count: usize) -> Vec<usize> {
+        let selected = page_selection(range, page_count).unwrap();
+        (1..=page_count).filter(|page| selected[page - 1]).collect()
+    }
+
+    #[test]
+    fn test_page_selection_ranges() {
+        let mut expected: Vec<usize> = (1..=5).collect();
+        expected.push(10);
+        expected.extend(20..=25);
+        assert_eq!(pages("1-5,10,20-", 25), expected);
+        assert_eq!(pages(" 2 , 4-4 ", 5), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_page_selection_open_start() {
+        assert_eq!(pages("-3", 10), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_page_selection_empty_is_every_page() {
+        assert_eq!(pages("", 3), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_page_selection_clips_to_last_page() {
+        assert_eq!(pages("8-20", 10), vec![8, 9, 10]);
+    }
+
+    #[test]
+    fn test_page_selection_errors() {
+        assert!(page_selection("0", 10).is_err());
+        assert!(page_selection("5-3", 10).is_err());
+        assert!(page_selection("x", 10).is_err());
+        assert!(page_selection("11-", 10).is_err());
+        assert!(page_selection("12,15-20", 10).is_err());
+    }
+
+    fn link(text: &str, url: &str) -> String {
+        let unit = PdfText { text: text.to_string(), link: Some(url.to_string()), ..Default::default() };
+        pdftext_to_md(unit, None).0
+    }
+
+    #[test]
+    fn test_link_targets_are_escaped() {
+        assert_eq!(link("site", "https://example.com/a"), "[site](https://example.com/a)");
+        assert_eq!(link("file", "docs/my report.pdf"), "[file](<docs/my report.pdf>)");
+        assert_eq!(
+            link("wiki", "https://en.wikipedia.org/wiki/Rust_(language)"),
+            "[wiki](<https://en.wikipedia.org/wiki/Rust_(language)>)"
+        );
+        assert_eq!(link("odd", "a b<c>"), "[odd](<a b%3Cc%3E>)");
+    }
+}
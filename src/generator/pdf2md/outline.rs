@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet};
+
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+use super::paragraph::Line;
+use super::pdf_page::decode_text_string;
+
+/// Markdown heading markers by level
+const HEADINGS: [&str; 6] = ["#", "##", "###", "####", "#####", "######"];
+/// Named destinations that still lead to a name or dictionary after this many steps
+/// are taken to be cyclic
+const MAX_DESTINATION_DEPTH: usize = 8;
+
+/// A bookmark of the document outline
+#[derive(Debug, Clone)]
+pub struct OutlineItem {
+    pub title: String,
+    /// 1 for top-level bookmarks
+    pub level: usize,
+    /// Index of the page it points to
+    pub page: Option<usize>,
+    /// Height on the page it points to, if the destination gives one
+    pub top: Option<f32>,
+}
+
+/// Where a bookmark or link goes: a page index and a height on it
+pub type Destination = (usize, Option<f32>);
+
+/// Resolves explicit (`[page /XYZ left top zoom]`) and named destinations, and
+/// `/GoTo` actions, to the page they point to
+pub struct Destinations<'a> {
+    document: &'a Document,
+    pages: HashMap<ObjectId, usize>,
+    named: HashMap<Vec<u8>, &'a Object>,
+}
+
+impl<'a> Destinations<'a> {
+    pub fn new(document: &'a Document) -> Destinations<'a> {
+        let pages = document.page_iter().enumerate().map(|(i, id)| (id, i)).collect();
+        let mut named = HashMap::new();
+        if let Ok(catalog) = document.catalog() {
+            // PDF 1.1 /Dests dictionary
+            if let Ok((_, Object::Dictionary(dests))) = catalog.get(b"Dests").and_then(|o| document.dereference(o)) {
+                for (name, dest) in dests.iter() {
+                    named.insert(name.clone(), dest);
+                }
+            }
+            // /Names /Dests name tree
+            let tree = catalog
+                .get(b"Names")
+                .and_then(|o| document.dereference(o))
+                .and_then(|(_, o)| o.as_dict())
+                .and_then(|names| names.get(b"Dests"))
+                .and_then(|o| document.dereference(o))
+                .and_then(|(_, o)| o.as_dict());
+            if let Ok(tree) = tree {
+                collect_name_tree(document, tree, &mut named, &mut HashSet::new());
+            }
+        }
+        Destinations { document, pages, named }
+    }
+
+    /// The destination of an outline item or link annotation: its `/Dest`, or the `/D`
+    /// of its `/GoTo` action
+    pub fn of(&self, dict: &Dictionary) -> Option<Destination> {
+        if let Ok(dest) = dict.get(b"Dest") {
+            return self.resolve(dest, 0);
+        }
+        let (_, action) = self.document.dereference(dict.get(b"A").ok()?).ok()?;
+        let action = action.as_dict().ok()?;
+        match action.get(b"S").and_then(Object::as_name).ok()? {
+            b"GoTo" => self.resolve(action.get(b"D").ok()?, 0),
+            _ => None,
+        }
+    }
+
+    fn resolve(&self, dest: &Object, depth: usize) -> Option<Destination> {
+        if depth > MAX_DESTINATION_DEPTH {
+            return None;
+        }
+        let (_, dest) = self.document.dereference(dest).ok()?;
+        match dest {
+            Object::Name(name) | Object::String(name, _) => self.resolve(self.named.get(name)?, depth + 1),
+            // named destinations may be dictionaries with the array in /D
+            Object::Dictionary(dict) => self.resolve(dict.get(b"D").ok()?, depth + 1),
+            Object::Array(array) => {
+                let page = match array.first()? {
+                    Object::Reference(id) => *self.pages.get(id)?,
+                    // some producers give a page index instead of a reference
+                    Object::Integer(index) => usize::try_from(*index).ok()?,
+                    _ => return None,
+                };
+                let top_index = match array.get(1).and_then(|o| o.as_name().ok()) {
+                    Some(b"XYZ") => Some(3),
+                    Some(b"FitH") | Some(b"FitBH") => Some(2),
+                    Some(b"FitR") => Some(5),
+                    _ => None,
+                };
+                let top = top_index.and_then(|i| array.get(i)?.as_float().ok());
+                Some((page, top))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn collect_name_tree<'a>(
+    document: &'a Document,
+    node: &'a Dictionary,
+    named: &mut HashMap<Vec<u8>, &'a Object>,
+    seen: &mut HashSet<*const Dictionary>,
+) {
+    // guard against cyclic trees
+    if !seen.insert(node as *const Dictionary) {
+        return;
+    }
+    if let Ok(names) = node.get(b"Names").and_then(Object::as_array) {
+        for pair in names.chunks(2) {
+            if let [Object::String(name, _), dest] = pair {
+                named.insert(name.clone(), dest);
+            }
+        }
+    }
+    if let Ok(kids) = node.get(b"Kids").and_then(Object::as_array) {
+        for kid in kids {
+            if let Ok((_, Object::Dictionary(kid))) = document.dereference(kid) {
+                collect_name_tree(document, kid, named, seen);
+            }
+        }
+    }
+}
+
+/// The bookmarks of the document, depth first
+pub fn read_outline(document: &Document, destinations: &Destinations) -> Vec<OutlineItem> {
+    let mut items = Vec::new();
+    let first = document
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Outlines"))
+        .and_then(|o| document.dereference(o))
+        .and_then(|(_, o)| o.as_dict())
+        .and_then(|outlines| outlines.get(b"First"));
+    if let Ok(first) = first {
+        read_items(document, destinations, first, 1, &mut items, &mut HashSet::new());
+    }
+    items
+}
+
+fn read_items(
+    document: &Document,
+    destinations: &Destinations,
+    first: &Object,
+    level: usize,
+    items: &mut Vec<OutlineItem>,
+    seen: &mut HashSet<ObjectId>,
+) {
+    let mut next = Some(first);
+    while let Some(Object::Reference(id)) = next {
+        // guard against cyclic sibling or child links
+        if !seen.insert(*id) {
+            return;
+        }
+        let Ok(item) = document.get_dictionary(*id) else {
+            return;
+        };
+        let title = item
+            .get(b"Title")
+            .and_then(|o| document.dereference(o))
+            .and_then(|(_, o)| o.as_str())
+            .map(decode_text_string)
+            .unwrap_or_default();
+        let (page, top) = destinations.of(item).map_or((None, None), |(page, top)| (Some(page), top));
+        items.push(OutlineItem {
+            title: title.split_whitespace().collect::<Vec<&str>>().join(" "),
+            level,
+            page,
+            top,
+        });
+        if let Ok(child) = item.get(b"First") {
+            read_items(document, destinations, child, level + 1, items, seen);
+        }
+        next = item.get(b"Next").ok();
+    }
+}
+
+/// Lowercase letters and digits only, to compare bookmark titles with page text
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Gives the lines of a page that spell out a bookmark title the bookmark's heading
+/// level, which takes precedence over the level the font size suggests. A title may
+/// be wrapped over several lines.
+pub fn apply_headings(lines: &mut [Line], items: &[&OutlineItem]) {
+    for item in items {
+        let title = normalize(&item.title);
+        if title.is_empty() {
+            continue;
+        }
+        let header = HEADINGS[item.level.clamp(1, HEADINGS.len()) - 1];
+
+        // runs of lines spelling out the title, as (first, end)
+        let mut candidates = Vec::new();
+        for start in 0..lines.len() {
+            let mut rest = title.as_str();
+            let mut end = start;
+            while let Some(Line::Text(line)) = lines.get(end) {
+                let text = normalize(&line.plain);
+                match rest.strip_prefix(text.as_str()) {
+                    Some(remaining) if !text.is_empty() => {
+                        rest = remaining;
+                        end += 1;
+                        if rest.is_empty() {
+                            break;
+                        }
+                    }
+                    _ => break,
+                }
+            }
+            if rest.is_empty() {
+                candidates.push((start, end));
+            }
+        }
+
+        // the one nearest the height the bookmark points at, else the first
+        let distance = |start: usize| match (&lines[start], item.top) {
+            (Line::Text(line), Some(top)) => (line.y - top).abs(),
+            _ => 0.0,
+        };
+        let best = candidates
+            .into_iter()
+            .min_by(|a, b| distance(a.0).partial_cmp(&distance(b.0)).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some((start, end)) = best {
+            for line in &mut lines[start..end] {
+                if let Line::Text(line) = line {
+                    line.header = header;
+                }
+            }
+        }
+    }
+}
+
+/// GitHub-style anchor of a heading
+pub fn slug(title: &str) -> String {
+    title
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Nested list of the bookmarks, linked to their headings
+pub fn table_of_contents(items: &[OutlineItem]) -> String {
+    let mut toc = String::from("## Contents\n\n");
+    for item in items.iter().filter(|item| !item.title.is_empty()) {
+        let indent = "  ".repeat(item.level.saturating_sub(1));
+        toc.push_str(&format!("{}- [{}](#{})\n", indent, item.title, slug(&item.title)));
+    }
+    toc.push('\n');
+    toc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::dictionary;
+
+    /// One page, with the catalog's `/Dests` as given
+    fn document(dests: Dictionary) -> (Document, ObjectId) {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let page_id = doc.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_id.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id, "Dests" => dests });
+        doc.trailer.set("Root", catalog_id);
+        (doc, page_id)
+    }
+
+    #[test]
+    fn test_named_destination() {
+        let (mut doc, page_id) = document(Dictionary::new());
+        let dests = dictionary! {
+            "intro" => vec![page_id.into(), "XYZ".into(), 0.into(), 700.into(), Object::Null],
+            "alias" => Object::Name(b"intro".to_vec()),
+        };
+        doc.catalog_mut().unwrap().set("Dests", dests);
+        let destinations = Destinations::new(&doc);
+        assert_eq!(destinations.pages.get(&page_id), Some(&0));
+        let link = dictionary! { "Dest" => Object::Name(b"alias".to_vec()) };
+        assert_eq!(destinations.of(&link), Some((0, Some(700.0))));
+    }
+
+    #[test]
+    fn test_cyclic_named_destinations() {
+        let (doc, _) = document(dictionary! {
+            "a" => Object::Name(b"b".to_vec()),
+            "b" => dictionary! { "D" => Object::Name(b"a".to_vec()) },
+            "self" => Object::Name(b"self".to_vec()),
+        });
+        let destinations = Destinations::new(&doc);
+        for name in [&b"a"[..], b"b", b"self"] {
+            let link = dictionary! { "Dest" => Object::Name(name.to_vec()) };
+            assert_eq!(destinations.of(&link), None);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use lopdf::{Dictionary, Document, Object};
+
+use super::pdf_page::decode_text_string;
+
+/// Number trees nested deeper than this are taken to be broken
+const MAX_DEPTH: usize = 16;
+/// Largest number written in roman numerals (MMMCMXCIX); larger ones are labelled in decimal
+const MAX_ROMAN: i64 = 3999;
+/// Largest number written in letters (twenty Zs); larger ones are labelled in decimal
+const MAX_LETTERS: i64 = 26 * 20;
+
+/// A page label range: the labels of the pages from `start` on
+struct LabelRange {
+    /// Index of the first page of the range
+    start: usize,
+    /// D, R, r, A or a; no style labels the pages with the prefix only
+    style: Option<Vec<u8>>,
+    prefix: String,
+    /// Number of the first page of the range
+    first: i64,
+}
+
+/// The label of each of the `page_count` pages, from the catalog's `/PageLabels`
+/// number tree ("iv", "A-3"), or the page number where it gives none
+pub fn page_labels(document: &Document, page_count: usize) -> Vec<String> {
+    let mut ranges = Vec::new();
+    let tree = document
+        .catalog()
+        .and_then(|catalog| catalog.get(b"PageLabels"))
+        .and_then(|o| document.dereference(o))
+        .and_then(|(_, o)| o.as_dict());
+    if let Ok(tree) = tree {
+        read_number_tree(document, tree, 0, &mut ranges);
+    }
+    ranges.sort_by_key(|range| range.start);
+
+    (0..page_count)
+        .map(|i| match ranges.iter().rev().find(|range| range.start <= i) {
+            Some(range) => label(range, i),
+            None => (i + 1).to_string(),
+        })
+        .collect()
+}
+
+/// Collects the ranges of a number tree node and its kids
+fn read_number_tree(document: &Document, node: &Dictionary, depth: usize, ranges: &mut Vec<LabelRange>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let array = |key: &[u8]| {
+        node.get(key)
+            .and_then(|o| document.dereference(o))
+            .and_then(|(_, o)| o.as_array())
+            .ok()
+    };
+
+    if let Some(nums) = array(b"Nums") {
+        for pair in nums.chunks_exact(2) {
+            let (Ok(start), Ok((_, Object::Dictionary(dict)))) = (pair[0].as_i64(), document.dereference(&pair[1])) else {
+                continue;
+            };
+            let Ok(start) = usize::try_from(start) else {
+                continue;
+            };
+            ranges.push(LabelRange {
+                start,
+                style: dict.get(b"S").and_then(Object::as_name).ok().map(<[u8]>::to_vec),
+                prefix: dict.get(b"P").and_then(Object::as_str).map(decode_text_string).unwrap_or_default(),
+                first: dict.get(b"St").and_then(Object::as_i64).unwrap_or(1).max(1),
+            });
+        }
+    }
+    for kid in array(b"Kids").into_iter().flatten() {
+        if let Ok((_, Object::Dictionary(kid))) = document.dereference(kid) {
+            read_number_tree(document, kid, depth + 1, ranges);
+        }
+    }
+}
+
+fn label(range: &LabelRange, page: usize) -> String {
+    let number = range.first.saturating_add((page - range.start) as i64);
+    let numbering = match range.style.as_deref() {
+        Some(b"R") if number <= MAX_ROMAN => roman(number),
+        Some(b"r") if number <= MAX_ROMAN => roman(number).to_lowercase(),
+        Some(b"A") if number <= MAX_LETTERS => letters(number),
+        Some(b"a") if number <= MAX_LETTERS => letters(number).to_lowercase(),
+        Some(b"D" | b"R" | b"r" | b"A" | b"a") => number.to_string(),
+        _ => String::new(),
+    };
+    format!("{}{}", range.prefix, numbering)
+}
+
+pub fn roman(mut number: i64) -> String {
+    const NUMERALS: [(i64, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    roman
+}
+
+/// A to Z, then AA to ZZ, AAA to ZZZ and so on
+fn letters(number: i64) -> String {
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+    letter.to_string().repeat(((number - 1) / 26 + 1) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(style: &[u8], first: i64) -> LabelRange {
+        LabelRange {
+            start: 0,
+            style: Some(style.to_vec()),
+            prefix: String::new(),
+            first,
+        }
+    }
+
+    #[test]
+    fn test_label_styles() {
+        assert_eq!(label(&range(b"D", 1), 4), "5");
+        assert_eq!(label(&range(b"r", 1), 3), "iv");
+        assert_eq!(label(&range(b"R", 1990), 0), "MCMXC");
+        assert_eq!(label(&range(b"A", 1), 27), "BB");
+        assert_eq!(label(&range(b"a", 26), 0), "z");
+    }
+
+    #[test]
+    fn test_huge_start_falls_back_to_decimal() {
+        assert_eq!(label(&range(b"R", 4000), 0), "4000");
+        assert_eq!(label(&range(b"a", 1_000_000_000_000), 1), "1000000000001");
+        assert_eq!(label(&range(b"D", i64::MAX), 3), i64::MAX.to_string());
+    }
+}
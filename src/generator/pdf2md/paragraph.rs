@@ -0,0 +1,360 @@
+/// A first line indented by this many font sizes starts a paragraph
+const INDENT: f32 = 0.8;
+/// A line ending this many font sizes short of the block's right edge, after a full stop,
+/// is the last line of its paragraph
+const SHORT_LINE: f32 = 4.0;
+/// Without an established line spacing, baselines further apart than this many font sizes
+/// belong to different paragraphs
+const MAX_LINE_SPACING: f32 = 2.0;
+
+/// Hyphenated prefixes that stay hyphenated when a line break falls after them
+const HYPHENATED_PREFIXES: [&str; 8] = ["self", "well", "ex", "all", "half", "cross", "quasi", "e"];
+
+/// One row of a page as the reflow sees it
+pub enum Line {
+    Text(TextLine),
+    /// Ready Markdown, e.g. a table
+    Block(String),
+    /// Vertical whitespace between layout blocks
+    Break,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TextLine {
+    /// Formatted text of the row
+    pub markdown: String,
+    /// The text without formatting
+    pub plain: String,
+    /// Heading marker (`#`, `##`, ...) or empty
+    pub header: &'static str,
+    pub left: f32,
+    pub right: f32,
+    /// Baseline
+    pub y: f32,
+    pub font_size: f32,
+    /// Font of the longest run in the row
+    pub font_name: Option<String>,
+}
+
+struct Paragraph {
+    header: &'static str,
+    markdown: String,
+    last: TextLine,
+    /// Baseline distance between the first two lines
+    spacing: Option<f32>,
+}
+
+/// Joins wrapped lines into paragraphs and paragraphs into Markdown.
+///
+/// A line continues the paragraph above it unless a vertical gap, a first-line indent,
+/// a change of font or heading level, a short line ending a sentence, or a list marker
+/// says otherwise. Words hyphenated at a line end are joined again.
+pub fn reflow(lines: Vec<Line>) -> String {
+    let block_rights = block_right_edges(&lines);
+    let mut markdown = String::new();
+    let mut paragraph: Option<Paragraph> = None;
+
+    for (line, block_right) in lines.into_iter().zip(block_rights) {
+        match line {
+            Line::Text(line) => {
+                let continues = paragraph
+                    .as_ref()
+                    .is_some_and(|p| continues_paragraph(p, &line, block_right));
+                match paragraph.as_mut() {
+                    Some(p) if continues => append_line(p, line),
+                    _ => {
+                        flush(&mut markdown, paragraph.take());
+                        paragraph = Some(Paragraph {
+                            header: line.header,
+                            markdown: line.markdown.trim().to_string(),
+                            last: line,
+                            spacing: None,
+                        });
+                    }
+                }
+            }
+            Line::Block(block) => {
+                flush(&mut markdown, paragraph.take());
+                markdown.push_str(&block);
+                markdown.push('\n');
+            }
+            Line::Break => flush(&mut markdown, paragraph.take()),
+        }
+    }
+    flush(&mut markdown, paragraph.take());
+
+    markdown
+}
+
+/// Joins the lines of one known paragraph, e.g. a structure element of a tagged PDF,
+/// the way `reflow` joins wrapped lines
+pub fn join_lines(lines: Vec<TextLine>) -> String {
+    let mut lines = lines.into_iter();
+    let Some(first) = lines.next() else {
+        return String::new();
+    };
+    let mut paragraph = Paragraph {
+        header: first.header,
+        markdown: first.markdown.trim().to_string(),
+        last: first,
+        spacing: None,
+    };
+    for line in lines {
+        append_line(&mut paragraph, line);
+    }
+    paragraph.markdown
+}
+
+/// Right edge of the text of the block (run of lines between breaks) each line is in
+fn block_right_edges(lines: &[Line]) -> Vec<f32> {
+    let mut rights = Vec::with_capacity(lines.len());
+    for block in lines.split_inclusive(|line| matches!(line, Line::Break)) {
+        let right = block
+            .iter()
+            .filter_map(|line| match line {
+                Line::Text(text) => Some(text.right),
+                _ => None,
+            })
+            .fold(f32::NEG_INFINITY, f32::max);
+        rights.extend(std::iter::repeat_n(right, block.len()));
+    }
+    rights
+}
+
+fn continues_paragraph(paragraph: &Paragraph, line: &TextLine, block_right: f32) -> bool {
+    let last = &paragraph.last;
+    let size = last.font_size.max(line.font_size).max(1.0);
+
+    if line.header != paragraph.header {
+        return false;
+    }
+    if !line.header.is_empty() {
+        // a heading wrapped over several lines
+        return true;
+    }
+
+    let gap = last.y - line.y;
+    let max_gap = match paragraph.spacing {
+        Some(spacing) => spacing * 1.3 + 0.5,
+        None => size * MAX_LINE_SPACING,
+    };
+    let same_font = (last.font_size - line.font_size).abs() <= 0.5 && last.font_name == line.font_name;
+    let indented = line.left - last.left > size * INDENT;
+    let ends_sentence = last
+        .plain
+        .trim_end()
+        .ends_with(['.', '!', '?', ':', '。', '！', '？', '：']);
+    let short = block_right - last.right > size * SHORT_LINE;
+
+    gap > 0.0
+        && gap <= max_gap
+        && same_font
+        && !indented
+        && !(short && ends_sentence)
+        && !starts_list_item(&line.plain)
+}
+
+fn append_line(paragraph: &mut Paragraph, line: TextLine) {
+    if paragraph.spacing.is_none() {
+        paragraph.spacing = Some(paragraph.last.y - line.y);
+    }
+
+    let next = line.markdown.trim();
+    let text = &mut paragraph.markdown;
+    let hyphenated = match text.ends_with('-') {
+        true => line_end_hyphen(&paragraph.last.plain, &line.plain),
+        false => None,
+    };
+    if let Some(hyphen) = hyphenated {
+        if !hyphen.keep {
+            text.pop();
+        }
+        text.push_str(next);
+    } else if text.chars().last().is_some_and(is_cjk) && next.chars().next().is_some_and(is_cjk) {
+        // CJK text has no spaces between words
+        text.push_str(next);
+    } else {
+        text.push(' ');
+        text.push_str(next);
+    }
+    paragraph.last = line;
+}
+
+/// A word broken at a hyphen at the end of a line
+struct LineEndHyphen {
+    /// Compounds ("self-" + "contained", "ISO-" + "8601") keep the hyphen,
+    /// a word hyphenated for the line break ("conver-" + "sion") loses it
+    keep: bool,
+}
+
+/// Whether `previous` ends in a word broken at a hyphen that `next` continues
+fn line_end_hyphen(previous: &str, next: &str) -> Option<LineEndHyphen> {
+    let head = previous.trim_end().strip_suffix('-')?;
+    let fragment: String = head
+        .chars()
+        .rev()
+        .take_while(|c| c.is_alphanumeric())
+        .collect::<Vec<char>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let first = next.trim_start().chars().next()?;
+    if fragment.is_empty() || !first.is_alphanumeric() {
+        // a dash, not a hyphen
+        return None;
+    }
+
+    let joined_word = fragment.chars().count() >= 2
+        && fragment.chars().all(char::is_lowercase)
+        && first.is_lowercase()
+        && !HYPHENATED_PREFIXES.contains(&fragment.as_str());
+    Some(LineEndHyphen { keep: !joined_word })
+}
+
+/// Bullets, "1." / "1)" / "a)" numbering
+fn starts_list_item(text: &str) -> bool {
+    let text = text.trim_start();
+    if text.starts_with(['•', '◦', '▪', '‣', '–', '-', '*', '·']) {
+        return true;
+    }
+    let marker: String = text.chars().take_while(|c| c.is_ascii_alphanumeric()).collect();
+    let rest = &text[marker.len()..];
+    let numbered = !marker.is_empty()
+        && (marker.chars().all(|c| c.is_ascii_digit()) || marker.chars().count() == 1);
+    numbered && (rest.starts_with(". ") || rest.starts_with(") "))
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3000}'..='\u{303F}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FFEF}')
+}
+
+fn flush(markdown: &mut String, paragraph: Option<Paragraph>) {
+    let Some(paragraph) = paragraph.filter(|p| !p.markdown.is_empty()) else {
+        return;
+    };
+    if paragraph.header.is_empty() {
+        markdown.push_str(&format!("{}\n\n", paragraph.markdown));
+    } else {
+        markdown.push_str(&format!("{} {}\n\n", paragraph.header, paragraph.markdown));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10pt line of body text from x = 72 to `right`, on baseline `y`
+    fn line(text: &str, y: f32, right: f32) -> TextLine {
+        TextLine {
+            markdown: text.to_string(),
+            plain: text.to_string(),
+            left: 72.0,
+            right,
+            y,
+            font_size: 10.0,
+            font_name: Some("Times-Roman".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Lines 12 units apart, all but the last running to the right margin
+    fn wrapped(texts: &[&str]) -> Vec<Line> {
+        texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let right = if i + 1 == texts.len() { 300.0 } else { 540.0 };
+                Line::Text(line(text, 700.0 - 12.0 * i as f32, right))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hyphenated_line_ends() {
+        let joined = reflow(wrapped(&["the conver-", "sion of text"]));
+        assert_eq!(joined, "the conversion of text\n\n");
+
+        let compound = reflow(wrapped(&["a self-", "contained example"]));
+        assert_eq!(compound, "a self-contained example\n\n");
+
+        let proper_noun = reflow(wrapped(&["from Baden-", "Württemberg"]));
+        assert_eq!(proper_noun, "from Baden-Württemberg\n\n");
+
+        let code = reflow(wrapped(&["dates in ISO-", "8601 format"]));
+        assert_eq!(code, "dates in ISO-8601 format\n\n");
+
+        let dash = reflow(wrapped(&["one thing -", "and another"]));
+        assert_eq!(dash, "one thing - and another\n\n");
+    }
+
+    #[test]
+    fn test_paragraph_breaks() {
+        let mut lines = wrapped(&["First paragraph runs on", "over two lines."]);
+        // short line ending a sentence, then an indented first line
+        let mut indented = line("Second paragraph", 676.0, 540.0);
+        indented.left = 90.0;
+        lines.push(Line::Text(indented));
+        lines.push(Line::Text(line("continues here", 664.0, 300.0)));
+        // too far below
+        lines.push(Line::Text(line("Third after a gap", 620.0, 300.0)));
+        lines.push(Line::Text(line("1. a list item", 608.0, 300.0)));
+        lines.push(Line::Break);
+        lines.push(Line::Text(line("After a break", 596.0, 300.0)));
+
+        assert_eq!(
+            reflow(lines),
+            concat!(
+                "First paragraph runs on over two lines.\n\n",
+                "Second paragraph continues here\n\n",
+                "Third after a gap\n\n",
+                "1. a list item\n\n",
+                "After a break\n\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_headings_and_font_changes_start_paragraphs() {
+        let heading = |text: &str, y: f32| TextLine { header: "##", font_size: 16.0, ..line(text, y, 400.0) };
+        let mut code = line("let x = 1;", 640.0, 200.0);
+        code.font_name = Some("Courier".to_string());
+        let lines = vec![
+            Line::Text(heading("A heading wrapped", 700.0)),
+            Line::Text(heading("over two lines", 682.0)),
+            Line::Text(line("Body text", 664.0, 540.0)),
+            Line::Text(line("continued", 652.0, 540.0)),
+            Line::Text(code),
+            Line::Block("| a |\n|---|".to_string()),
+            Line::Text(line("Closing words", 600.0, 300.0)),
+        ];
+
+        assert_eq!(
+            reflow(lines),
+            concat!(
+                "## A heading wrapped over two lines\n\n",
+                "Body text continued\n\n",
+                "let x = 1;\n\n",
+                "| a |\n|---|\n",
+                "Closing words\n\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_cjk_lines_join_without_space() {
+        assert_eq!(reflow(wrapped(&["这是一个", "测试。"])), "这是一个测试。\n\n");
+    }
+
+    #[test]
+    fn test_join_lines_of_a_known_paragraph() {
+        let lines = vec![line("A tagged para-", 700.0, 540.0), line("graph, far below", 500.0, 300.0)];
+        assert_eq!(join_lines(lines), "A tagged paragraph, far below");
+        assert_eq!(join_lines(Vec::new()), "");
+    }
+}
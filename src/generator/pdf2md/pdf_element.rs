@@ -1,11 +1,25 @@
+use super::borderless;
+use super::pdf_image::PdfImage;
+
 #[derive(Clone)]
 pub enum PdfElement {
     Text(PdfText),
     Table(PdfTable),
+    Image(PdfImage),
 }
 pub enum PdfUnit {
     Text(PdfText),
     Line(PdfLine),
+    Image(PdfImage),
+}
+
+/// The marked-content sequence a unit was drawn in
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct MarkedContent {
+    /// Marked-content ID, which ties the content to the structure tree of a tagged PDF
+    pub mcid: Option<i64>,
+    /// Page furniture such as running headers, page numbers and decoration
+    pub artifact: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -17,11 +31,27 @@ pub struct PdfLine {
 #[derive(Default, Clone, Debug)]
 pub struct PdfText {
     pub text: String,
+    pub bold: bool,
     pub italic: bool,
+    /// Set in a fixed-pitch font, i.e. code
+    pub monospace: bool,
     pub font_name: Option<String>,
     pub font_size: Option<f32>,
     pub x: f32,
     pub y: f32,
+    /// Advance of the run along the baseline, in page units
+    pub width: f32,
+    /// Font size in page units
+    pub height: f32,
+    /// Width of a space in the run's font, in page units
+    pub space_width: f32,
+    /// Text rise above the baseline (negative below), in page units
+    pub rise: f32,
+    /// Runs whose baseline isn't horizontal, e.g. vertical labels
+    pub rotated: bool,
+    /// Target of the link annotation the run is in
+    pub link: Option<String>,
+    pub marked: MarkedContent,
     pub underlined: bool,
     pub color: Option<String>,
 }
@@ -34,11 +64,16 @@ struct TableBoundary {
     maxy: f32,
     elements: Vec<PdfText>,
 }
+/// A cell of a table as its (left, right, bottom, top) and its runs
+pub type TableCell = ((f32, f32, f32, f32), Vec<PdfText>);
+
 #[derive(Default, Clone, Debug)]
 pub struct PdfTable {
     boundaries: Vec<TableBoundary>,
     y: f32, // just the center of it
     x: f32, // just the center of it
+    /// Leading rows that make up the header
+    header_rows: usize,
 }
 
 impl PdfElement {
@@ -46,12 +81,14 @@ impl PdfElement {
         match self {
             PdfElement::Text(pdf_text) => pdf_text.y,
             PdfElement::Table(pdf_table) => pdf_table.y,
+            PdfElement::Image(pdf_image) => pdf_image.y,
         }
     }
     pub fn get_x(&self) -> f32 {
         match self {
             PdfElement::Text(pdf_text) => pdf_text.x,
             PdfElement::Table(pdf_table) => pdf_table.x,
+            PdfElement::Image(pdf_image) => pdf_image.x,
         }
     }
 }
@@ -215,7 +252,12 @@ fn intersections_to_table(mut intersections: Vec<(f32, f32)>) -> Option<PdfTable
     if boundaries.len() <= 1 {
         return None;
     }
-    Some(PdfTable { boundaries, x, y })
+    Some(PdfTable {
+        boundaries,
+        x,
+        y,
+        header_rows: 1,
+    })
 }
 
 fn lines_to_intersections(lines: Vec<PdfLine>) -> Vec<(f32, f32)> {
@@ -382,6 +424,30 @@ impl PdfTable {
         pdf_tables
     }
 
+    /// A table of known cells, e.g. found from the alignment of text. `rows` go top to
+    /// bottom; the cells of a row share its bottom.
+    pub fn from_cells(rows: Vec<Vec<TableCell>>, header_rows: usize) -> PdfTable {
+        let boundaries: Vec<TableBoundary> = rows
+            .into_iter()
+            .flatten()
+            .map(|((minx, maxx, miny, maxy), elements)| TableBoundary { minx, maxx, miny, maxy, elements })
+            .collect();
+        let (minx, maxx, miny, maxy) = boundaries.iter().fold(
+            (f32::INFINITY, f32::NEG_INFINITY, f32::INFINITY, f32::NEG_INFINITY),
+            |(minx, maxx, miny, maxy), b| (minx.min(b.minx), maxx.max(b.maxx), miny.min(b.miny), maxy.max(b.maxy)),
+        );
+        PdfTable {
+            boundaries,
+            x: (minx + maxx) / 2.0,
+            y: (miny + maxy) / 2.0,
+            header_rows,
+        }
+    }
+
+    pub fn header_rows(&self) -> usize {
+        self.header_rows
+    }
+
     pub fn assign(&mut self, element: &PdfText) -> bool {
         for boundary in self.boundaries.iter_mut() {
             if boundary.assign(element) {
@@ -391,6 +457,21 @@ impl PdfTable {
         false
     }
 
+    /// (minx, maxx, miny, maxy) over all cells
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        self.boundaries.iter().fold(
+            (self.x, self.x, self.y, self.y),
+            |(minx, maxx, miny, maxy), b| {
+                (
+                    minx.min(b.minx).min(b.maxx),
+                    maxx.max(b.minx).max(b.maxx),
+                    miny.min(b.miny).min(b.maxy),
+                    maxy.max(b.miny).max(b.maxy),
+                )
+            },
+        )
+    }
+
     pub fn get_sorted_elements(&mut self) -> Vec<Vec<Vec<PdfText>>> {
         self.boundaries.sort_by(|a, b| {
             b.miny.partial_cmp(&a.miny).unwrap().then(
@@ -458,22 +539,23 @@ impl TableBoundary {
 }
 
 pub fn units_to_elements(units: Vec<PdfUnit>) -> Vec<PdfElement> {
-    let (texts, lines): (Vec<_>, Vec<_>) =
+    let (texts, lines, images): (Vec<_>, Vec<_>, Vec<_>) =
         units
             .into_iter()
-            .fold((vec![], vec![]), |(mut texts, mut lines), unit| {
+            .fold((vec![], vec![], vec![]), |(mut texts, mut lines, mut images), unit| {
                 match unit {
                     PdfUnit::Text(t) => texts.push(t),
                     PdfUnit::Line(l) => lines.push(l),
+                    PdfUnit::Image(i) => images.push(i),
                 }
-                (texts, lines)
+                (texts, lines, images)
             });
     let mut tables = PdfTable::from_lines(lines);
     // assign to the tables
     let texts = if !tables.is_empty() {
         let mut new_texts = Vec::new();
-        let mut assigned = false;
         for text in texts.iter() {
+            let mut assigned = false;
             for table in tables.iter_mut() {
                 if table.assign(text) {
                     assigned = true;
@@ -487,12 +569,17 @@ pub fn units_to_elements(units: Vec<PdfUnit>) -> Vec<PdfElement> {
     } else {
         texts
     };
+    // tables without ruling lines, from the alignment of the remaining text
+    let (borderless, texts) = borderless::detect(texts);
+    tables.extend(borderless);
 
     let mut elements: Vec<PdfElement> = Vec::new();
     let texts: Vec<PdfElement> = texts.into_iter().map(PdfElement::Text).collect();
     let tables: Vec<PdfElement> = tables.into_iter().map(PdfElement::Table).collect();
+    let images: Vec<PdfElement> = images.into_iter().map(PdfElement::Image).collect();
     elements.extend(texts);
     elements.extend(tables);
+    elements.extend(images);
     elements
 }
 
@@ -0,0 +1,911 @@
+use std::collections::HashMap;
+
+use lopdf::{Dictionary, Document, Encoding, Object};
+
+use super::glyph_list::GLYPH_NAMES;
+
+/// A range of input codes of a CMap, `<lo> <hi>` with both of the same length
+#[derive(Debug, Clone)]
+struct CodespaceRange {
+    low: Vec<u8>,
+    high: Vec<u8>,
+}
+
+impl CodespaceRange {
+    fn two_bytes() -> Vec<CodespaceRange> {
+        vec![CodespaceRange {
+            low: vec![0x00, 0x00],
+            high: vec![0xFF, 0xFF],
+        }]
+    }
+
+    fn contains(&self, bytes: &[u8]) -> bool {
+        bytes.len() == self.low.len()
+            && bytes
+                .iter()
+                .zip(self.low.iter().zip(&self.high))
+                .all(|(b, (low, high))| (low..=high).contains(&b))
+    }
+}
+
+/// The parts of a CMap file that text extraction needs: code lengths and, for a
+/// `/ToUnicode` CMap, what each code maps to
+#[derive(Debug, Default, Clone)]
+struct CMap {
+    codespace: Vec<CodespaceRange>,
+    /// (code length, code) -> text
+    unicode: HashMap<(usize, u32), String>,
+    /// (code length, code) -> CID, for an embedded `/Encoding` CMap
+    cids: HashMap<(usize, u32), u32>,
+}
+
+/// Tokens of the PostScript subset CMap files are written in
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Hex(Vec<u8>),
+    Name(String),
+    Keyword(String),
+    ArrayStart,
+    ArrayEnd,
+}
+
+fn tokenize(data: &[u8]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        match data[i] {
+            b'%' => {
+                while i < data.len() && data[i] != b'\n' && data[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            b'<' if data.get(i + 1) == Some(&b'<') => i += 2,
+            b'>' if data.get(i + 1) == Some(&b'>') => i += 2,
+            b'<' => {
+                let end = data[i..]
+                    .iter()
+                    .position(|b| *b == b'>')
+                    .map_or(data.len(), |p| i + p);
+                let digits: Vec<u8> = data[i + 1..end]
+                    .iter()
+                    .filter(|b| b.is_ascii_hexdigit())
+                    .copied()
+                    .collect();
+                let bytes = digits
+                    .chunks(2)
+                    .map(|pair| {
+                        let text = String::from_utf8_lossy(pair);
+                        // an odd final digit is followed by an implied 0
+                        u8::from_str_radix(&format!("{:0<2}", text), 16).unwrap_or(0)
+                    })
+                    .collect();
+                tokens.push(Token::Hex(bytes));
+                i = end + 1;
+            }
+            b'[' => {
+                tokens.push(Token::ArrayStart);
+                i += 1;
+            }
+            b']' => {
+                tokens.push(Token::ArrayEnd);
+                i += 1;
+            }
+            b'(' => {
+                // literal strings (e.g. in CIDSystemInfo) are not needed
+                let mut depth = 0;
+                while i < data.len() {
+                    match data[i] {
+                        b'\\' => i += 1,
+                        b'(' => depth += 1,
+                        b')' => depth -= 1,
+                        _ => {}
+                    }
+                    i += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                let start = i;
+                i += 1;
+                while i < data.len() && !b"[]<>()/% \t\r\n\x0c{}".contains(&data[i]) {
+                    i += 1;
+                }
+                let word = String::from_utf8_lossy(&data[start..i]).to_string();
+                match word.strip_prefix('/') {
+                    Some(name) => tokens.push(Token::Name(name.to_string())),
+                    None => tokens.push(Token::Keyword(word)),
+                }
+            }
+        }
+    }
+    tokens
+}
+
+fn code_of(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |code, b| (code << 8) | *b as u32)
+}
+
+/// Text of a `/ToUnicode` destination: UTF-16BE bytes, or a glyph name
+fn destination_text(token: &Token) -> Option<String> {
+    match token {
+        Token::Hex(bytes) => {
+            let units: Vec<u16> = bytes
+                .chunks(2)
+                .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        }
+        Token::Name(name) => glyph_name_to_unicode(name),
+        _ => None,
+    }
+}
+
+impl CMap {
+    fn parse(data: &[u8]) -> CMap {
+        let tokens = tokenize(data);
+        let mut cmap = CMap::default();
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let Token::Keyword(keyword) = &tokens[i] else {
+                i += 1;
+                continue;
+            };
+            i += 1;
+            match keyword.as_str() {
+                "begincodespacerange" => {
+                    while let (Some(Token::Hex(low)), Some(Token::Hex(high))) =
+                        (tokens.get(i), tokens.get(i + 1))
+                    {
+                        cmap.codespace.push(CodespaceRange {
+                            low: low.clone(),
+                            high: high.clone(),
+                        });
+                        i += 2;
+                    }
+                }
+                "beginbfchar" => {
+                    while let (Some(Token::Hex(source)), Some(destination)) =
+                        (tokens.get(i), tokens.get(i + 1))
+                    {
+                        if let Some(text) = destination_text(destination) {
+                            cmap.unicode.insert((source.len(), code_of(source)), text);
+                        }
+                        i += 2;
+                    }
+                }
+                "beginbfrange" => {
+                    while let (Some(Token::Hex(low)), Some(Token::Hex(high))) =
+                        (tokens.get(i), tokens.get(i + 1))
+                    {
+                        i += 2;
+                        let (low_code, high_code) = (code_of(low), code_of(high));
+                        // ranges are meant to stay within the last byte; cap malformed ones
+                        let count = high_code.saturating_sub(low_code).min(0xFFFF);
+                        match tokens.get(i) {
+                            Some(Token::Hex(start)) => {
+                                i += 1;
+                                for offset in 0..=count {
+                                    let mut units: Vec<u16> = start
+                                        .chunks(2)
+                                        .map(|pair| {
+                                            u16::from_be_bytes([
+                                                pair[0],
+                                                *pair.get(1).unwrap_or(&0),
+                                            ])
+                                        })
+                                        .collect();
+                                    if let Some(last) = units.last_mut() {
+                                        *last = last.wrapping_add(offset as u16);
+                                    }
+                                    let text = String::from_utf16_lossy(&units);
+                                    cmap.unicode.insert((low.len(), low_code + offset), text);
+                                }
+                            }
+                            Some(Token::ArrayStart) => {
+                                i += 1;
+                                let mut offset = 0;
+                                while let Some(token) =
+                                    tokens.get(i).filter(|t| **t != Token::ArrayEnd)
+                                {
+                                    if let Some(text) =
+                                        destination_text(token).filter(|_| offset <= count)
+                                    {
+                                        cmap.unicode.insert((low.len(), low_code + offset), text);
+                                    }
+                                    offset += 1;
+                                    i += 1;
+                                }
+                                i += 1;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                "begincidchar" => {
+                    while let (Some(Token::Hex(source)), Some(Token::Keyword(cid))) =
+                        (tokens.get(i), tokens.get(i + 1))
+                    {
+                        if let Ok(cid) = cid.parse::<u32>() {
+                            cmap.cids.insert((source.len(), code_of(source)), cid);
+                        }
+                        i += 2;
+                    }
+                }
+                "begincidrange" => {
+                    while let (Some(Token::Hex(low)), Some(Token::Hex(high)), Some(Token::Keyword(cid))) =
+                        (tokens.get(i), tokens.get(i + 1), tokens.get(i + 2))
+                    {
+                        let (low_code, high_code) = (code_of(low), code_of(high));
+                        if let Ok(cid) = cid.parse::<u32>() {
+                            for offset in 0..=high_code.saturating_sub(low_code).min(0xFFFF) {
+                                // a malformed range running past the largest CID ends there
+                                let Some(cid) = cid.checked_add(offset) else {
+                                    break;
+                                };
+                                cmap.cids.insert((low.len(), low_code + offset), cid);
+                            }
+                        }
+                        i += 3;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        cmap
+    }
+}
+
+/// Unicode of an Adobe Glyph List name, `uniXXXX` or `uXXXX[XX]`; suffixes such as
+/// `.sc` or `.alt` are ignored and ligatures joined with `_` are spelled out
+pub fn glyph_name_to_unicode(name: &str) -> Option<String> {
+    let name = name.split('.').next().unwrap_or(name);
+    if name.contains('_') {
+        return name.split('_').map(glyph_name_to_unicode).collect();
+    }
+
+    if let Ok(index) = GLYPH_NAMES.binary_search_by(|(glyph, _)| (*glyph).cmp(name)) {
+        return Some(GLYPH_NAMES[index].1.to_string());
+    }
+    if let Some(hex) = name
+        .strip_prefix("uni")
+        .filter(|h| h.len() >= 4 && h.len() % 4 == 0)
+    {
+        let units: Option<Vec<u16>> = (0..hex.len())
+            .step_by(4)
+            .map(|i| u16::from_str_radix(&hex[i..i + 4], 16).ok())
+            .collect();
+        return units.map(|units| String::from_utf16_lossy(&units));
+    }
+    if let Some(hex) = name
+        .strip_prefix('u')
+        .filter(|h| (4..=6).contains(&h.len()))
+    {
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .map(String::from);
+    }
+    None
+}
+
+/// How a font without (a complete) `/ToUnicode` map turns codes into text
+#[derive(Debug, Clone)]
+enum Fallback {
+    /// Simple fonts: one byte per code through the base encoding and `/Differences`
+    Simple(Vec<Option<String>>),
+    /// Predefined CJK CMaps whose codes are the bytes of a legacy or UTF-16 encoding
+    Charset(&'static encoding_rs::Encoding),
+    /// CIDs (e.g. `Identity-H`) that only a `/ToUnicode` map can turn into text
+    None,
+}
+
+/// Styling a font implies for the text shown with it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FontStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub monospace: bool,
+}
+
+/// How the codes of a composite font select the CIDs that `/W` gives widths for
+#[derive(Debug, Clone)]
+enum CidMap {
+    Identity,
+    Table(HashMap<(usize, u32), u32>),
+    /// Predefined CJK CMaps, whose CID tables we don't have
+    Unknown,
+}
+
+/// Decodes the strings shown with a font into Unicode: through its `/ToUnicode` CMap
+/// first, then the encoding its `/Encoding` names. Also knows the glyph widths.
+#[derive(Debug, Clone)]
+pub struct PdfFont {
+    to_unicode: Option<CMap>,
+    /// Code lengths of a composite (Type0) font; empty for simple fonts
+    codespace: Vec<CodespaceRange>,
+    fallback: Fallback,
+    cids: CidMap,
+    /// Code (simple fonts) or CID -> width in glyph space
+    widths: HashMap<u32, f32>,
+    default_width: f32,
+    /// Glyph space units per em: 1/1000, except for Type3 fonts
+    width_scale: f32,
+    pub style: FontStyle,
+}
+
+impl PdfFont {
+    pub fn new(document: &Document, font: &Dictionary) -> PdfFont {
+        let to_unicode = font
+            .get(b"ToUnicode")
+            .and_then(|o| document.dereference(o))
+            .and_then(|(_, o)| o.as_stream())
+            .and_then(|stream| stream.get_plain_content())
+            .ok()
+            .map(|data| CMap::parse(&data));
+
+        let subtype = font.get(b"Subtype").and_then(Object::as_name).unwrap_or(b"");
+        if subtype != b"Type0" {
+            let (widths, default_width) = simple_widths(document, font);
+            let width_scale = match subtype {
+                b"Type3" => font
+                    .get(b"FontMatrix")
+                    .and_then(Object::as_array)
+                    .ok()
+                    .and_then(|matrix| number(document, matrix.first()?))
+                    .map_or(0.001, f32::abs),
+                _ => 0.001,
+            };
+            return PdfFont {
+                to_unicode,
+                codespace: Vec::new(),
+                fallback: Fallback::Simple(simple_encoding(document, font)),
+                cids: CidMap::Identity,
+                widths,
+                default_width,
+                width_scale,
+                style: font_style(document, font),
+            };
+        }
+
+        let encoding = font
+            .get(b"Encoding")
+            .and_then(|o| document.dereference(o))
+            .map(|(_, o)| o);
+        let (codespace, fallback, cids) = match encoding {
+            Ok(Object::Name(name)) if name.starts_with(b"Identity") => {
+                (CodespaceRange::two_bytes(), Fallback::None, CidMap::Identity)
+            }
+            Ok(Object::Name(name)) => {
+                let (codespace, fallback) = predefined_cmap(&String::from_utf8_lossy(name));
+                (codespace, fallback, CidMap::Unknown)
+            }
+            Ok(Object::Stream(stream)) => {
+                // an embedded CMap maps codes to CIDs, which only matter for widths
+                let embedded = stream
+                    .get_plain_content()
+                    .map(|data| CMap::parse(&data))
+                    .unwrap_or_default();
+                let cids = match embedded.cids.is_empty() {
+                    true => CidMap::Unknown,
+                    false => CidMap::Table(embedded.cids),
+                };
+                let used = stream
+                    .dict
+                    .get(b"UseCMap")
+                    .and_then(Object::as_name)
+                    .map(|name| predefined_cmap(&String::from_utf8_lossy(name)));
+                match (embedded.codespace.is_empty(), used) {
+                    (false, _) => (embedded.codespace, Fallback::None, cids),
+                    (true, Ok((codespace, fallback))) => (codespace, fallback, cids),
+                    (true, Err(_)) => (CodespaceRange::two_bytes(), Fallback::None, cids),
+                }
+            }
+            _ => (CodespaceRange::two_bytes(), Fallback::None, CidMap::Identity),
+        };
+
+        let descendant = font
+            .get(b"DescendantFonts")
+            .and_then(|o| document.dereference(o))
+            .and_then(|(_, o)| o.as_array())
+            .ok()
+            .and_then(|fonts| document.dereference(fonts.first()?).ok())
+            .and_then(|(_, o)| o.as_dict().ok());
+        let (widths, default_width) = descendant
+            .map(|descendant| cid_widths(document, descendant))
+            .unwrap_or((HashMap::new(), 1000.0));
+
+        // the descriptor of a composite font is on its CIDFont
+        let style = match descendant {
+            Some(descendant) if descendant.has(b"FontDescriptor") => font_style(document, descendant),
+            _ => font_style(document, font),
+        };
+
+        PdfFont {
+            to_unicode,
+            codespace,
+            fallback,
+            cids,
+            widths,
+            default_width,
+            width_scale: 0.001,
+            style,
+        }
+    }
+
+    /// Advance of a code in ems (text space units at font size 1)
+    pub fn width(&self, code: u32, length: usize) -> f32 {
+        let glyph = match (&self.cids, self.codespace.is_empty()) {
+            (_, true) | (CidMap::Identity, false) => Some(code),
+            (CidMap::Table(cids), false) => cids.get(&(length, code)).copied(),
+            (CidMap::Unknown, false) => None,
+        };
+        match glyph {
+            Some(glyph) => self.widths.get(&glyph).copied().unwrap_or(self.default_width) * self.width_scale,
+            // CJK CMaps: single bytes are half-width, longer codes full-width
+            None if length > 1 => 1.0,
+            None => 0.5,
+        }
+    }
+
+    /// Advance of the space character in ems, a quarter em if the font has none
+    pub fn space_width(&self) -> f32 {
+        let space = self
+            .to_unicode
+            .as_ref()
+            .and_then(|m| m.unicode.iter().find(|(_, text)| *text == " "))
+            .map(|((length, code), _)| (*code, *length))
+            .or_else(|| self.codespace.is_empty().then_some((32, 1)));
+        match space {
+            Some((code, length)) if self.width(code, length) > 0.0 => self.width(code, length),
+            _ => 0.25,
+        }
+    }
+
+    /// Splits a shown string into codes, as (code, length in bytes)
+    pub fn codes(&self, bytes: &[u8]) -> Vec<(u32, usize)> {
+        if self.codespace.is_empty() {
+            return bytes.iter().map(|b| (*b as u32, 1)).collect();
+        }
+
+        let max_length = self
+            .codespace
+            .iter()
+            .map(|r| r.low.len())
+            .max()
+            .unwrap_or(1);
+        let mut codes = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let length = (1..=max_length)
+                .filter(|length| i + length <= bytes.len())
+                .find(|length| {
+                    self.codespace
+                        .iter()
+                        .any(|r| r.contains(&bytes[i..i + length]))
+                })
+                // bytes outside the codespace: take the shortest code length
+                .unwrap_or_else(|| {
+                    self.codespace
+                        .iter()
+                        .map(|r| r.low.len())
+                        .min()
+                        .unwrap_or(1)
+                });
+            let end = (i + length).min(bytes.len());
+            codes.push((code_of(&bytes[i..end]), end - i));
+            i = end;
+        }
+        codes
+    }
+
+    /// Text of one code
+    pub fn code_to_unicode(&self, code: u32, length: usize) -> Option<String> {
+        if let Some(text) = self
+            .to_unicode
+            .as_ref()
+            .and_then(|m| m.unicode.get(&(length, code)))
+        {
+            return Some(text.clone());
+        }
+        match &self.fallback {
+            Fallback::Simple(table) => table.get(code as usize).cloned().flatten(),
+            Fallback::Charset(charset) => {
+                let bytes = &code.to_be_bytes()[4 - length.min(4)..];
+                let (text, _, malformed) = charset.decode(bytes);
+                (!malformed).then(|| text.to_string())
+            }
+            Fallback::None => None,
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        // charset CMaps without a ToUnicode map decode in one go, so that multi-unit
+        // sequences such as UTF-16 surrogate pairs stay together
+        if let (None, Fallback::Charset(charset)) = (&self.to_unicode, &self.fallback) {
+            return charset.decode_without_bom_handling(bytes).0.to_string();
+        }
+        self.codes(bytes)
+            .into_iter()
+            .filter_map(|(code, length)| self.code_to_unicode(code, length))
+            .collect()
+    }
+}
+
+fn number(document: &Document, object: &Object) -> Option<f32> {
+    document.dereference(object).ok()?.1.as_float().ok()
+}
+
+/// Bold, italic and fixed pitch from the `/FontDescriptor` (`/Flags`, `/FontWeight`,
+/// `/ItalicAngle`) and the font name, without the `ABCDEF+` prefix of subset fonts
+fn font_style(document: &Document, font: &Dictionary) -> FontStyle {
+    const FIXED_PITCH: i64 = 1;
+    const ITALIC: i64 = 1 << 6;
+    const FORCE_BOLD: i64 = 1 << 18;
+
+    let descriptor = font
+        .get(b"FontDescriptor")
+        .and_then(|o| document.dereference(o))
+        .and_then(|(_, o)| o.as_dict())
+        .ok();
+    let entry = |key: &[u8]| descriptor.and_then(|d| d.get(key).ok()).and_then(|o| number(document, o));
+    let flags = entry(b"Flags").unwrap_or_default() as i64;
+    let weight = entry(b"FontWeight").unwrap_or_default();
+    let italic_angle = entry(b"ItalicAngle").unwrap_or_default();
+
+    let name = font
+        .get(b"BaseFont")
+        .and_then(Object::as_name)
+        .map(|name| String::from_utf8_lossy(name).to_lowercase())
+        .unwrap_or_default();
+    let name = name.split_once('+').map_or(name.as_str(), |(_, name)| name);
+    // "Arial,BoldItalic", "MinionPro-BoldIt"
+    let style = name.rsplit(['-', ',']).next().filter(|_| name.contains(['-', ','])).unwrap_or("");
+
+    FontStyle {
+        bold: flags & FORCE_BOLD != 0
+            || weight >= 600.0
+            || ["bold", "black", "heavy", "demi"].iter().any(|w| name.contains(w))
+            || style == "bd",
+        italic: flags & ITALIC != 0
+            || italic_angle.abs() > 0.5
+            || name.contains("italic")
+            || name.contains("oblique")
+            || style.ends_with("it"),
+        monospace: flags & FIXED_PITCH != 0
+            || ["courier", "mono", "consol", "menlo", "code", "typewriter", "cmtt"]
+                .iter()
+                .any(|w| name.contains(w)),
+    }
+}
+
+/// `/Widths` from `/FirstChar` on, and `/MissingWidth` for other codes; the standard
+/// fonts, which may come without widths, get an average width
+fn simple_widths(document: &Document, font: &Dictionary) -> (HashMap<u32, f32>, f32) {
+    let first = font.get(b"FirstChar").ok().and_then(|o| number(document, o)).unwrap_or(0.0) as u32;
+    let widths = font
+        .get(b"Widths")
+        .and_then(|o| document.dereference(o))
+        .and_then(|(_, o)| o.as_array())
+        .map(|widths| {
+            widths
+                .iter()
+                .enumerate()
+                .filter_map(|(i, width)| Some((first.checked_add(i as u32)?, number(document, width)?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let missing = font
+        .get(b"FontDescriptor")
+        .and_then(|o| document.dereference(o))
+        .and_then(|(_, o)| o.as_dict())
+        .and_then(|descriptor| descriptor.get(b"MissingWidth"))
+        .ok()
+        .and_then(|o| number(document, o))
+        .filter(|width| *width > 0.0);
+    let monospace = font
+        .get(b"BaseFont")
+        .and_then(Object::as_name)
+        .is_ok_and(|name| String::from_utf8_lossy(name).contains("Courier"));
+    let default_width = missing.unwrap_or(if monospace { 600.0 } else { 500.0 });
+
+    (widths, default_width)
+}
+
+/// `/W` of a CIDFont, `[c [w1 w2 ...] c_first c_last w ...]`, and its `/DW`
+fn cid_widths(document: &Document, descendant: &Dictionary) -> (HashMap<u32, f32>, f32) {
+    let default_width = descendant
+        .get(b"DW")
+        .ok()
+        .and_then(|o| number(document, o))
+        .unwrap_or(1000.0);
+    let mut widths = HashMap::new();
+    let Ok(items) = descendant
+        .get(b"W")
+        .and_then(|o| document.dereference(o))
+        .and_then(|(_, o)| o.as_array())
+    else {
+        return (widths, default_width);
+    };
+
+    let mut i = 0;
+    while i < items.len() {
+        let Some(first) = number(document, &items[i]) else {
+            i += 1;
+            continue;
+        };
+        let first = first as u32;
+        match document.dereference(items.get(i + 1).unwrap_or(&Object::Null)).map(|(_, o)| o) {
+            Ok(Object::Array(list)) => {
+                for (offset, width) in list.iter().enumerate() {
+                    let Some(cid) = u32::try_from(offset).ok().and_then(|offset| first.checked_add(offset)) else {
+                        break;
+                    };
+                    if let Some(width) = number(document, width) {
+                        widths.insert(cid, width);
+                    }
+                }
+                i += 2;
+            }
+            _ => {
+                let last = items.get(i + 1).and_then(|o| number(document, o));
+                let width = items.get(i + 2).and_then(|o| number(document, o));
+                if let (Some(last), Some(width)) = (last, width) {
+                    // cap malformed ranges
+                    for cid in first..=(last as u32).min(first.saturating_add(0xFFFF)) {
+                        widths.insert(cid, width);
+                    }
+                }
+                i += 3;
+            }
+        }
+    }
+
+    (widths, default_width)
+}
+
+/// Code lengths and fallback of a predefined CMap name
+fn predefined_cmap(name: &str) -> (Vec<CodespaceRange>, Fallback) {
+    let one_or_two_bytes = |single_high: u8| {
+        vec![
+            CodespaceRange {
+                low: vec![0x00],
+                high: vec![single_high],
+            },
+            CodespaceRange {
+                low: vec![single_high + 1, 0x40],
+                high: vec![0xFF, 0xFF],
+            },
+        ]
+    };
+
+    let charset = if name.contains("UCS2") || name.contains("UTF16") {
+        return (
+            CodespaceRange::two_bytes(),
+            Fallback::Charset(encoding_rs::UTF_16BE),
+        );
+    } else if name.starts_with("GBK2K") {
+        encoding_rs::GB18030
+    } else if name.starts_with("GB") {
+        encoding_rs::GBK
+    } else if name.contains("B5") {
+        encoding_rs::BIG5
+    } else if name.contains("RKSJ") {
+        encoding_rs::SHIFT_JIS
+    } else if name.starts_with("KSC") {
+        encoding_rs::EUC_KR
+    } else if name.starts_with("EUC") {
+        encoding_rs::EUC_JP
+    } else {
+        // Identity-H/V and CMaps we don't know
+        return (CodespaceRange::two_bytes(), Fallback::None);
+    };
+    (one_or_two_bytes(0x80), Fallback::Charset(charset))
+}
+
+/// Byte -> text table of a simple font: the named base encoding (StandardEncoding
+/// if there is none) with the `/Differences` glyph names applied
+fn simple_encoding(document: &Document, font: &Dictionary) -> Vec<Option<String>> {
+    let encoding = font
+        .get(b"Encoding")
+        .and_then(|o| document.dereference(o))
+        .map(|(_, o)| o);
+    let (base_name, differences) = match encoding {
+        Ok(Object::Name(name)) => (name.clone(), None),
+        Ok(Object::Dictionary(dict)) => (
+            dict.get(b"BaseEncoding")
+                .and_then(Object::as_name)
+                .map(<[u8]>::to_vec)
+                .unwrap_or_else(|_| b"StandardEncoding".to_vec()),
+            dict.get(b"Differences").and_then(Object::as_array).ok(),
+        ),
+        _ => (b"StandardEncoding".to_vec(), None),
+    };
+
+    let mut table = vec![None; 256];
+    // lopdf knows the standard single-byte encodings by name
+    let mut base = Dictionary::new();
+    base.set("Type", Object::Name(b"Font".to_vec()));
+    base.set("Encoding", Object::Name(base_name));
+    match base.get_font_encoding(document) {
+        Ok(Encoding::OneByteEncoding(characters)) => {
+            for (entry, character) in table.iter_mut().zip(characters.iter()) {
+                *entry = character
+                    .and_then(|c| char::from_u32(c as u32))
+                    .map(String::from);
+            }
+        }
+        _ => {
+            for (byte, entry) in table.iter_mut().enumerate().skip(0x20) {
+                *entry = char::from_u32(byte as u32).map(String::from);
+            }
+        }
+    }
+
+    // [code /name /name ... code /name ...]
+    let mut code = 0usize;
+    for item in differences.into_iter().flatten() {
+        match item {
+            Object::Integer(start) => code = (*start).max(0) as usize,
+            Object::Name(name) => {
+                if let Some(entry) = table.get_mut(code) {
+                    *entry = glyph_name_to_unicode(&String::from_utf8_lossy(name));
+                }
+                code += 1;
+            }
+            _ => {}
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, Stream};
+
+    const TO_UNICODE: &[u8] = b"/CIDInit /ProcSet findresource begin
+12 dict begin
+begincmap
+/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def
+1 begincodespacerange
+<0000> <FFFF>
+endcodespacerange
+2 beginbfchar
+<0003> <0020>
+<0010> <4E2D6587>
+endbfchar
+2 beginbfrange
+<0020> <0022> <0041>
+<0030> <0032> [<0066006C> /eacute /uni65E5]
+endbfrange
+endcmap";
+
+    fn type0_font(encoding: Object, to_unicode: Option<&[u8]>) -> PdfFont {
+        let mut font = dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type0",
+            "BaseFont" => "Test",
+            "Encoding" => encoding,
+        };
+        if let Some(data) = to_unicode {
+            font.set("ToUnicode", Object::Stream(Stream::new(dictionary! {}, data.to_vec())));
+        }
+        PdfFont::new(&Document::with_version("1.5"), &font)
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize(b"% comment\n<00 1f> /Name [<4e2d>] 3 begincmap (lit(eral)) <<");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Hex(vec![0x00, 0x1F]),
+                Token::Name("Name".to_string()),
+                Token::ArrayStart,
+                Token::Hex(vec![0x4E, 0x2D]),
+                Token::ArrayEnd,
+                Token::Keyword("3".to_string()),
+                Token::Keyword("begincmap".to_string()),
+            ]
+        );
+        // an odd final digit is followed by an implied 0
+        assert_eq!(tokenize(b"<7>"), vec![Token::Hex(vec![0x70])]);
+    }
+
+    #[test]
+    fn test_bfchar() {
+        let cmap = CMap::parse(TO_UNICODE);
+        assert_eq!(cmap.codespace.len(), 1);
+        assert_eq!(cmap.unicode.get(&(2, 0x0003)).map(String::as_str), Some(" "));
+        // one code to several characters
+        assert_eq!(cmap.unicode.get(&(2, 0x0010)).map(String::as_str), Some("中文"));
+    }
+
+    #[test]
+    fn test_bfrange_hex_destination() {
+        let cmap = CMap::parse(TO_UNICODE);
+        let text = |code| cmap.unicode.get(&(2, code)).map(String::as_str);
+        assert_eq!((text(0x20), text(0x21), text(0x22)), (Some("A"), Some("B"), Some("C")));
+        assert_eq!(text(0x23), None);
+    }
+
+    #[test]
+    fn test_bfrange_array_destination() {
+        let cmap = CMap::parse(TO_UNICODE);
+        let text = |code| cmap.unicode.get(&(2, code)).map(String::as_str);
+        assert_eq!((text(0x30), text(0x31), text(0x32)), (Some("fl"), Some("é"), Some("日")));
+    }
+
+    #[test]
+    fn test_identity_font_decodes_through_to_unicode() {
+        let font = type0_font(Object::Name(b"Identity-H".to_vec()), Some(TO_UNICODE));
+        assert_eq!(font.codes(&[0x00, 0x10, 0x00, 0x21]), vec![(0x0010, 2), (0x0021, 2)]);
+        assert_eq!(font.decode(&[0x00, 0x10, 0x00, 0x03, 0x00, 0x20]), "中文 A");
+    }
+
+    #[test]
+    fn test_two_byte_codespace_splitting() {
+        let encoding = b"begincmap
+2 begincodespacerange
+<00> <80>
+<8140> <FFFC>
+endcodespacerange
+1 begincidrange
+<8140> <8142> 100
+endcidrange
+endcmap";
+        let font = type0_font(Object::Stream(Stream::new(dictionary! {}, encoding.to_vec())), None);
+        assert_eq!(
+            font.codes(&[0x41, 0x81, 0x40, 0x42, 0x81, 0x42]),
+            vec![(0x41, 1), (0x8140, 2), (0x42, 1), (0x8142, 2)]
+        );
+    }
+
+    #[test]
+    fn test_predefined_cmap_decodes_charset() {
+        // "Aあ" in Shift-JIS
+        let font = type0_font(Object::Name(b"90ms-RKSJ-H".to_vec()), None);
+        let bytes = [0x41, 0x82, 0xA0];
+        assert_eq!(font.codes(&bytes), vec![(0x41, 1), (0x82A0, 2)]);
+        assert_eq!(font.decode(&bytes), "Aあ");
+    }
+
+    #[test]
+    fn test_differences_glyph_names() {
+        let font = dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => dictionary! {
+                "Type" => "Encoding",
+                "BaseEncoding" => "WinAnsiEncoding",
+                "Differences" => vec![
+                    Object::Integer(65),
+                    Object::Name(b"Alpha".to_vec()),
+                    Object::Name(b"uni4E2D".to_vec()),
+                    Object::Name(b"f_i".to_vec()),
+                ],
+            },
+        };
+        let font = PdfFont::new(&Document::with_version("1.5"), &font);
+        assert_eq!(font.decode(b"ABCD"), "Α中fiD");
+    }
+
+    #[test]
+    fn test_glyph_name_to_unicode() {
+        assert_eq!(glyph_name_to_unicode("eacute").as_deref(), Some("é"));
+        assert_eq!(glyph_name_to_unicode("uni65E5").as_deref(), Some("日"));
+        assert_eq!(glyph_name_to_unicode("uni00410042").as_deref(), Some("AB"));
+        assert_eq!(glyph_name_to_unicode("u1F600").as_deref(), Some("😀"));
+        assert_eq!(glyph_name_to_unicode("a.sc").as_deref(), Some("a"));
+        assert_eq!(glyph_name_to_unicode("f_f_i").as_deref(), Some("ffi"));
+        assert_eq!(glyph_name_to_unicode("g123"), None);
+    }
+}
@@ -0,0 +1,358 @@
+use std::io::Cursor;
+
+use image::{imageops::FilterType, DynamicImage, GrayImage, ImageFormat, RgbImage, RgbaImage};
+use lopdf::{Dictionary, Document, Object, Stream};
+
+use super::pdf_element::MarkedContent;
+
+/// An image XObject drawn on a page: its data as PNG or JPEG and the rectangle
+/// it covers in page space
+#[derive(Clone, Debug)]
+pub struct PdfImage {
+    pub data: Vec<u8>,
+    pub x: f32,
+    /// Top edge
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub marked: MarkedContent,
+}
+
+/// Colour spaces of image samples
+#[derive(Debug, Clone)]
+enum ColorSpace {
+    Gray,
+    Rgb,
+    Cmyk,
+    /// Samples index `lookup`, which holds `base` colour components per entry
+    Indexed { base: Box<ColorSpace>, lookup: Vec<u8> },
+}
+
+impl ColorSpace {
+    fn components(&self) -> usize {
+        match self {
+            ColorSpace::Gray | ColorSpace::Indexed { .. } => 1,
+            ColorSpace::Rgb => 3,
+            ColorSpace::Cmyk => 4,
+        }
+    }
+
+    fn from_object(document: &Document, object: &Object) -> Result<ColorSpace, String> {
+        let object = resolve(document, object);
+        if let Ok(name) = object.as_name() {
+            return match name {
+                b"DeviceGray" | b"CalGray" | b"G" => Ok(ColorSpace::Gray),
+                b"DeviceRGB" | b"CalRGB" | b"RGB" => Ok(ColorSpace::Rgb),
+                b"DeviceCMYK" | b"CMYK" => Ok(ColorSpace::Cmyk),
+                other => Err(format!("Unsupported color space {}", String::from_utf8_lossy(other))),
+            };
+        }
+
+        let array = object
+            .as_array()
+            .map_err(|e| format!("Failed to read color space: {}", e))?;
+        let family = array.first().and_then(|o| o.as_name().ok()).unwrap_or_default();
+        match family {
+            b"CalGray" => Ok(ColorSpace::Gray),
+            b"CalRGB" | b"Lab" => Ok(ColorSpace::Rgb),
+            b"ICCBased" => {
+                let profile = array
+                    .get(1)
+                    .map(|o| resolve(document, o))
+                    .and_then(|o| o.as_stream().ok())
+                    .ok_or("Failed to read ICC profile of color space")?;
+                if let Ok(alternate) = profile.dict.get(b"Alternate") {
+                    return ColorSpace::from_object(document, alternate);
+                }
+                match profile.dict.get(b"N").and_then(Object::as_i64) {
+                    Ok(1) => Ok(ColorSpace::Gray),
+                    Ok(4) => Ok(ColorSpace::Cmyk),
+                    _ => Ok(ColorSpace::Rgb),
+                }
+            }
+            b"Indexed" | b"I" => {
+                let base = array.get(1).ok_or("Indexed color space without base")?;
+                let base = ColorSpace::from_object(document, base)?;
+                let lookup = match array.get(3).map(|o| resolve(document, o)) {
+                    Some(Object::String(bytes, _)) => bytes.clone(),
+                    Some(Object::Stream(stream)) => stream
+                        .get_plain_content()
+                        .map_err(|e| format!("Failed to read color lookup table: {}", e))?,
+                    _ => return Err("Indexed color space without lookup table".to_string()),
+                };
+                Ok(ColorSpace::Indexed { base: Box::new(base), lookup })
+            }
+            other => Err(format!("Unsupported color space {}", String::from_utf8_lossy(other))),
+        }
+    }
+
+    /// RGB of one pixel's components, each scaled to 0..=255 (an index for `Indexed`)
+    fn to_rgb(&self, components: &[u8]) -> [u8; 3] {
+        match self {
+            ColorSpace::Gray => [components[0]; 3],
+            ColorSpace::Rgb => [components[0], components[1], components[2]],
+            ColorSpace::Cmyk => {
+                let k = 255 - components[3] as u32;
+                let channel = |c: u8| ((255 - c as u32) * k / 255) as u8;
+                [channel(components[0]), channel(components[1]), channel(components[2])]
+            }
+            ColorSpace::Indexed { base, lookup } => {
+                let size = base.components();
+                let start = components[0] as usize * size;
+                match lookup.get(start..start + size) {
+                    Some(entry) => base.to_rgb(entry),
+                    None => [0, 0, 0],
+                }
+            }
+        }
+    }
+}
+
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> &'a Object {
+    match object {
+        Object::Reference(id) => document.get_object(*id).unwrap_or(object),
+        _ => object,
+    }
+}
+
+/// Whether an XObject stream is an image (`/Subtype /Image`)
+pub fn is_image(stream: &Stream) -> bool {
+    stream
+        .dict
+        .get(b"Subtype")
+        .and_then(Object::as_name)
+        .is_ok_and(|subtype| subtype == b"Image")
+}
+
+/// Encodes an image XObject as JPEG (DCT-encoded images without a soft mask are passed
+/// through) or PNG (everything else, with the soft mask as alpha channel)
+pub fn decode_image(document: &Document, stream: &Stream) -> Result<Vec<u8>, String> {
+    let dict = &stream.dict;
+    let filters: Vec<Vec<u8>> = stream
+        .filters()
+        .map(|filters| filters.into_iter().map(<[u8]>::to_vec).collect())
+        .unwrap_or_default();
+    let soft_mask = dict
+        .get(b"SMask")
+        .ok()
+        .map(|mask| resolve(document, mask))
+        .and_then(|mask| mask.as_stream().ok());
+
+    let rgb = match filters.last().map(Vec::as_slice) {
+        Some(b"DCTDecode") | Some(b"DCT") => {
+            let jpeg = undo_filters(stream, filters.len() - 1)?;
+            if soft_mask.is_none() {
+                return Ok(jpeg);
+            }
+            image::load_from_memory_with_format(&jpeg, ImageFormat::Jpeg)
+                .map_err(|e| format!("Failed to decode JPEG image: {}", e))?
+                .to_rgb8()
+        }
+        Some(b"JPXDecode") | Some(b"CCITTFaxDecode") | Some(b"CCF") | Some(b"JBIG2Decode") => {
+            let filter = String::from_utf8_lossy(filters.last().unwrap_or(&Vec::new())).to_string();
+            return Err(format!("Unsupported image filter {}", filter));
+        }
+        _ => decode_samples(document, stream)?,
+    };
+
+    let image = match soft_mask.map(|mask| decode_mask(mask, rgb.width(), rgb.height())) {
+        Some(Ok(alpha)) => {
+            let mut rgba = RgbaImage::new(rgb.width(), rgb.height());
+            for (x, y, pixel) in rgba.enumerate_pixels_mut() {
+                let [r, g, b] = rgb.get_pixel(x, y).0;
+                *pixel = image::Rgba([r, g, b, alpha.get_pixel(x, y).0[0]]);
+            }
+            DynamicImage::ImageRgba8(rgba)
+        }
+        // an unreadable mask leaves the image opaque
+        _ => DynamicImage::ImageRgb8(rgb),
+    };
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image as PNG: {}", e))?;
+    Ok(png)
+}
+
+/// Content of a stream with its first `count` filters undone
+fn undo_filters(stream: &Stream, count: usize) -> Result<Vec<u8>, String> {
+    if count == 0 {
+        return Ok(stream.content.clone());
+    }
+    let filters: Vec<Object> = stream
+        .filters()
+        .map_err(|e| format!("Failed to read image filters: {}", e))?
+        .into_iter()
+        .take(count)
+        .map(|name| Object::Name(name.to_vec()))
+        .collect();
+    let mut dict = stream.dict.clone();
+    dict.set("Filter", Object::Array(filters));
+    Stream::new(dict, stream.content.clone())
+        .decompressed_content()
+        .map_err(|e| format!("Failed to decompress image: {}", e))
+}
+
+/// Sample data of a stream, with TIFF predictors undone (lopdf handles the PNG ones)
+fn plain_samples(stream: &Stream, components: usize, bits: usize) -> Result<Vec<u8>, String> {
+    let mut data = stream
+        .get_plain_content()
+        .map_err(|e| format!("Failed to decompress image: {}", e))?;
+
+    let params = stream.dict.get(b"DecodeParms").and_then(Object::as_dict).ok();
+    let predictor = params.and_then(|p| p.get(b"Predictor").and_then(Object::as_i64).ok());
+    if predictor == Some(2) && bits == 8 {
+        let columns = params
+            .and_then(|p| p.get(b"Columns").and_then(Object::as_i64).ok())
+            .unwrap_or(1)
+            .max(1) as usize;
+        let colors = params
+            .and_then(|p| p.get(b"Colors").and_then(Object::as_i64).ok())
+            .unwrap_or(components as i64)
+            .max(1) as usize;
+        let row_length = columns * colors;
+        for row in data.chunks_mut(row_length) {
+            for i in colors..row.len() {
+                row[i] = row[i].wrapping_add(row[i - colors]);
+            }
+        }
+    }
+    Ok(data)
+}
+
+fn dimensions(dict: &Dictionary) -> Result<(u32, u32), String> {
+    let get = |key: &[u8]| {
+        dict.get(key)
+            .and_then(Object::as_i64)
+            .ok()
+            .filter(|v| *v > 0)
+            .map(|v| v as u32)
+    };
+    match (get(b"Width"), get(b"Height")) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err("Image without width or height".to_string()),
+    }
+}
+
+/// Unpacks rows of `bits`-bit samples into one byte per component, scaled to 0..=255
+/// unless `raw` (colour table indices)
+fn unpack(data: &[u8], width: usize, height: usize, components: usize, bits: usize, raw: bool) -> Vec<u8> {
+    let row_bytes = (width * components * bits).div_ceil(8);
+    let max = ((1u32 << bits) - 1).max(1);
+    let mut samples = Vec::with_capacity(width * height * components);
+
+    for row in 0..height {
+        let start = row * row_bytes;
+        let Some(row) = data.get(start..start + row_bytes) else {
+            break;
+        };
+        for i in 0..width * components {
+            let value = match bits {
+                8 => row[i] as u32,
+                // high byte of 16-bit samples
+                16 => row[i * 2] as u32,
+                _ => {
+                    let bit = i * bits;
+                    let byte = row[bit / 8] as u32;
+                    (byte >> (8 - bits - bit % 8)) & max
+                }
+            };
+            let value = match (raw, bits) {
+                (true, _) | (false, 8) | (false, 16) => value,
+                (false, _) => value * 255 / max,
+            };
+            samples.push(value as u8);
+        }
+    }
+    samples.resize(width * height * components, 0);
+    samples
+}
+
+/// Applies a `/Decode` array (pairs of output range per component) to 0..=255 samples
+fn apply_decode(samples: &mut [u8], decode: &[f32], components: usize) {
+    for (i, sample) in samples.iter_mut().enumerate() {
+        let c = i % components;
+        let (Some(low), Some(high)) = (decode.get(c * 2), decode.get(c * 2 + 1)) else {
+            continue;
+        };
+        let value = low + (*sample as f32 / 255.0) * (high - low);
+        *sample = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+}
+
+fn decode_array(dict: &Dictionary) -> Option<Vec<f32>> {
+    let array = dict.get(b"Decode").and_then(Object::as_array).ok()?;
+    array.iter().map(|o| o.as_float().ok()).collect()
+}
+
+fn decode_samples(document: &Document, stream: &Stream) -> Result<RgbImage, String> {
+    let dict = &stream.dict;
+    let (width, height) = dimensions(dict)?;
+    let image_mask = dict.get(b"ImageMask").and_then(Object::as_bool).unwrap_or(false);
+
+    let color_space = match dict.get(b"ColorSpace") {
+        _ if image_mask => ColorSpace::Gray,
+        Ok(object) => ColorSpace::from_object(document, object)?,
+        Err(_) => return Err("Image without color space".to_string()),
+    };
+    let bits = match dict.get(b"BitsPerComponent").and_then(Object::as_i64) {
+        _ if image_mask => 1,
+        Ok(bits @ (1 | 2 | 4 | 8 | 16)) => bits as usize,
+        Ok(bits) => return Err(format!("Unsupported image bit depth {}", bits)),
+        Err(_) => 8,
+    };
+    let components = color_space.components();
+    let indexed = matches!(color_space, ColorSpace::Indexed { .. });
+
+    let data = plain_samples(stream, components, bits)?;
+    let mut samples = unpack(&data, width as usize, height as usize, components, bits, indexed);
+    if image_mask {
+        // stencil masks paint where the sample is 0 (or 1 with /Decode [1 0])
+        let inverted = decode_array(dict).is_some_and(|d| d.first() == Some(&1.0));
+        for sample in samples.iter_mut() {
+            *sample = if (*sample == 0) != inverted { 0 } else { 255 };
+        }
+    } else if let Some(decode) = decode_array(dict).filter(|_| !indexed) {
+        apply_decode(&mut samples, &decode, components);
+    }
+
+    let mut rgb = RgbImage::new(width, height);
+    for (pixel, components) in rgb.pixels_mut().zip(samples.chunks(components)) {
+        *pixel = image::Rgb(color_space.to_rgb(components));
+    }
+    Ok(rgb)
+}
+
+/// A soft mask as alpha values, scaled to the size of the image it belongs to
+fn decode_mask(mask: &Stream, width: u32, height: u32) -> Result<GrayImage, String> {
+    let (mask_width, mask_height) = dimensions(&mask.dict)?;
+    let bits = match mask.dict.get(b"BitsPerComponent").and_then(Object::as_i64) {
+        Ok(bits @ (1 | 2 | 4 | 8 | 16)) => bits as usize,
+        Ok(bits) => return Err(format!("Unsupported soft mask bit depth {}", bits)),
+        Err(_) => 8,
+    };
+
+    let alpha = match mask.filters().ok().and_then(|f| f.last().map(|f| f.to_vec())) {
+        Some(filter) if filter == b"DCTDecode" => {
+            let jpeg = undo_filters(mask, mask.filters().map(|f| f.len() - 1).unwrap_or(0))?;
+            image::load_from_memory_with_format(&jpeg, ImageFormat::Jpeg)
+                .map_err(|e| format!("Failed to decode JPEG soft mask: {}", e))?
+                .to_luma8()
+        }
+        _ => {
+            let data = plain_samples(mask, 1, bits)?;
+            let mut samples = unpack(&data, mask_width as usize, mask_height as usize, 1, bits, false);
+            if let Some(decode) = decode_array(&mask.dict) {
+                apply_decode(&mut samples, &decode, 1);
+            }
+            GrayImage::from_raw(mask_width, mask_height, samples).ok_or("Invalid soft mask size")?
+        }
+    };
+
+    if alpha.dimensions() == (width, height) {
+        Ok(alpha)
+    } else {
+        Ok(image::imageops::resize(&alpha, width, height, FilterType::Triangle))
+    }
+}
@@ -1,39 +1,73 @@
-use std::{collections::BTreeMap, error::Error, mem::take, str::from_utf8};
+use std::{collections::BTreeMap, error::Error, mem::take};
 
-use lopdf::{Dictionary, Document, Encoding, Object, ObjectId};
+use lopdf::{Dictionary, Document, Object, ObjectId};
 
 use super::{
-    pdf_element::{PdfLine, PdfText, PdfUnit},
+    pdf_element::{MarkedContent, PdfLine, PdfText, PdfUnit},
+    pdf_font::PdfFont,
+    pdf_image::{decode_image, is_image, PdfImage},
     pdf_state::PdfState,
 };
+use crate::config::SETTINGS;
+
+/// A TJ offset wider than this fraction of a space separates words
+const SPACE_FRACTION: f32 = 0.5;
+
+/// The resources one content stream draws with: the page's or a form XObject's
+struct Resources<'a> {
+    dictionary: Option<&'a Dictionary>,
+    fonts: BTreeMap<Vec<u8>, &'a Dictionary>,
+    decoders: BTreeMap<Vec<u8>, PdfFont>,
+}
+
+impl<'a> Resources<'a> {
+    fn new(document: &Document, dictionary: Option<&'a Dictionary>, fonts: BTreeMap<Vec<u8>, &'a Dictionary>) -> Self {
+        let decoders = fonts
+            .iter()
+            .map(|(name, font)| (name.clone(), PdfFont::new(document, font)))
+            .collect();
+        Resources { dictionary, fonts, decoders }
+    }
+
+    /// The `/Resources` of a form XObject, if it has its own
+    fn of_form(document: &'a Document, form: &'a Dictionary) -> Option<Self> {
+        let (_, dictionary) = document.dereference(form.get(b"Resources").ok()?).ok()?;
+        let dictionary = dictionary.as_dict().ok()?;
+        let mut fonts = BTreeMap::new();
+        let font_dict = dictionary
+            .get(b"Font")
+            .and_then(|o| document.dereference(o))
+            .and_then(|(_, o)| o.as_dict());
+        for (name, font) in font_dict.into_iter().flat_map(Dictionary::iter) {
+            if let Ok((_, Object::Dictionary(font))) = document.dereference(font) {
+                fonts.insert(name.clone(), font);
+            }
+        }
+        Some(Resources::new(document, Some(dictionary), fonts))
+    }
+}
 
 pub struct PdfPage<'a> {
     pub stream: Vec<u8>,
     document: &'a Document,
-    fonts: BTreeMap<Vec<u8>, &'a Dictionary>,
-    encodings: BTreeMap<Vec<u8>, Encoding<'a>>,
-    resource: Option<&'a Dictionary>,
+    /// The page resources, then those of each form XObject being drawn; names resolve
+    /// against the innermost resources that define them
+    resources: Vec<Resources<'a>>,
     current_font_alias: Vec<u8>,
     current_font_name: Option<String>,
     current_font_size: Option<f32>,
     state: PdfState,
     state_stack: Vec<PdfState>,
-    pub child_fonts: Option<BTreeMap<Vec<u8>, &'a Dictionary>>,
-    pub child_encodings: Option<BTreeMap<Vec<u8>, Encoding<'a>>>,
+    /// Open marked-content sequences
+    marked_stack: Vec<MarkedContent>,
+    /// Nesting of form XObjects being drawn
+    form_depth: usize,
 }
 
 impl<'a> PdfPage<'a> {
     pub fn from_object_id(doc: &Document, id: ObjectId) -> Result<PdfPage, Box<dyn Error>> {
         let stream = doc.get_page_content(id)?;
         let fonts = doc.get_page_fonts(id)?;
-        let encodings: BTreeMap<Vec<u8>, Encoding> = fonts
-            .clone()
-            .into_iter()
-            .filter_map(|(name, font)| match font.get_font_encoding(&doc) {
-                Ok(it) => Some((name, it)),
-                Err(_) => None,
-            })
-            .collect();
 
         let resource = doc.get_page_resources(id).ok().and_then(|r| {
             let res = match r.0 {
@@ -48,17 +82,15 @@ impl<'a> PdfPage<'a> {
 
         Ok(PdfPage {
             stream,
-            fonts,
-            resource,
+            resources: vec![Resources::new(doc, resource, fonts)],
             document: doc,
-            encodings,
             current_font_alias: Vec::new(),
             state: PdfState::new(),
             state_stack: Vec::new(),
+            marked_stack: Vec::new(),
+            form_depth: 0,
             current_font_name: None,
             current_font_size: None,
-            child_fonts: None,
-            child_encodings: None,
         })
     }
 
@@ -76,20 +108,41 @@ impl<'a> PdfPage<'a> {
                         // ' is like TJ just with T* before it
                         // " is like as ' just with aw and ac as the first 2 operands
                         let r: &str = op.operator.as_ref();
+                        if r == "\"" {
+                            let spacing = op.operands.get(..2).ok_or("failed to query spacing for '\"'")?;
+                            self.state.tw(spacing[0].as_float()?);
+                            self.state.tc(spacing[1].as_float()?);
+                        }
                         if r == "'" || r == "\"" {
                             self.state.t_star();
                         }
-                        // currently ignores spacing in between chars. may include joined words
-                        // because of weird pdfs design.
-                        let text = self.extract_text_from_objs(&op.operands);
+                        let operands = match r {
+                            "\"" => op.operands.get(2..).unwrap_or_default(),
+                            _ => &op.operands[..],
+                        };
+                        let (text, advance) = self.show_text(operands);
                         let (x, y) = self.state.current_position();
+                        let (along, across) = self.state.text_scale();
+                        let size = self.current_font_size.unwrap_or_default();
+                        let space_width = self.get_current_font().map_or(0.25, PdfFont::space_width);
+                        self.state.advance(advance);
+                        let (end_x, end_y) = self.state.current_position();
                         current_element.text = text;
                         current_element.x = x;
                         current_element.y = y;
-                        current_element.italic = self.state.tm.c != 0.0;
+                        current_element.width = (end_x - x).hypot(end_y - y);
+                        current_element.height = size * across;
+                        current_element.space_width = space_width * size * along * self.state.horizontal_scaling;
+                        current_element.rise = self.state.rise();
+                        current_element.marked = self.marked();
+                        current_element.rotated = self.state.text_rotated();
+                        let style = self.get_current_font().map(|font| font.style).unwrap_or_default();
+                        current_element.bold = style.bold || self.state.fake_bold();
+                        current_element.italic = style.italic || self.state.text_skewed();
+                        current_element.monospace = style.monospace;
+                        current_element.color = self.state.fill_color.clone();
                         current_element.font_name = self.current_font_name.clone();
-                        current_element.font_size =
-                            self.current_font_size.map(|s| s * self.state.tm.a);
+                        current_element.font_size = self.current_font_size.map(|s| s * across);
                         elements.push(PdfUnit::Text(take(&mut current_element)));
                         Ok(())
                     }
@@ -101,45 +154,57 @@ impl<'a> PdfPage<'a> {
                             .ok_or("failed to query xobject from 'Do' operator")?;
 
                         let obj_name = obj.as_name()?;
-                        if from_utf8(obj_name).unwrap_or_default().contains("Im") {
-                            return Ok(());
-                        }
+                        let stream = self
+                            .resource(b"XObject", obj_name)
+                            .ok_or("failed to query xobject from resources")?
+                            .as_stream()?;
 
-                        let resource = self.resource.ok_or("failed to query resource from pdf")?;
-                        let dict = resource.get(b"XObject")?.as_dict()?;
-                        let id = dict.get(obj_name)?.as_reference()?;
-                        let stream = self.document.get_object(id)?.as_stream()?;
-                        let raw = stream.decompressed_content()?;
-
-                        let mut child_fonts: BTreeMap<Vec<u8>, &Dictionary> = BTreeMap::new();
-                        if let Ok(dict) = stream.dict.get(b"Resources").and_then(Object::as_dict) {
-                            if let Ok(fonts) = dict.get(b"Font").and_then(Object::as_dict) {
-                                for (k, v) in fonts.iter() {
-                                    if let Some(font_dict) = v
-                                        .as_reference()
-                                        .ok()
-                                        .and_then(|id| self.document.get_object(id).ok())
-                                        .and_then(|obj| obj.as_dict().ok())
-                                    {
-                                        child_fonts.insert(k.clone(), font_dict);
-                                    }
-                                }
+                        if is_image(stream) {
+                            let (min_x, min_y, max_x, max_y) = self.state.unit_square_bounds();
+                            let min_size = SETTINGS.read().unwrap().pdf_min_image_size;
+                            // skip decorative specks and rules
+                            if max_x - min_x < min_size || max_y - min_y < min_size {
+                                return Ok(());
                             }
+                            let data = decode_image(self.document, stream)?;
+                            elements.push(PdfUnit::Image(PdfImage {
+                                data,
+                                x: min_x,
+                                y: max_y,
+                                width: max_x - min_x,
+                                height: max_y - min_y,
+                                marked: self.marked(),
+                            }));
+                            return Ok(());
                         }
-                        let child_encodings: BTreeMap<Vec<u8>, Encoding> = child_fonts
-                            .iter()
-                            .filter_map(|(name, font)| {
-                                match font.get_font_encoding(&self.document) {
-                                    Ok(enc) => Some((name.clone(), enc)),
-                                    Err(_) => None,
-                                }
-                            })
-                            .collect();
-                        self.child_fonts = Some(child_fonts);
-                        self.child_encodings = Some(child_encodings);
+                        let raw = stream.get_plain_content()?;
 
-                        let units = self.handle_stream(raw)?;
-                        elements.extend(units);
+                        // the form draws with its own resources, or those around it without them
+                        let resources = Resources::of_form(self.document, &stream.dict);
+                        let has_resources = resources.is_some();
+                        self.resources.extend(resources);
+
+                        // the form is drawn in its own graphics state, through its /Matrix
+                        let saved = self.state.clone();
+                        let matrix: Vec<f32> = stream
+                            .dict
+                            .get(b"Matrix")
+                            .and_then(Object::as_array)
+                            .map(|m| m.iter().filter_map(|f| f.as_float().ok()).collect())
+                            .unwrap_or_default();
+                        if let [a, b, c, d, e, f] = matrix[..] {
+                            self.state.cm(a, b, c, d, e, f);
+                        }
+                        let depth = self.marked_stack.len();
+                        self.form_depth += 1;
+                        let units = self.handle_stream(raw);
+                        self.form_depth -= 1;
+                        if has_resources {
+                            self.resources.pop();
+                        }
+                        self.marked_stack.truncate(depth);
+                        self.state = saved;
+                        elements.extend(units?);
                         Ok(())
                     }
                     "BT" => {
@@ -171,8 +236,10 @@ impl<'a> PdfPage<'a> {
                         // styles realted to fonts~
                         self.current_font_size = items[1].as_float().ok();
                         let font_info = self
-                            .fonts
-                            .get(font_alias)
+                            .resources
+                            .iter()
+                            .rev()
+                            .find_map(|resources| resources.fonts.get(font_alias))
                             .ok_or("failed to get fonts for page")?;
                         let font_name = font_info.get(b"BaseFont")?;
                         self.current_font_name =
@@ -229,6 +296,26 @@ impl<'a> PdfPage<'a> {
                         self.state.td_capital(items[0], items[1]);
                         Ok(())
                     }
+                    "Tc" => {
+                        // character spacing
+                        self.state.tc(op.operands.first().ok_or("failed to query Tc")?.as_float()?);
+                        Ok(())
+                    }
+                    "Tw" => {
+                        // word spacing
+                        self.state.tw(op.operands.first().ok_or("failed to query Tw")?.as_float()?);
+                        Ok(())
+                    }
+                    "Ts" => {
+                        // text rise
+                        self.state.ts(op.operands.first().ok_or("failed to query Ts")?.as_float()?);
+                        Ok(())
+                    }
+                    "Tz" => {
+                        // horizontal scaling
+                        self.state.tz(op.operands.first().ok_or("failed to query Tz")?.as_float()?);
+                        Ok(())
+                    }
                     "TL" => {
                         // sets leading
                         self.state.tl(op.operands[0].as_float().unwrap());
@@ -239,21 +326,20 @@ impl<'a> PdfPage<'a> {
                         self.state.t_star();
                         Ok(())
                     }
-                    "sc" | "rg" => {
-                        // sets color
-                        if let Some(items) = op.operands.get(..3) {
-                            let r = items[0].as_float()?;
-                            let g = items[1].as_float()?;
-                            let b = items[2].as_float()?;
-                            if r != 0.0 || g != 0.0 || b != 0.0 {
-                                let color = rgb_to_hex(
-                                    items[0].as_float()?,
-                                    items[1].as_float()?,
-                                    items[2].as_float()?,
-                                );
-                                current_element.color = Some(color);
-                            }
-                        }
+                    "g" | "rg" | "k" | "sc" | "scn" => {
+                        // sets fill color; scn may end with a pattern name
+                        let components: Vec<f32> = op.operands.iter().filter_map(|o| o.as_float().ok()).collect();
+                        self.state.fill(&components);
+                        Ok(())
+                    }
+                    "Tr" => {
+                        // text render mode
+                        self.state.tr(op.operands.first().ok_or("failed to query Tr")?.as_i64()?);
+                        Ok(())
+                    }
+                    "w" => {
+                        // line width
+                        self.state.w(op.operands.first().ok_or("failed to query line width")?.as_float()?);
                         Ok(())
                     }
                     "SC" | "RG" => {
@@ -261,7 +347,7 @@ impl<'a> PdfPage<'a> {
                         if let Some(last) = elements.last_mut() {
                             match last {
                                 PdfUnit::Text(pdf_text) => pdf_text.underlined = true,
-                                PdfUnit::Line(_) => {}
+                                PdfUnit::Line(_) | PdfUnit::Image(_) => {}
                             };
                         }
                         Ok(())
@@ -328,24 +414,39 @@ impl<'a> PdfPage<'a> {
 
                         Ok(())
                     }
+                    "BMC" => {
+                        // marked content without properties, e.g. /Artifact BMC
+                        let tag = op.operands.first().and_then(|o| o.as_name().ok()).unwrap_or_default();
+                        self.begin_marked(tag, None);
+                        Ok(())
+                    }
                     "BDC" => {
-                        // tag with text content / stream
+                        // tag with properties, inline or named in the resources
                         //BDC: [/Span, <</ActualText (��T)>>]
-                        //rest i don't thik we should care
-                        match &op.operands[1] {
-                            Object::Dictionary(dictionary) => {
-                                let obj = dictionary.get(b"ActualText")?;
-                                let text = self.extract_text_from_obj(obj);
-                                let (x, y) = self.state.current_position();
-                                current_element.text = text;
-                                current_element.x = x;
-                                current_element.y = y;
-                                elements.push(PdfUnit::Text(take(&mut current_element)));
-                            }
-                            _ => {}
+                        //BDC: [/P, <</MCID 3>>]
+                        let tag = op.operands.first().and_then(|o| o.as_name().ok()).unwrap_or_default();
+                        let properties = match op.operands.get(1) {
+                            Some(Object::Dictionary(dictionary)) => Some(dictionary),
+                            Some(Object::Name(name)) => self.property_list(name),
+                            _ => None,
+                        };
+                        self.begin_marked(tag, properties);
+                        if let Some(obj) = properties.and_then(|p| p.get(b"ActualText").ok()) {
+                            // a text string, not codes of the current font
+                            let text = decode_text_string(&PdfPage::extract_bytes_from_obj(obj));
+                            let (x, y) = self.state.current_position();
+                            current_element.text = text;
+                            current_element.x = x;
+                            current_element.y = y;
+                            current_element.marked = self.marked();
+                            elements.push(PdfUnit::Text(take(&mut current_element)));
                         }
                         Ok(())
                     }
+                    "EMC" => {
+                        self.marked_stack.pop();
+                        Ok(())
+                    }
                     _ => {
                         // eprintln!("didnt hanlde {} with {:?}", op.operator, op.operands);
                         Ok(())
@@ -356,25 +457,45 @@ impl<'a> PdfPage<'a> {
         Ok(elements)
     }
 
-    fn get_current_encoding(&self) -> Option<&Encoding<'_>> {
-        match self.encodings.get(&self.current_font_alias) {
-            Some(en) => Some(en),
-            None => {
-                if let Some(enc) = &self.child_encodings {
-                    let encoding = enc.get(&self.current_font_alias)?;
-                    return Some(encoding);
-                }
-                None
-            }
-        }
+    fn get_current_font(&self) -> Option<&PdfFont> {
+        self.resources
+            .iter()
+            .rev()
+            .find_map(|resources| resources.decoders.get(&self.current_font_alias))
     }
 
-    fn extract_text_from_objs(&self, objs: &[Object]) -> String {
-        let mut text = String::new();
-        for obj in objs {
-            text.push_str(&self.extract_text_from_obj(obj));
-        }
-        text
+    /// Entry `name` of the resource `category` (`/XObject`, `/Properties`) in the
+    /// innermost resources that define it
+    fn resource(&self, category: &[u8], name: &[u8]) -> Option<&'a Object> {
+        let document = self.document;
+        self.resources.iter().rev().find_map(|resources| {
+            let (_, entries) = document.dereference(resources.dictionary?.get(category).ok()?).ok()?;
+            let (_, entry) = document.dereference(entries.as_dict().ok()?.get(name).ok()?).ok()?;
+            Some(entry)
+        })
+    }
+
+    /// Opens a marked-content sequence. It inherits the MCID and the artifact flag of
+    /// the sequence around it.
+    fn begin_marked(&mut self, tag: &[u8], properties: Option<&Dictionary>) {
+        let outer = self.marked();
+        // MCIDs in a form refer to the form's structure parents, not the page's
+        let mcid = properties
+            .filter(|_| self.form_depth == 0)
+            .and_then(|p| p.get(b"MCID").and_then(Object::as_i64).ok());
+        self.marked_stack.push(MarkedContent {
+            mcid: mcid.or(outer.mcid),
+            artifact: outer.artifact || tag == b"Artifact",
+        });
+    }
+
+    fn marked(&self) -> MarkedContent {
+        self.marked_stack.last().copied().unwrap_or_default()
+    }
+
+    /// A property list of the current resources, for `BDC` operators that name one
+    fn property_list(&self, name: &[u8]) -> Option<&'a Dictionary> {
+        self.resource(b"Properties", name)?.as_dict().ok()
     }
 
     fn extract_bytes_from_obj(obj: &Object) -> Vec<u8> {
@@ -393,52 +514,243 @@ impl<'a> PdfPage<'a> {
         text
     }
 
-    fn extract_text_from_obj(&self, obj: &Object) -> String {
-        let bytes = PdfPage::extract_bytes_from_obj(obj);
-
-        // encoding from font
-        if let Some(s) = self
-            .get_current_encoding()
-            .and_then(|encoding| Document::decode_text(encoding, &bytes).ok())
-        {
-            return s;
-        }
+    /// Text of the strings of a Tj / TJ operator and how far they move the text position,
+    /// in unscaled text space units. TJ offsets wider than half a space become spaces.
+    fn show_text(&self, operands: &[Object]) -> (String, f32) {
+        let font = self.get_current_font();
+        let size = self.current_font_size.unwrap_or_default();
+        let (char_spacing, word_spacing) = (self.state.char_spacing, self.state.word_spacing);
+        let space_width = font.map_or(0.25, PdfFont::space_width);
 
-        // if utf16
-        if bytes.len() >= 2 {
-            match (bytes[0], bytes[1]) {
-                (0xFE, 0xFF) => {
-                    // UTF-16BE
-                    let u16s: Vec<u16> = bytes[2..]
-                        .chunks(2)
-                        .filter_map(|chunk| {
-                            chunk.get(1).map(|&b1| u16::from_be_bytes([chunk[0], b1]))
-                        })
-                        .collect();
-                    return String::from_utf16_lossy(&u16s);
+        let mut text = String::new();
+        let mut advance = 0.0;
+        let items = operands.iter().flat_map(|obj| match obj {
+            Object::Array(items) => items.iter().collect(),
+            _ => vec![obj],
+        });
+        for item in items {
+            match item {
+                Object::Integer(_) | Object::Real(_) => {
+                    // thousandths of an em, subtracted from the advance
+                    let offset = -item.as_float().unwrap_or_default() / 1000.0;
+                    if offset > space_width * SPACE_FRACTION && !text.ends_with(char::is_whitespace) {
+                        text.push(' ');
+                    }
+                    advance += offset * size;
                 }
-                (0xFF, 0xFE) => {
-                    // UTF-16LE
-                    let u16s: Vec<u16> = bytes[2..]
-                        .chunks(2)
-                        .filter_map(|chunk| {
-                            chunk.get(1).map(|&b1| u16::from_le_bytes([chunk[0], b1]))
-                        })
-                        .collect();
-                    return String::from_utf16_lossy(&u16s);
+                _ => {
+                    let bytes = PdfPage::extract_bytes_from_obj(item);
+                    let Some(font) = font else {
+                        let decoded = decode_text_string(&bytes);
+                        advance += decoded.chars().count() as f32 * (0.5 * size + char_spacing);
+                        text.push_str(&decoded);
+                        continue;
+                    };
+                    for (code, length) in font.codes(&bytes) {
+                        advance += font.width(code, length) * size + char_spacing;
+                        // word spacing applies to single-byte code 32 only
+                        if code == 32 && length == 1 {
+                            advance += word_spacing;
+                        }
+                    }
+                    text.push_str(&font.decode(&bytes));
                 }
-                _ => {}
             }
         }
+        (text, advance)
+    }
+}
 
-        // fallback to utf8
-        String::from_utf8_lossy(&bytes).into_owned()
+/// Decodes a PDF text string (e.g. `/ActualText`): UTF-16 with a byte order mark, else UTF-8
+pub fn decode_text_string(bytes: &[u8]) -> String {
+    // if utf16
+    if bytes.len() >= 2 {
+        match (bytes[0], bytes[1]) {
+            (0xFE, 0xFF) => {
+                // UTF-16BE
+                let u16s: Vec<u16> = bytes[2..]
+                    .chunks(2)
+                    .filter_map(|chunk| chunk.get(1).map(|&b1| u16::from_be_bytes([chunk[0], b1])))
+                    .collect();
+                return String::from_utf16_lossy(&u16s);
+            }
+            (0xFF, 0xFE) => {
+                // UTF-16LE
+                let u16s: Vec<u16> = bytes[2..]
+                    .chunks(2)
+                    .filter_map(|chunk| chunk.get(1).map(|&b1| u16::from_le_bytes([chunk[0], b1])))
+                    .collect();
+                return String::from_utf16_lossy(&u16s);
+            }
+            _ => {}
+        }
     }
+
+    // fallback to utf8
+    String::from_utf8_lossy(bytes).into_owned()
 }
 
-fn rgb_to_hex(r: f32, g: f32, b: f32) -> String {
-    let r = (r * 255.0).round() as u8;
-    let g = (g * 255.0).round() as u8;
-    let b = (b * 255.0).round() as u8;
-    format!("#{:02X}{:02X}{:02X}", r, g, b)
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, Stream};
+
+    /// A gray image `width` samples wide and one high
+    fn image(width: i64) -> Stream {
+        let dict = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => width,
+            "Height" => 1,
+            "ColorSpace" => "DeviceGray",
+            "BitsPerComponent" => 8,
+        };
+        Stream::new(dict, vec![128; width as usize])
+    }
+
+    /// A one-page document drawing `content` with `resources`
+    fn document(content: &[u8], resources: Dictionary) -> (Document, ObjectId) {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.to_vec()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+            "Resources" => resources,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_id.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        (doc, page_id)
+    }
+
+    fn form(doc: &mut Document, content: &[u8], resources: Option<Dictionary>) -> ObjectId {
+        let mut dict = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), 1.into(), 1.into()],
+        };
+        if let Some(resources) = resources {
+            dict.set("Resources", resources);
+        }
+        doc.add_object(Stream::new(dict, content.to_vec()))
+    }
+
+    fn units(doc: &Document, page_id: ObjectId) -> Vec<PdfUnit> {
+        let mut page = PdfPage::from_object_id(doc, page_id).unwrap();
+        page.handle_stream(page.stream.clone()).unwrap()
+    }
+
+    fn image_widths(units: &[PdfUnit]) -> Vec<u32> {
+        units
+            .iter()
+            .filter_map(|unit| match unit {
+                PdfUnit::Image(image) => Some(image::load_from_memory(&image.data).unwrap().width()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_image_inside_form_uses_form_resources() {
+        let (mut doc, page_id) = document(b"q 100 0 0 100 0 0 cm /Fm1 Do Q", dictionary! {});
+        let page_image = doc.add_object(image(3));
+        let form_image = doc.add_object(image(5));
+        let form_resources = Some(dictionary! { "XObject" => dictionary! { "Im1" => form_image } });
+        let form_id = form(&mut doc, b"/Im1 Do", form_resources);
+        let resources = dictionary! { "XObject" => dictionary! { "Fm1" => form_id, "Im1" => page_image } };
+        doc.get_object_mut(page_id).unwrap().as_dict_mut().unwrap().set("Resources", resources);
+
+        assert_eq!(image_widths(&units(&doc, page_id)), vec![5]);
+    }
+
+    #[test]
+    fn test_form_without_resources_uses_page_resources() {
+        let (mut doc, page_id) = document(b"q 100 0 0 100 0 0 cm /Fm1 Do Q", dictionary! {});
+        let page_image = doc.add_object(image(3));
+        let form_id = form(&mut doc, b"/Im1 Do", None);
+        let resources = dictionary! { "XObject" => dictionary! { "Fm1" => form_id, "Im1" => page_image } };
+        doc.get_object_mut(page_id).unwrap().as_dict_mut().unwrap().set("Resources", resources);
+
+        assert_eq!(image_widths(&units(&doc, page_id)), vec![3]);
+    }
+
+    #[test]
+    fn test_form_resources_end_with_the_form() {
+        // the form's /Im1 must not replace the page's once the form is drawn
+        let (mut doc, page_id) = document(b"100 0 0 100 0 0 cm /Fm1 Do /Im1 Do", dictionary! {});
+        let page_image = doc.add_object(image(3));
+        let form_image = doc.add_object(image(5));
+        let form_resources = Some(dictionary! { "XObject" => dictionary! { "Im1" => form_image } });
+        let form_id = form(&mut doc, b"/Im1 Do", form_resources);
+        let resources = dictionary! { "XObject" => dictionary! { "Fm1" => form_id, "Im1" => page_image } };
+        doc.get_object_mut(page_id).unwrap().as_dict_mut().unwrap().set("Resources", resources);
+
+        assert_eq!(image_widths(&units(&doc, page_id)), vec![5, 3]);
+    }
+
+    fn font(base_font: &str) -> Dictionary {
+        dictionary! { "Type" => "Font", "Subtype" => "Type1", "BaseFont" => base_font }
+    }
+
+    #[test]
+    fn test_form_fonts_take_precedence() {
+        let (mut doc, page_id) = document(b"/Fm1 Do BT /F1 10 Tf (page) Tj ET", dictionary! {});
+        let page_font = doc.add_object(font("Helvetica"));
+        let form_font = doc.add_object(font("Courier"));
+        let form_resources = Some(dictionary! { "Font" => dictionary! { "F1" => form_font } });
+        let form_id = form(&mut doc, b"BT /F1 10 Tf (form) Tj ET", form_resources);
+        let resources = dictionary! {
+            "Font" => dictionary! { "F1" => page_font },
+            "XObject" => dictionary! { "Fm1" => form_id },
+        };
+        doc.get_object_mut(page_id).unwrap().as_dict_mut().unwrap().set("Resources", resources);
+
+        let texts: Vec<(String, Option<String>, f32)> = units(&doc, page_id)
+            .into_iter()
+            .filter_map(|unit| match unit {
+                PdfUnit::Text(text) => Some((text.text, text.font_name, text.width)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts.len(), 2);
+        assert_eq!((texts[0].0.as_str(), texts[0].1.as_deref()), ("form", Some("Courier")));
+        assert_eq!((texts[1].0.as_str(), texts[1].1.as_deref()), ("page", Some("Helvetica")));
+        // measured with the form's monospaced font: four 600-unit glyphs at 10pt
+        assert!((texts[0].2 - 24.0).abs() < 0.01, "{}", texts[0].2);
+    }
+
+    #[test]
+    fn test_named_property_lists_resolve_through_form_resources() {
+        let (mut doc, page_id) = document(b"/P <</MCID 3>> BDC /Fm1 Do EMC /P /MC0 BDC EMC", dictionary! {});
+        let properties = |text: &str, mcid: i64| {
+            dictionary! { "MCID" => mcid, "ActualText" => Object::string_literal(text) }
+        };
+        let form_resources = Some(dictionary! { "Properties" => dictionary! { "MC0" => properties("form", 7) } });
+        let form_id = form(&mut doc, b"/Span /MC0 BDC EMC", form_resources);
+        let resources = dictionary! {
+            "XObject" => dictionary! { "Fm1" => form_id },
+            "Properties" => dictionary! { "MC0" => properties("page", 4) },
+        };
+        doc.get_object_mut(page_id).unwrap().as_dict_mut().unwrap().set("Resources", resources);
+
+        let marks: Vec<(String, Option<i64>)> = units(&doc, page_id)
+            .into_iter()
+            .filter_map(|unit| match unit {
+                PdfUnit::Text(text) => Some((text.text, text.marked.mcid)),
+                _ => None,
+            })
+            .collect();
+        // the form's own property list; MCIDs inside a form are the page's
+        assert_eq!(marks, vec![("form".to_string(), Some(3)), ("page".to_string(), Some(4))]);
+    }
+}
@@ -46,12 +46,34 @@ impl Matrix3x3 {
     }
 }
 
+fn rgb_to_hex(r: f32, g: f32, b: f32) -> String {
+    let r = (r * 255.0).round() as u8;
+    let g = (g * 255.0).round() as u8;
+    let b = (b * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
 #[derive(Debug, Clone)]
 pub struct PdfState {
     ctm: Matrix3x3,
     pub tm: Matrix3x3,
+    /// Text line matrix: the start of the current line, which Td and T* move from
+    tlm: Matrix3x3,
     leading: f32,
     pub m: (f32, f32),
+    /// Tc, in unscaled text space units
+    pub char_spacing: f32,
+    /// Tw, in unscaled text space units
+    pub word_spacing: f32,
+    /// Tz, as a fraction
+    pub horizontal_scaling: f32,
+    /// Ts, in unscaled text space units
+    rise: f32,
+    /// Tr
+    render_mode: i64,
+    line_width: f32,
+    /// Nonstroking color as `#RRGGBB`, `None` for black
+    pub fill_color: Option<String>,
 }
 
 impl Default for PdfState {
@@ -64,13 +86,22 @@ impl PdfState {
         Self {
             tm: Matrix3x3::identity(),
             ctm: Matrix3x3::identity(),
+            tlm: Matrix3x3::identity(),
             leading: 0.0,
             m: (0.0, 0.0),
+            char_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scaling: 1.0,
+            rise: 0.0,
+            render_mode: 0,
+            line_width: 1.0,
+            fill_color: None,
         }
     }
 
     pub fn bt(&mut self) {
         self.tm = Matrix3x3::identity();
+        self.tlm = Matrix3x3::identity();
     }
 
     pub fn et(&mut self) {
@@ -83,7 +114,8 @@ impl PdfState {
 
     pub fn td(&mut self, tx: f32, ty: f32) {
         let translation = Matrix3x3::from_components(1.0, 0.0, 0.0, 1.0, tx, ty);
-        self.tm = self.tm.multiply(&translation);
+        self.tlm = self.tlm.multiply(&translation);
+        self.tm = self.tlm;
     }
 
     pub fn td_capital(&mut self, tx: f32, ty: f32) {
@@ -94,25 +126,115 @@ impl PdfState {
     pub fn tm(&mut self, a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) {
         let m = Matrix3x3::from_components(a, b, c, d, e, f);
         self.tm = m;
+        self.tlm = m;
+    }
+
+    pub fn tc(&mut self, char_spacing: f32) {
+        self.char_spacing = char_spacing;
+    }
+
+    pub fn tw(&mut self, word_spacing: f32) {
+        self.word_spacing = word_spacing;
+    }
+
+    pub fn ts(&mut self, rise: f32) {
+        self.rise = rise;
+    }
+
+    pub fn tr(&mut self, render_mode: i64) {
+        self.render_mode = render_mode;
+    }
+
+    pub fn w(&mut self, line_width: f32) {
+        self.line_width = line_width;
+    }
+
+    /// Sets the fill color from gray, RGB or CMYK components
+    pub fn fill(&mut self, components: &[f32]) {
+        let (r, g, b) = match components {
+            [gray] => (*gray, *gray, *gray),
+            [r, g, b] => (*r, *g, *b),
+            [c, m, y, k] => ((1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k)),
+            _ => return,
+        };
+        self.fill_color = match r == 0.0 && g == 0.0 && b == 0.0 {
+            true => None,
+            false => Some(rgb_to_hex(r, g, b)),
+        };
+    }
+
+    /// Text both filled and stroked with a visible line, a common way to embolden a
+    /// regular font
+    pub fn fake_bold(&self) -> bool {
+        matches!(self.render_mode, 2 | 6) && self.line_width > 0.0
+    }
+
+    pub fn tz(&mut self, scale: f32) {
+        self.horizontal_scaling = scale / 100.0;
+    }
+
+    /// Moves the text position along the baseline after showing text; `tx` is in
+    /// unscaled text space units, before horizontal scaling
+    pub fn advance(&mut self, tx: f32) {
+        let translation = Matrix3x3::from_components(1.0, 0.0, 0.0, 1.0, tx * self.horizontal_scaling, 0.0);
+        self.tm = self.tm.multiply(&translation);
+    }
+
+    /// How much one text space unit measures on the page along and across the baseline
+    pub fn text_scale(&self) -> (f32, f32) {
+        let combined = self.ctm.multiply(&self.tm);
+        (combined.a.hypot(combined.b), combined.c.hypot(combined.d))
     }
 
     pub fn t_star(&mut self) {
         self.td(0.0, -self.leading);
     }
 
+    /// Concatenates a transformation (`cm`, or a form XObject's `/Matrix`) with the CTM
     pub fn cm(&mut self, a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) {
-        self.ctm = Matrix3x3::from_components(a, b, c, d, e, f);
+        let m = Matrix3x3::from_components(a, b, c, d, e, f);
+        self.ctm = self.ctm.multiply(&m);
     }
 
     pub fn m(&mut self, tx: f32, ty: f32) {
         self.m = (tx, ty);
     }
 
+    /// Page position of the text origin on the baseline, without the rise
     pub fn current_position(&self) -> (f32, f32) {
-        // can be wrong. but i think we apply the tm on the ctm and not the other way around
+        // text space is mapped by the text matrix first, then the CTM
         let combined = self.ctm.multiply(&self.tm);
-        let f = combined.apply_to_origin();
-        f
+        combined.apply_to_origin()
+    }
+
+    /// Text rise in page units
+    pub fn rise(&self) -> f32 {
+        self.rise * self.text_scale().1
+    }
+
+    /// Whether the baseline runs other than left to right, e.g. a vertical label
+    pub fn text_rotated(&self) -> bool {
+        let combined = self.ctm.multiply(&self.tm);
+        combined.a <= 0.0 || combined.b.abs() > combined.a * 0.1
+    }
+
+    /// Whether the glyphs are slanted by the text matrix (synthetic italics)
+    pub fn text_skewed(&self) -> bool {
+        let m = self.ctm.multiply(&self.tm);
+        let (along, across) = self.text_scale();
+        // cosine of the angle between the text space axes
+        along > 0.0 && across > 0.0 && ((m.a * m.c + m.b * m.d) / (along * across)).abs() > 0.1
+    }
+
+    /// Page-space bounding box (min x, min y, max x, max y) of the unit square
+    /// that images are drawn into
+    pub fn unit_square_bounds(&self) -> (f32, f32, f32, f32) {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)]
+            .map(|(x, y)| self.ctm.apply_to_point(x, y));
+        corners.iter().fold(
+            (f32::INFINITY, f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+            |(min_x, min_y, max_x, max_y), (x, y)| (min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y)),
+        )
     }
 
     pub fn l(&self, to: (f32, f32)) -> ((f32, f32), (f32, f32)) {
@@ -126,4 +248,82 @@ impl PdfState {
         let to = self.ctm.apply_to_point(to.0, to.1);
         (from, to)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-3 && (actual.1 - expected.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_cm_concatenates() {
+        let mut state = PdfState::new();
+        state.cm(1.0, 0.0, 0.0, 1.0, 100.0, 200.0);
+        state.cm(2.0, 0.0, 0.0, 2.0, 10.0, 10.0);
+        state.bt();
+        state.td(5.0, 5.0);
+        // (5, 5) -> scaled and moved by the inner cm -> moved by the outer cm
+        assert_close(state.current_position(), (120.0, 220.0));
+        assert_close(state.text_scale(), (2.0, 2.0));
+    }
+
+    #[test]
+    fn test_q_restores_ctm() {
+        let mut state = PdfState::new();
+        state.cm(1.0, 0.0, 0.0, 1.0, 50.0, 50.0);
+        let saved = state.clone();
+        state.cm(1.0, 0.0, 0.0, 1.0, 25.0, 0.0);
+        state = saved;
+        state.bt();
+        assert_close(state.current_position(), (50.0, 50.0));
+    }
+
+    #[test]
+    fn test_td_moves_from_line_start() {
+        let mut state = PdfState::new();
+        state.bt();
+        state.tm(1.0, 0.0, 0.0, 1.0, 72.0, 700.0);
+        state.tl(14.0);
+        state.advance(120.0);
+        state.t_star();
+        assert_close(state.current_position(), (72.0, 686.0));
+        state.advance(30.0);
+        state.td(10.0, 0.0);
+        assert_close(state.current_position(), (82.0, 686.0));
+    }
+
+    #[test]
+    fn test_horizontal_scaling_and_rise() {
+        let mut state = PdfState::new();
+        state.bt();
+        state.tm(2.0, 0.0, 0.0, 2.0, 0.0, 0.0);
+        state.tz(50.0);
+        state.advance(10.0);
+        assert_close(state.current_position(), (10.0, 0.0));
+        state.ts(3.0);
+        assert!((state.rise() - 6.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rotation_and_skew() {
+        let mut state = PdfState::new();
+        state.bt();
+        assert!(!state.text_rotated() && !state.text_skewed());
+        // 90 degrees counter-clockwise
+        state.tm(0.0, 1.0, -1.0, 0.0, 30.0, 400.0);
+        assert!(state.text_rotated());
+        assert!(!state.text_skewed());
+        // slanted by about 12 degrees
+        state.tm(1.0, 0.0, 0.21, 1.0, 0.0, 0.0);
+        assert!(!state.text_rotated());
+        assert!(state.text_skewed());
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use super::page_label::roman;
+use super::pdf_element::PdfUnit;
+
+/// Rows this close to the top or bottom of the text on a page can be running headers or footers
+const EDGE_ROWS: usize = 3;
+/// Texts on one baseline within this distance form a row
+const ROW_TOLERANCE: f32 = 1.0;
+/// A repeated row must sit at the same height, within this distance, on every page
+const POSITION_TOLERANCE: f32 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Region {
+    Top,
+    Bottom,
+}
+
+/// A row of text near the top or bottom of a page
+struct EdgeRow {
+    region: Region,
+    /// 0 for the outermost row
+    depth: usize,
+    y: f32,
+    /// Indices of the page's units on the row
+    units: Vec<usize>,
+    text: String,
+}
+
+/// Removes running headers, footers and page numbers from the units of every page.
+///
+/// A row near the top or bottom of a page is running text when the same text (numbers
+/// ignored on the outermost row) is at the same height on several pages (three, or both
+/// pages of a two-page document), and every row between it and the page edge is running
+/// text too. An outermost row that reads as a page number is running text when page
+/// numbers sit at its height on as many pages and increase from page to page.
+pub fn remove_running_text(pages: &mut [Vec<PdfUnit>]) {
+    let min_pages = pages.len().clamp(2, 3);
+    let edges: Vec<Vec<EdgeRow>> = pages.iter().map(|units| edge_rows(units)).collect();
+
+    // (region, normalized text) -> heights on the pages it appears on
+    let mut occurrences: HashMap<(Region, String), Vec<f32>> = HashMap::new();
+    for rows in &edges {
+        for row in rows {
+            let heights = occurrences.entry((row.region, row.key())).or_default();
+            heights.push(row.y);
+        }
+    }
+    let is_repeated = |row: &EdgeRow| {
+        let Some(heights) = occurrences.get(&(row.region, row.key())) else {
+            return false;
+        };
+        let same_place = heights
+            .iter()
+            .filter(|y| (**y - row.y).abs() <= POSITION_TOLERANCE)
+            .count();
+        same_place >= min_pages
+    };
+
+    // (region, height, value) of the outermost rows that read as page numbers, in page order
+    let numbers: Vec<(Region, f32, i64)> = edges
+        .iter()
+        .flatten()
+        .filter(|row| row.depth == 0)
+        .filter_map(|row| Some((row.region, row.y, page_number(&row.text)?)))
+        .collect();
+    let is_numbering = |row: &EdgeRow| {
+        let values: Vec<i64> = numbers
+            .iter()
+            .filter(|(region, y, _)| *region == row.region && (y - row.y).abs() <= POSITION_TOLERANCE)
+            .map(|(_, _, value)| *value)
+            .collect();
+        values.len() >= min_pages && values.windows(2).all(|pair| pair[0] < pair[1])
+    };
+    let is_running = |row: &EdgeRow| match page_number(&row.text) {
+        Some(_) if row.depth == 0 => is_numbering(row),
+        _ => is_repeated(row),
+    };
+
+    for (units, rows) in pages.iter_mut().zip(&edges) {
+        // working inwards from the page edge, running text ends at the first row that isn't
+        let mut running: Vec<usize> = [Region::Top, Region::Bottom]
+            .into_iter()
+            .flat_map(|region| {
+                rows.iter()
+                    .filter(move |row| row.region == region)
+                    .take_while(|row| is_running(row))
+            })
+            .flat_map(|row| row.units.iter().copied())
+            .collect();
+        running.sort_unstable();
+        running.dedup();
+        for index in running.into_iter().rev() {
+            units.remove(index);
+        }
+    }
+}
+
+impl EdgeRow {
+    /// What the row is compared by: numbers are ignored on the outermost row, where
+    /// page numbers are, unless they are all it holds; rows further in must repeat exactly
+    fn key(&self) -> String {
+        match self.depth {
+            0 if self.text.chars().any(char::is_alphabetic) => normalize(&self.text),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+/// The top and bottom rows of text on a page
+fn edge_rows(units: &[PdfUnit]) -> Vec<EdgeRow> {
+    let mut texts: Vec<(usize, f32, f32)> = units
+        .iter()
+        .enumerate()
+        .filter_map(|(i, unit)| match unit {
+            PdfUnit::Text(text) if !text.text.trim().is_empty() => Some((i, text.x, text.y)),
+            _ => None,
+        })
+        .collect();
+    texts.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut rows: Vec<Vec<(usize, f32, f32)>> = Vec::new();
+    for text in texts {
+        match rows.last_mut() {
+            Some(row) if (row[0].2 - text.2).abs() <= ROW_TOLERANCE => row.push(text),
+            _ => rows.push(vec![text]),
+        }
+    }
+
+    let count = rows.len();
+    // outermost first in each region
+    let mut edges = Vec::new();
+    for (index, mut row) in rows.into_iter().enumerate() {
+        // the top and bottom regions don't overlap on pages with few rows
+        let (region, depth) = if index < EDGE_ROWS && index < count.div_ceil(2) {
+            (Region::Top, index)
+        } else if index + EDGE_ROWS >= count && index >= count.div_ceil(2) {
+            (Region::Bottom, count - 1 - index)
+        } else {
+            continue;
+        };
+        row.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        let text = row
+            .iter()
+            .filter_map(|(i, _, _)| match &units[*i] {
+                PdfUnit::Text(text) => Some(text.text.trim()),
+                _ => None,
+            })
+            .collect::<Vec<&str>>()
+            .join(" ");
+        edges.push(EdgeRow {
+            region,
+            depth,
+            y: row[0].2,
+            units: row.iter().map(|(i, _, _)| *i).collect(),
+            text,
+        });
+    }
+    edges.sort_by_key(|row| (row.region == Region::Bottom, row.depth));
+    edges
+}
+
+/// Lowercase text with whitespace collapsed and every number replaced by `#`,
+/// so "Page 3 of 10" and "Page 4 of 10" compare equal
+fn normalize(text: &str) -> String {
+    let mut normalized = String::new();
+    for word in text.split_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        let mut in_number = false;
+        for c in word.chars().flat_map(char::to_lowercase) {
+            if c.is_ascii_digit() {
+                if !in_number {
+                    normalized.push('#');
+                }
+                in_number = true;
+            } else {
+                normalized.push(c);
+                in_number = false;
+            }
+        }
+    }
+    normalized
+}
+
+/// The number of a page number row: "12", "- 12 -", "Page 12", "12 / 40", "Page 12 of 40",
+/// "xii", "第 12 页"
+fn page_number(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if let Some(number) = text.strip_prefix('第').and_then(|t| t.strip_suffix('页')) {
+        return decimal(number.trim());
+    }
+
+    let text = text
+        .to_lowercase()
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '–' | '—' | '|'))
+        .to_string();
+    let number = ["page ", "p. ", "pg. "]
+        .iter()
+        .find_map(|prefix| text.strip_prefix(prefix))
+        .unwrap_or(&text)
+        .trim();
+
+    let total = number.split_once(" of ").or_else(|| number.split_once('/'));
+    if let Some((number, total)) = total {
+        let (number, total) = (decimal(number.trim())?, decimal(total.trim())?);
+        return (number <= total).then_some(number);
+    }
+    decimal(number).or_else(|| roman_value(number))
+}
+
+fn decimal(text: &str) -> Option<i64> {
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Value of a lowercase roman numeral, `None` unless written the standard way ("iv", not "iiii")
+fn roman_value(text: &str) -> Option<i64> {
+    let digit = |c: char| match c {
+        'i' => Some(1),
+        'v' => Some(5),
+        'x' => Some(10),
+        'l' => Some(50),
+        'c' => Some(100),
+        'd' => Some(500),
+        'm' => Some(1000),
+        _ => None,
+    };
+    let digits: Vec<i64> = text.chars().map(digit).collect::<Option<_>>()?;
+    let mut value = 0;
+    for (i, digit) in digits.iter().enumerate() {
+        match digits.get(i + 1) {
+            Some(next) if next > digit => value -= digit,
+            _ => value += digit,
+        }
+    }
+    (value > 0 && roman(value).to_lowercase() == text).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pdf2md::pdf_element::PdfText;
+
+    fn text(text: &str, y: f32) -> PdfUnit {
+        PdfUnit::Text(PdfText {
+            text: text.to_string(),
+            x: 72.0,
+            y,
+            ..Default::default()
+        })
+    }
+
+    fn body(page: usize, row: usize) -> String {
+        format!("{} {}", ["Apples", "Pears", "Plums"][page], ["one", "two", "three"][row])
+    }
+
+    /// Pages with three rows of body text, different on every page, and `footer` below them
+    fn pages(footers: &[&str]) -> Vec<Vec<PdfUnit>> {
+        footers
+            .iter()
+            .enumerate()
+            .map(|(page, footer)| {
+                let mut units: Vec<PdfUnit> = (0..3)
+                    .map(|row| text(&body(page, row), 700.0 - 20.0 * row as f32))
+                    .collect();
+                units.push(text(footer, 40.0));
+                units
+            })
+            .collect()
+    }
+
+    fn texts(page: &[PdfUnit]) -> Vec<&str> {
+        page.iter()
+            .filter_map(|unit| match unit {
+                PdfUnit::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_page_number() {
+        assert_eq!(page_number("12"), Some(12));
+        assert_eq!(page_number("- 12 -"), Some(12));
+        assert_eq!(page_number("Page 12"), Some(12));
+        assert_eq!(page_number("12 / 40"), Some(12));
+        assert_eq!(page_number("Page 12 of 40"), Some(12));
+        assert_eq!(page_number("xii"), Some(12));
+        assert_eq!(page_number("第 12 页"), Some(12));
+        assert_eq!(page_number("2023 2024"), None);
+        assert_eq!(page_number("41 of 40"), None);
+    }
+
+    #[test]
+    fn test_roman_page_numbers_are_validated() {
+        assert_eq!(roman_value("xiv"), Some(14));
+        assert_eq!(roman_value("mcmxc"), Some(1990));
+        for word in ["civil", "ill", "vicci", "iiii", "vv", "ic"] {
+            assert_eq!(roman_value(word), None, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_increasing_page_numbers_are_removed() {
+        let mut pages = pages(&["1", "- 2 -", "iii"]);
+        remove_running_text(&mut pages);
+        for (i, page) in pages.iter().enumerate() {
+            assert_eq!(texts(page), vec![body(i, 0), body(i, 1), body(i, 2)]);
+        }
+    }
+
+    #[test]
+    fn test_page_number_on_single_page_is_kept() {
+        let mut pages = pages(&["3"]);
+        remove_running_text(&mut pages);
+        assert_eq!(texts(&pages[0]).last(), Some(&"3"));
+    }
+
+    #[test]
+    fn test_numbers_that_do_not_increase_are_kept() {
+        let mut pages = pages(&["5", "5", "2"]);
+        remove_running_text(&mut pages);
+        assert!(pages.iter().all(|page| page.len() == 4));
+    }
+
+    #[test]
+    fn test_roman_looking_words_are_kept() {
+        let mut pages = pages(&["Civil", "ill", "vicci"]);
+        remove_running_text(&mut pages);
+        assert!(pages.iter().all(|page| page.len() == 4));
+    }
+
+    #[test]
+    fn test_numeric_table_row_is_kept() {
+        let mut pages = pages(&["2023 2024", "2019 2020", "2021 2022"]);
+        remove_running_text(&mut pages);
+        assert!(pages.iter().all(|page| page.len() == 4));
+    }
+
+    #[test]
+    fn test_repeated_footer_is_removed() {
+        let mut pages = pages(&["Report 2024 · Page 1", "Report 2024 · Page 2", "Report 2024 · Page 3"]);
+        remove_running_text(&mut pages);
+        assert!(pages.iter().all(|page| page.len() == 3));
+    }
+}
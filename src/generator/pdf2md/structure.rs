@@ -0,0 +1,809 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+use super::paragraph::{self, TextLine};
+use super::pdf_element::{PdfText, PdfUnit};
+use super::pdf_image::PdfImage;
+use super::pdf_page::decode_text_string;
+use super::{image_to_md, strip_bold, text_line, to_markdown_table};
+
+/// Structure types that only group other elements
+const GROUPING: [&[u8]; 12] = [
+    b"Document",
+    b"DocumentFragment",
+    b"Part",
+    b"Art",
+    b"Sect",
+    b"Div",
+    b"NonStruct",
+    b"Private",
+    b"TOC",
+    b"Index",
+    b"Aside",
+    b"Form",
+];
+/// Structure types whose content runs on within the block around them
+const INLINE: [&[u8]; 22] = [
+    b"Span", b"Quote", b"Note", b"Reference", b"BibEntry", b"Code", b"Link", b"Annot", b"Ruby", b"RB",
+    b"RT", b"RP", b"Warichu", b"WT", b"WP", b"Lbl", b"LBody", b"Em", b"Strong", b"Sub", b"FENote",
+    b"Formula",
+];
+/// Block-level structure types
+const BLOCK: [&[u8]; 23] = [
+    b"P", b"H", b"H1", b"H2", b"H3", b"H4", b"H5", b"H6", b"Title", b"Caption", b"BlockQuote", b"L", b"LI",
+    b"Table", b"TR", b"TH", b"TD", b"THead", b"TBody", b"TFoot", b"Figure", b"TOCI", b"Artifact",
+];
+/// `/ListNumbering` values of unnumbered lists
+const BULLETS: [&[u8]; 4] = [b"None", b"Disc", b"Circle", b"Square"];
+/// Structure trees nested deeper than this are treated as broken
+const MAX_DEPTH: usize = 64;
+
+/// Marked content of a structure element as (page index, MCID), in reading order
+type Content = Vec<(usize, i64)>;
+
+/// A block-level element of the structure tree
+#[derive(Debug, Clone)]
+pub enum Block {
+    Heading(usize, Content),
+    Paragraph(Content),
+    Quote(Content),
+    ListItem {
+        /// 0 for top-level lists
+        depth: usize,
+        /// Position in a numbered list
+        number: Option<usize>,
+        label: Content,
+        body: Content,
+    },
+    Table(Vec<TableRow>),
+    Figure {
+        alt: Option<String>,
+        content: Content,
+        /// Page of the element, for figures without marked content
+        page: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct TableRow {
+    header: bool,
+    cells: Vec<Content>,
+}
+
+impl Block {
+    /// The pages the block has content on
+    fn pages(&self) -> BTreeSet<usize> {
+        let contents: Vec<&Content> = match self {
+            Block::Heading(_, content) | Block::Paragraph(content) | Block::Quote(content) => vec![content],
+            Block::ListItem { label, body, .. } => vec![label, body],
+            Block::Table(rows) => rows.iter().flat_map(|row| row.cells.iter()).collect(),
+            Block::Figure { content, page, .. } => {
+                let mut pages: BTreeSet<usize> = content.iter().map(|(page, _)| *page).collect();
+                if pages.is_empty() {
+                    pages.extend(*page);
+                }
+                return pages;
+            }
+        };
+        contents.into_iter().flatten().map(|(page, _)| *page).collect()
+    }
+
+    /// The part of the block on `page`
+    fn on_page(&self, page: usize) -> Block {
+        let only = |content: &Content| -> Content { content.iter().filter(|(p, _)| *p == page).copied().collect() };
+        match self {
+            Block::Heading(level, content) => Block::Heading(*level, only(content)),
+            Block::Paragraph(content) => Block::Paragraph(only(content)),
+            Block::Quote(content) => Block::Quote(only(content)),
+            Block::ListItem { depth, number, label, body } => Block::ListItem {
+                depth: *depth,
+                number: *number,
+                label: only(label),
+                body: only(body),
+            },
+            Block::Table(rows) => Block::Table(
+                rows.iter()
+                    .map(|row| TableRow { header: row.header, cells: row.cells.iter().map(only).collect() })
+                    .filter(|row| row.cells.iter().any(|cell| !cell.is_empty()))
+                    .collect(),
+            ),
+            Block::Figure { alt, content, page: figure_page } => Block::Figure {
+                alt: alt.clone(),
+                content: only(content),
+                page: *figure_page,
+            },
+        }
+    }
+
+    /// MCIDs of all the block's content
+    fn mcids(&self) -> Vec<i64> {
+        let contents: Vec<&Content> = match self {
+            Block::Heading(_, content) | Block::Paragraph(content) | Block::Quote(content) => vec![content],
+            Block::ListItem { label, body, .. } => vec![label, body],
+            Block::Table(rows) => rows.iter().flat_map(|row| row.cells.iter()).collect(),
+            Block::Figure { content, .. } => vec![content],
+        };
+        contents.into_iter().flatten().map(|(_, mcid)| *mcid).collect()
+    }
+}
+
+/// The logical structure of a tagged PDF, as the blocks on each page in reading order
+pub struct StructTree {
+    pages: Vec<Vec<Block>>,
+}
+
+impl StructTree {
+    /// The structure tree of the document, if it is tagged
+    pub fn read(document: &Document) -> Option<StructTree> {
+        let catalog = document.catalog().ok()?;
+        let (_, root) = document.dereference(catalog.get(b"StructTreeRoot").ok()?).ok()?;
+        let root = root.as_dict().ok()?;
+        let role_map = root
+            .get(b"RoleMap")
+            .and_then(|o| document.dereference(o))
+            .and_then(|(_, o)| o.as_dict())
+            .ok();
+
+        let mut walker = Walker {
+            document,
+            pages: document.page_iter().enumerate().map(|(i, id)| (id, i)).collect(),
+            role_map,
+            blocks: Vec::new(),
+        };
+        walker.group(root, None, 0, 0);
+        if walker.blocks.is_empty() {
+            return None;
+        }
+
+        let mut pages = vec![Vec::new(); walker.pages.len()];
+        for block in walker.blocks {
+            for page in block.pages() {
+                if let Some(blocks) = pages.get_mut(page) {
+                    blocks.push(block.on_page(page));
+                }
+            }
+        }
+        Some(StructTree { pages })
+    }
+
+    /// The blocks on page `index`
+    pub fn page(&self, index: usize) -> &[Block] {
+        self.pages.get(index).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// A kid of a structure element
+enum Kid<'a> {
+    /// Marked content on a page
+    Marked(Option<usize>, i64),
+    Element(&'a Dictionary),
+}
+
+struct Walker<'a> {
+    document: &'a Document,
+    pages: HashMap<ObjectId, usize>,
+    role_map: Option<&'a Dictionary>,
+    blocks: Vec<Block>,
+}
+
+impl<'a> Walker<'a> {
+    /// Standard structure type of an element, through the role map
+    fn kind(&self, element: &Dictionary) -> Vec<u8> {
+        let mut kind = element.get(b"S").and_then(Object::as_name).unwrap_or_default().to_vec();
+        let mut seen = HashSet::new();
+        while !is_standard(&kind) && seen.insert(kind.clone()) {
+            match self.role_map.and_then(|map| map.get(&kind).and_then(Object::as_name).ok()) {
+                Some(mapped) => kind = mapped.to_vec(),
+                None => break,
+            }
+        }
+        kind
+    }
+
+    fn page_of(&self, dict: &Dictionary) -> Option<usize> {
+        let id = dict.get(b"Pg").and_then(Object::as_reference).ok()?;
+        self.pages.get(&id).copied()
+    }
+
+    /// Marked content and child elements of an element; object references (annotations)
+    /// and content in form XObjects are left out
+    fn kids(&self, element: &'a Dictionary, page: Option<usize>) -> Vec<Kid<'a>> {
+        let Ok(k) = element.get(b"K") else {
+            return Vec::new();
+        };
+        let items = match self.document.dereference(k) {
+            Ok((_, Object::Array(items))) => items.iter().collect(),
+            _ => vec![k],
+        };
+        items
+            .into_iter()
+            .filter_map(|item| match self.document.dereference(item).ok()?.1 {
+                Object::Integer(mcid) => Some(Kid::Marked(page, *mcid)),
+                Object::Dictionary(dict) => match dict.get(b"Type").and_then(Object::as_name) {
+                    Ok(b"MCR") if !dict.has(b"Stm") => {
+                        let mcid = dict.get(b"MCID").and_then(Object::as_i64).ok()?;
+                        Some(Kid::Marked(self.page_of(dict).or(page), mcid))
+                    }
+                    Ok(b"MCR") | Ok(b"OBJR") => None,
+                    _ => Some(Kid::Element(dict)),
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All marked content of an element and its descendants, optionally leaving out nested lists
+    fn content(&self, element: &'a Dictionary, page: Option<usize>, nested_lists: bool, depth: usize) -> Content {
+        let page = self.page_of(element).or(page);
+        let mut content = Content::new();
+        if depth > MAX_DEPTH {
+            return content;
+        }
+        for kid in self.kids(element, page) {
+            match kid {
+                Kid::Marked(Some(page), mcid) => content.push((page, mcid)),
+                Kid::Marked(None, _) => {}
+                Kid::Element(kid) => {
+                    if nested_lists || self.kind(kid) != b"L" {
+                        content.extend(self.content(kid, page, nested_lists, depth + 1));
+                    }
+                }
+            }
+        }
+        content
+    }
+
+    /// Walks the kids of a grouping element. Content between block-level kids forms paragraphs.
+    fn group(&mut self, element: &'a Dictionary, page: Option<usize>, sections: usize, depth: usize) {
+        let page = self.page_of(element).or(page);
+        let mut pending = Content::new();
+        for kid in self.kids(element, page) {
+            match kid {
+                Kid::Marked(Some(page), mcid) => pending.push((page, mcid)),
+                Kid::Marked(None, _) => {}
+                Kid::Element(kid) if INLINE.contains(&self.kind(kid).as_slice()) => {
+                    pending.extend(self.content(kid, page, true, depth + 1));
+                }
+                Kid::Element(kid) => {
+                    if !pending.is_empty() {
+                        self.blocks.push(Block::Paragraph(std::mem::take(&mut pending)));
+                    }
+                    self.walk(kid, page, sections, depth + 1);
+                }
+            }
+        }
+        if !pending.is_empty() {
+            self.blocks.push(Block::Paragraph(pending));
+        }
+    }
+
+    fn walk(&mut self, element: &'a Dictionary, page: Option<usize>, sections: usize, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let page = self.page_of(element).or(page);
+        let kind = self.kind(element);
+        let block = match kind.as_slice() {
+            [b'H', level @ b'1'..=b'6'] => Block::Heading((level - b'0') as usize, self.content(element, page, true, depth)),
+            // headings of the section they are in
+            b"H" => Block::Heading(sections.clamp(1, 6), self.content(element, page, true, depth)),
+            b"Title" => Block::Heading(1, self.content(element, page, true, depth)),
+            b"P" | b"Caption" | b"TOCI" => Block::Paragraph(self.content(element, page, true, depth)),
+            b"BlockQuote" => Block::Quote(self.content(element, page, true, depth)),
+            b"Figure" => Block::Figure {
+                alt: element
+                    .get(b"Alt")
+                    .and_then(|o| self.document.dereference(o))
+                    .and_then(|(_, o)| o.as_str())
+                    .map(decode_text_string)
+                    .ok()
+                    .filter(|alt| !alt.trim().is_empty()),
+                content: self.content(element, page, true, depth),
+                page,
+            },
+            b"L" => return self.list(element, page, 0, depth),
+            b"Table" => return self.table(element, page, depth),
+            b"Artifact" => return,
+            b"Sect" => return self.group(element, page, sections + 1, depth),
+            _ => return self.group(element, page, sections, depth),
+        };
+        self.blocks.push(block);
+    }
+
+    fn list(&mut self, list: &'a Dictionary, page: Option<usize>, level: usize, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let page = self.page_of(list).or(page);
+        let numbered = list_numbering(self.document, list).is_some_and(|numbering| !BULLETS.contains(&numbering));
+        let mut number = 0;
+        for kid in self.kids(list, page) {
+            let Kid::Element(item) = kid else {
+                continue;
+            };
+            match self.kind(item).as_slice() {
+                b"L" => self.list(item, page, level + 1, depth + 1),
+                b"Caption" => self.blocks.push(Block::Paragraph(self.content(item, page, true, depth + 1))),
+                _ => {
+                    number += 1;
+                    let item_page = self.page_of(item).or(page);
+                    let mut label = Content::new();
+                    let mut body = Content::new();
+                    let mut nested = Vec::new();
+                    for part in self.kids(item, item_page) {
+                        match part {
+                            Kid::Marked(Some(page), mcid) => body.push((page, mcid)),
+                            Kid::Marked(None, _) => {}
+                            Kid::Element(part) => match self.kind(part).as_slice() {
+                                b"Lbl" => label.extend(self.content(part, item_page, true, depth + 2)),
+                                b"L" => nested.push(part),
+                                _ => {
+                                    body.extend(self.content(part, item_page, false, depth + 2));
+                                    // lists nested in the body of the item
+                                    nested.extend(self.kids(part, item_page).into_iter().filter_map(|k| match k {
+                                        Kid::Element(k) if self.kind(k) == b"L" => Some(k),
+                                        _ => None,
+                                    }));
+                                }
+                            },
+                        }
+                    }
+                    self.blocks.push(Block::ListItem {
+                        depth: level,
+                        number: numbered.then_some(number),
+                        label,
+                        body,
+                    });
+                    for nested in nested {
+                        self.list(nested, item_page, level + 1, depth + 2);
+                    }
+                }
+            }
+        }
+    }
+
+    fn table(&mut self, table: &'a Dictionary, page: Option<usize>, depth: usize) {
+        let page = self.page_of(table).or(page);
+        let mut rows = Vec::new();
+        let mut captions = Vec::new();
+        self.table_rows(table, page, false, &mut rows, &mut captions, depth);
+        self.blocks.extend(captions.into_iter().map(Block::Paragraph));
+        if !rows.is_empty() {
+            self.blocks.push(Block::Table(rows));
+        }
+    }
+
+    fn table_rows(
+        &self,
+        element: &'a Dictionary,
+        page: Option<usize>,
+        header: bool,
+        rows: &mut Vec<TableRow>,
+        captions: &mut Vec<Content>,
+        depth: usize,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        for kid in self.kids(element, page) {
+            let Kid::Element(kid) = kid else {
+                continue;
+            };
+            let kid_page = self.page_of(kid).or(page);
+            match self.kind(kid).as_slice() {
+                b"TR" => {
+                    let mut all_th = true;
+                    let mut cells = Vec::new();
+                    for cell in self.kids(kid, kid_page) {
+                        if let Kid::Element(cell) = cell {
+                            all_th &= self.kind(cell) == b"TH";
+                            cells.push(self.content(cell, kid_page, true, depth + 2));
+                        }
+                    }
+                    if !cells.is_empty() {
+                        rows.push(TableRow { header: header || all_th, cells });
+                    }
+                }
+                b"Caption" => captions.push(self.content(kid, kid_page, true, depth + 1)),
+                kind => self.table_rows(kid, kid_page, header || kind == b"THead", rows, captions, depth + 1),
+            }
+        }
+    }
+}
+
+fn is_standard(kind: &[u8]) -> bool {
+    GROUPING.contains(&kind) || INLINE.contains(&kind) || BLOCK.contains(&kind)
+}
+
+/// `/ListNumbering` from the list attributes of an `L` element
+fn list_numbering<'a>(document: &'a Document, list: &'a Dictionary) -> Option<&'a [u8]> {
+    let (_, attributes) = document.dereference(list.get(b"A").ok()?).ok()?;
+    let attributes = match attributes {
+        Object::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    attributes.into_iter().find_map(|attribute| {
+        let (_, attribute) = document.dereference(attribute).ok()?;
+        attribute.as_dict().ok()?.get(b"ListNumbering").and_then(Object::as_name).ok()
+    })
+}
+
+/// Markdown of a tagged page: its blocks filled in with the runs and images of their
+/// marked content. Units outside the structure tree are returned for the layout
+/// heuristics, all of them if none are in it; artifacts are dropped unless `keep_artifacts`.
+pub fn tagged_page_to_md(
+    units: Vec<PdfUnit>,
+    blocks: &[Block],
+    median_font_size: Option<f32>,
+    keep_artifacts: bool,
+) -> (String, Vec<PdfUnit>) {
+    let tagged: HashSet<i64> = blocks.iter().flat_map(Block::mcids).collect();
+    let mcid = |unit: &PdfUnit| match unit {
+        PdfUnit::Text(pdf_text) => pdf_text.marked.mcid,
+        PdfUnit::Image(pdf_image) => pdf_image.marked.mcid,
+        PdfUnit::Line(_) => None,
+    };
+    if !units.iter().any(|unit| mcid(unit).is_some_and(|mcid| tagged.contains(&mcid))) {
+        // the content stream doesn't carry the tree's marks: lay the page out as untagged
+        return (String::new(), units);
+    }
+    let mut marked: HashMap<i64, Vec<PdfUnit>> = HashMap::new();
+    let mut rest = Vec::new();
+    for unit in units {
+        let marks = match &unit {
+            PdfUnit::Text(pdf_text) => pdf_text.marked,
+            PdfUnit::Image(pdf_image) => pdf_image.marked,
+            // tables come from the structure, not from ruling lines
+            PdfUnit::Line(_) => continue,
+        };
+        match marks.mcid.filter(|mcid| tagged.contains(mcid)) {
+            Some(mcid) => marked.entry(mcid).or_default().push(unit),
+            None if marks.artifact && !keep_artifacts => {}
+            None => rest.push(unit),
+        }
+    }
+    // the runs and images of some content, each used once
+    let mut take = |content: &Content| -> (Vec<PdfText>, Vec<PdfImage>) {
+        let mut texts = Vec::new();
+        let mut images = Vec::new();
+        for unit in content.iter().flat_map(|(_, mcid)| marked.remove(mcid).unwrap_or_default()) {
+            match unit {
+                PdfUnit::Text(pdf_text) => texts.push(pdf_text),
+                PdfUnit::Image(pdf_image) => images.push(pdf_image),
+                PdfUnit::Line(_) => {}
+            }
+        }
+        (texts, images)
+    };
+
+    let mut markdown = String::new();
+    let mut in_list = false;
+    for block in blocks {
+        let is_list_item = matches!(block, Block::ListItem { .. });
+        if in_list && !is_list_item {
+            markdown.push('\n');
+        }
+        in_list = is_list_item;
+
+        let mut images = Vec::new();
+        match block {
+            Block::Heading(level, content) => {
+                let (texts, block_images) = take(content);
+                images = block_images;
+                // headings carry their level in the structure, bold is redundant there
+                let text = strip_bold(&block_text(texts, median_font_size)).to_string();
+                if !text.is_empty() {
+                    markdown.push_str(&format!("{} {}\n\n", "#".repeat(*level), text));
+                }
+            }
+            Block::Paragraph(content) => {
+                let (texts, block_images) = take(content);
+                images = block_images;
+                let text = block_text(texts, median_font_size);
+                if !text.is_empty() {
+                    markdown.push_str(&format!("{}\n\n", text));
+                }
+            }
+            Block::Quote(content) => {
+                let (texts, block_images) = take(content);
+                images = block_images;
+                let text = block_text(texts, median_font_size);
+                if !text.is_empty() {
+                    markdown.push_str(&format!("> {}\n\n", text));
+                }
+            }
+            Block::ListItem { depth, number, label, body } => {
+                let label = block_text(take(label).0, median_font_size);
+                let (texts, block_images) = take(body);
+                images = block_images;
+                let text = block_text(texts, median_font_size);
+                markdown.push_str(&format!("{}{}\n", "  ".repeat(*depth), list_item(&label, *number, &text)));
+            }
+            Block::Table(rows) => {
+                let mut cells: Vec<Vec<String>> = rows
+                    .iter()
+                    .map(|row| row.cells.iter().map(|cell| block_text(take(cell).0, median_font_size)).collect())
+                    .collect();
+                let columns = cells.iter().map(Vec::len).max().unwrap_or_default();
+                for row in cells.iter_mut() {
+                    row.resize(columns, String::new());
+                }
+                // Markdown tables need a header row: the first one tagged as such, else the first
+                let header = rows.iter().position(|row| row.header).unwrap_or_default();
+                if header < cells.len() {
+                    let headers = cells.remove(header);
+                    markdown.push_str(&to_markdown_table(&headers, &cells));
+                    markdown.push('\n');
+                }
+            }
+            Block::Figure { alt, content, .. } => {
+                let (texts, figure_images) = take(content);
+                if figure_images.is_empty() {
+                    // a vector drawing: its description, else the text drawn in it
+                    match alt {
+                        Some(alt) => markdown.push_str(&format!("*{}*\n\n", alt.trim())),
+                        None => {
+                            let text = block_text(texts, median_font_size);
+                            if !text.is_empty() {
+                                markdown.push_str(&format!("{}\n\n", text));
+                            }
+                        }
+                    }
+                }
+                for image in figure_images {
+                    if let Ok(image_md) = image_to_md(&image) {
+                        markdown.push_str(&format!("{}\n\n", with_alt_text(image_md, alt.as_deref())));
+                    }
+                }
+            }
+        }
+        for image in images {
+            if let Ok(image_md) = image_to_md(&image) {
+                markdown.push_str(&format!("{}\n\n", image_md));
+            }
+        }
+    }
+    if in_list {
+        markdown.push('\n');
+    }
+
+    (markdown, rest)
+}
+
+/// The runs of one block joined into a paragraph of inline Markdown
+fn block_text(texts: Vec<PdfText>, median_font_size: Option<f32>) -> String {
+    // runs on one baseline form a line
+    let mut rows: Vec<Vec<PdfText>> = Vec::new();
+    for pdf_text in texts {
+        match rows.last_mut() {
+            Some(row) if (row[0].y - pdf_text.y).abs() <= row[0].height.max(1.0) * 0.5 => row.push(pdf_text),
+            _ => rows.push(vec![pdf_text]),
+        }
+    }
+    let lines: Vec<TextLine> = rows
+        .into_iter()
+        .map(|mut row| {
+            row.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
+            text_line(row, median_font_size)
+        })
+        .filter(|line| !line.markdown.is_empty())
+        .collect();
+    paragraph::join_lines(lines)
+}
+
+/// A Markdown list item. A label like "1." is the marker; other labels with letters
+/// ("a)", "iv.") stay in the text of a bulleted item, and bullet glyphs give way to the
+/// position in a numbered list or to "-".
+fn list_item(label: &str, number: Option<usize>, text: &str) -> String {
+    let label = label.trim();
+    // bullets some producers tag as part of the body
+    let text = match label.is_empty() {
+        true => text.trim_start_matches(is_bullet).trim_start(),
+        false => text,
+    };
+    let digits = label.trim_end_matches(['.', ')']);
+    if label.len() > digits.len() && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        return format!("{} {}", label, text);
+    }
+    if label.chars().any(char::is_alphanumeric) {
+        return format!("- {} {}", label, text);
+    }
+    match number {
+        Some(number) => format!("{}. {}", number, text),
+        None => format!("- {}", text),
+    }
+}
+
+/// Bullet glyphs, including the private-use ones of symbol fonts
+fn is_bullet(c: char) -> bool {
+    matches!(c, '•' | '◦' | '▪' | '‣' | '·' | '–' | '-' | '*' | '\u{E000}'..='\u{F8FF}')
+}
+
+/// Replaces the alt text image2md gave an image with the figure's `/Alt`
+fn with_alt_text(image_md: String, alt: Option<&str>) -> String {
+    match (alt, image_md.find("](")) {
+        (Some(alt), Some(end)) if image_md.starts_with("![") => {
+            format!("![{}]{}", alt.replace(['[', ']'], "").trim(), &image_md[end + 1..])
+        }
+        _ => image_md,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pdf2md::pdf_element::MarkedContent;
+    use lopdf::dictionary;
+
+    /// A structure element of type `kind` with kids `k`
+    fn element(doc: &mut Document, kind: &str, k: impl Into<Object>) -> Object {
+        doc.add_object(dictionary! { "Type" => "StructElem", "S" => kind, "K" => k.into() }).into()
+    }
+
+    /// A two-page document whose structure tree covers the kinds of blocks, page 1 first
+    fn tagged_document() -> Document {
+        let mut doc = Document::with_version("1.7");
+        let pages_id = doc.new_object_id();
+        let page_ids: Vec<ObjectId> = (0..2)
+            .map(|_| doc.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id }))
+            .collect();
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => page_ids.iter().map(|id| Object::from(*id)).collect::<Vec<_>>(),
+                "Count" => 2,
+            }),
+        );
+
+        let heading = element(&mut doc, "H1", 0);
+        let span = element(&mut doc, "Span", 2);
+        let paragraph = element(&mut doc, "P", vec![Object::from(1), span]);
+        let label = element(&mut doc, "Lbl", 3);
+        let body = element(&mut doc, "LBody", 4);
+        let item = element(&mut doc, "LI", vec![label, body]);
+        let list = doc.add_object(dictionary! {
+            "Type" => "StructElem",
+            "S" => "L",
+            "A" => dictionary! { "O" => "List", "ListNumbering" => "Decimal" },
+            "K" => vec![item],
+        });
+        let header_cells = vec![element(&mut doc, "TH", 5), element(&mut doc, "TH", 6)];
+        let header_row = element(&mut doc, "TR", header_cells);
+        let head = element(&mut doc, "THead", vec![header_row]);
+        let body_cells = vec![element(&mut doc, "TD", 7), element(&mut doc, "TD", 8)];
+        let body_row = element(&mut doc, "TR", body_cells);
+        let table = element(&mut doc, "Table", vec![head, body_row]);
+        let custom = element(&mut doc, "Subheading", 9);
+        let figure = doc.add_object(dictionary! {
+            "Type" => "StructElem",
+            "S" => "Figure",
+            "Alt" => Object::string_literal("A chart"),
+            "K" => 10,
+        });
+        let artifact = element(&mut doc, "Artifact", 12);
+        let next_page = dictionary! { "Type" => "MCR", "Pg" => page_ids[1], "MCID" => 0 };
+        let continued = element(&mut doc, "P", vec![Object::from(11), next_page.into()]);
+
+        let kids = vec![heading, paragraph, list.into(), table, custom, figure.into(), artifact, continued];
+        let document = doc.add_object(dictionary! {
+            "Type" => "StructElem",
+            "S" => "Document",
+            "Pg" => page_ids[0],
+            "K" => kids,
+        });
+        let root = doc.add_object(dictionary! {
+            "Type" => "StructTreeRoot",
+            "K" => document,
+            "RoleMap" => dictionary! { "Subheading" => "H2" },
+        });
+        let catalog = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+            "StructTreeRoot" => root,
+        });
+        doc.trailer.set("Root", catalog);
+        doc
+    }
+
+    fn text(text: &str, x: f32, y: f32, mcid: Option<i64>) -> PdfUnit {
+        PdfUnit::Text(PdfText {
+            text: text.to_string(),
+            x,
+            y,
+            width: 5.0 * text.chars().count() as f32,
+            height: 10.0,
+            font_size: Some(10.0),
+            space_width: 2.5,
+            marked: MarkedContent { mcid, artifact: false },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_structure_tree_blocks_per_page() {
+        let tree = StructTree::read(&tagged_document()).unwrap();
+        let blocks: Vec<String> = tree.page(0).iter().map(|block| format!("{:?}", block)).collect();
+        assert_eq!(
+            blocks,
+            vec![
+                "Heading(1, [(0, 0)])",
+                "Paragraph([(0, 1), (0, 2)])",
+                "ListItem { depth: 0, number: Some(1), label: [(0, 3)], body: [(0, 4)] }",
+                concat!(
+                    "Table([TableRow { header: true, cells: [[(0, 5)], [(0, 6)]] }, ",
+                    "TableRow { header: false, cells: [[(0, 7)], [(0, 8)]] }])"
+                ),
+                "Heading(2, [(0, 9)])",
+                "Figure { alt: Some(\"A chart\"), content: [(0, 10)], page: Some(0) }",
+                "Paragraph([(0, 11)])",
+            ]
+        );
+        // the paragraph running on to the next page through a marked-content reference
+        let blocks: Vec<String> = tree.page(1).iter().map(|block| format!("{:?}", block)).collect();
+        assert_eq!(blocks, vec!["Paragraph([(1, 0)])"]);
+        assert!(tree.page(2).is_empty());
+    }
+
+    #[test]
+    fn test_untagged_document_has_no_tree() {
+        let mut doc = tagged_document();
+        let catalog = doc.catalog_mut().unwrap();
+        catalog.remove(b"StructTreeRoot");
+        assert!(StructTree::read(&doc).is_none());
+    }
+
+    #[test]
+    fn test_tagged_page_fills_blocks_by_mcid() {
+        let tree = StructTree::read(&tagged_document()).unwrap();
+        let units = vec![
+            text("Overview", 72.0, 750.0, Some(0)),
+            text("Some", 72.0, 720.0, Some(1)),
+            text("text", 100.0, 720.0, Some(2)),
+            text("1.", 72.0, 700.0, Some(3)),
+            text("First", 90.0, 700.0, Some(4)),
+            text("Key", 72.0, 680.0, Some(5)),
+            text("Value", 200.0, 680.0, Some(6)),
+            text("a", 72.0, 666.0, Some(7)),
+            text("b", 200.0, 666.0, Some(8)),
+            text("Details", 72.0, 640.0, Some(9)),
+            text("Closing", 72.0, 600.0, Some(11)),
+            text("Page 1", 300.0, 40.0, None),
+            PdfUnit::Text(PdfText {
+                marked: MarkedContent { mcid: None, artifact: true },
+                ..Default::default()
+            }),
+        ];
+        let (markdown, rest) = tagged_page_to_md(units, tree.page(0), Some(10.0), false);
+        assert_eq!(
+            markdown,
+            concat!(
+                "# Overview\n\nSome text\n\n1. First\n\n",
+                "| Key | Value |\n|---|---|\n| a | b |\n\n",
+                "## Details\n\n*A chart*\n\nClosing\n\n"
+            )
+        );
+        // content outside the tree is left for the layout heuristics, artifacts are dropped
+        assert_eq!(rest.len(), 1);
+        assert!(matches!(&rest[0], PdfUnit::Text(text) if text.text == "Page 1"));
+    }
+
+    #[test]
+    fn test_page_without_tree_marks_is_laid_out_untagged() {
+        let tree = StructTree::read(&tagged_document()).unwrap();
+        let units = vec![text("Untagged", 72.0, 700.0, None), text("Stray", 72.0, 680.0, Some(42))];
+        let (markdown, rest) = tagged_page_to_md(units, tree.page(0), Some(10.0), false);
+        assert_eq!(markdown, "");
+        assert_eq!(rest.len(), 2);
+    }
+
+    #[test]
+    fn test_list_item_labels() {
+        assert_eq!(list_item("1.", Some(1), "First"), "1. First");
+        assert_eq!(list_item("•", Some(3), "Third"), "3. Third");
+        assert_eq!(list_item("•", None, "Point"), "- Point");
+        assert_eq!(list_item("a)", None, "Option"), "- a) Option");
+        assert_eq!(list_item("", None, "• Bullet in the body"), "- Bullet in the body");
+    }
+}
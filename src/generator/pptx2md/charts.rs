@@ -0,0 +1,464 @@
+use base64::Engine;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::package::Package;
+use super::{attribute, format_table_as_markdown, TableData};
+use crate::config::SETTINGS;
+
+/// One `c:ser` with its cached values, keyed by point index
+#[derive(Debug, Default)]
+struct Series {
+    name: Option<String>,
+    categories: BTreeMap<usize, String>,
+    values: BTreeMap<usize, String>,
+}
+
+#[derive(Debug, Default)]
+struct Chart {
+    title: Option<String>,
+    kinds: Vec<String>,
+    /// (axis element, title) in document order
+    axis_titles: Vec<(String, String)>,
+    series: Vec<Series>,
+}
+
+/// Renders a chart part (`ppt/charts/chartN.xml`) as its title, type, axis titles and
+/// a table of the values cached in the chart, followed by a link to the embedded workbook
+pub fn chart_to_markdown(package: &mut Package, chart_part: &str) -> Result<String, String> {
+    let Some(xml) = package.read_string(chart_part)? else {
+        return Ok(String::new());
+    };
+    let chart = parse_chart(&xml)?;
+
+    let mut markdown = String::new();
+    let kind = if chart.kinds.is_empty() {
+        "chart".to_string()
+    } else {
+        format!("{} chart", chart.kinds.join(" and "))
+    };
+    match &chart.title {
+        Some(title) => markdown.push_str(&format!("**Chart: {}** ({})\n\n", title, kind)),
+        None => markdown.push_str(&format!("**Chart** ({})\n\n", kind)),
+    }
+
+    let axes: Vec<String> = chart
+        .axis_titles
+        .iter()
+        .map(|(axis, title)| format!("{}: {}", axis_label(axis), title))
+        .collect();
+    if !axes.is_empty() {
+        markdown.push_str(&format!("{}\n\n", axes.join("; ")));
+    }
+
+    let table = chart_table(&chart);
+    if table.rows.len() > 1 {
+        markdown.push_str(&format_table_as_markdown(&table));
+        markdown.push('\n');
+    }
+
+    let relationships = package.relationships(chart_part)?;
+    let workbook = relationships
+        .values()
+        .find(|r| r.has_type("package") && !r.external)
+        .map(|r| r.target.clone());
+    let data = match &workbook {
+        Some(target) => package.read(target)?,
+        None => None,
+    };
+    if let (Some(target), Some(data)) = (workbook, data) {
+        markdown.push_str(&workbook_link(chart_part, &target, &data)?);
+        markdown.push_str("\n\n");
+    }
+
+    Ok(markdown)
+}
+
+fn parse_chart(xml: &str) -> Result<Chart, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut chart = Chart::default();
+    // names of the open elements
+    let mut open: Vec<Vec<u8>> = Vec::new();
+    let mut title = String::new();
+    let mut point: Option<usize> = None;
+    // index of the c:lvl being read in a multi-level category cache
+    let mut category_level = 0;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                match element.name().as_ref() {
+                    b"c:title" => title.clear(),
+                    b"c:ser" => chart.series.push(Series::default()),
+                    b"c:pt" => point = attribute(&element, b"idx").and_then(|i| i.parse().ok()),
+                    b"c:multiLvlStrCache" => category_level = 0,
+                    name if is_plot_element(name) => {
+                        chart.kinds.push(chart_kind(name, None));
+                    }
+                    _ => {}
+                }
+                open.push(element.name().as_ref().to_vec());
+            }
+            Ok(Event::Empty(element)) if element.name().as_ref() == b"c:barDir" => {
+                // bar charts are "column" charts unless drawn horizontally
+                let direction = attribute(&element, b"val");
+                if let Some(kind) = chart.kinds.last_mut() {
+                    *kind = chart_kind(b"c:barChart", direction.as_deref());
+                }
+            }
+            Ok(Event::Text(text)) => {
+                let text = text.unescape().unwrap_or_default();
+                let current = open.last().map(|n| n.as_slice());
+                if open.iter().any(|n| n == b"c:title") {
+                    // rich text, or the cached value of a c:strRef title
+                    if current == Some(b"a:t") || current == Some(b"c:v") {
+                        title.push_str(&text);
+                    }
+                } else if current == Some(b"c:v") {
+                    store_value(&mut chart, &open, point, category_level, text.trim());
+                }
+            }
+            Ok(Event::End(element)) => {
+                open.pop();
+                match element.name().as_ref() {
+                    b"c:title" => {
+                        let text = title.trim().to_string();
+                        let owner = open.last().map(|n| n.as_slice());
+                        match owner {
+                            _ if text.is_empty() => {}
+                            Some(b"c:chart") => chart.title = Some(text),
+                            Some(axis) if axis.ends_with(b"Ax") => {
+                                let axis = String::from_utf8_lossy(axis).to_string();
+                                chart.axis_titles.push((axis, text));
+                            }
+                            _ => {}
+                        }
+                    }
+                    b"c:pt" => point = None,
+                    b"c:lvl" => category_level += 1,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing chart XML: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(chart)
+}
+
+/// Files a cached `c:v` under the series name, categories or values it belongs to
+fn store_value(
+    chart: &mut Chart,
+    open: &[Vec<u8>],
+    point: Option<usize>,
+    category_level: usize,
+    text: &str,
+) {
+    let Some(series) = chart.series.last_mut() else {
+        return;
+    };
+    let within = |name: &[u8]| open.iter().any(|n| n == name);
+
+    if within(b"c:tx") {
+        series.name = Some(text.to_string());
+    } else if within(b"c:cat") || within(b"c:xVal") {
+        // multi-level categories: keep the innermost level only
+        if let (Some(point), 0) = (point, category_level) {
+            series.categories.insert(point, text.to_string());
+        }
+    } else if let Some(point) = point.filter(|_| within(b"c:val") || within(b"c:yVal")) {
+        series.values.insert(point, text.to_string());
+    }
+}
+
+/// Categories down the first column, one column per series
+fn chart_table(chart: &Chart) -> TableData {
+    let category_axis = chart
+        .axis_titles
+        .iter()
+        .find(|(axis, _)| axis == "c:catAx" || axis == "c:dateAx")
+        .map(|(_, title)| title.clone());
+    let categories = chart
+        .series
+        .iter()
+        .map(|s| &s.categories)
+        .find(|c| !c.is_empty());
+
+    let mut points: Vec<usize> = chart
+        .series
+        .iter()
+        .flat_map(|s| s.values.keys().copied())
+        .chain(categories.into_iter().flat_map(|c| c.keys().copied()))
+        .collect();
+    points.sort_unstable();
+    points.dedup();
+
+    let mut header = vec![category_axis.unwrap_or_else(|| "Category".to_string())];
+    header.extend(
+        chart
+            .series
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s.name.clone().unwrap_or_else(|| format!("Series {}", i + 1))),
+    );
+
+    let mut rows = vec![header];
+    for point in points {
+        let category = categories
+            .and_then(|c| c.get(&point).cloned())
+            .unwrap_or_else(|| (point + 1).to_string());
+        let mut row = vec![category];
+        row.extend(
+            chart
+                .series
+                .iter()
+                .map(|s| s.values.get(&point).cloned().unwrap_or_default()),
+        );
+        rows.push(row);
+    }
+
+    TableData {
+        rows,
+        has_header: true,
+    }
+}
+
+fn is_plot_element(name: &[u8]) -> bool {
+    name.starts_with(b"c:") && name.ends_with(b"Chart")
+}
+
+fn chart_kind(name: &[u8], bar_direction: Option<&str>) -> String {
+    let kind = match name {
+        b"c:barChart" | b"c:bar3DChart" => match bar_direction {
+            Some("bar") => "bar",
+            _ => "column",
+        },
+        b"c:lineChart" | b"c:line3DChart" => "line",
+        b"c:pieChart" | b"c:pie3DChart" | b"c:ofPieChart" => "pie",
+        b"c:doughnutChart" => "doughnut",
+        b"c:areaChart" | b"c:area3DChart" => "area",
+        b"c:scatterChart" => "scatter",
+        b"c:bubbleChart" => "bubble",
+        b"c:radarChart" => "radar",
+        b"c:stockChart" => "stock",
+        b"c:surfaceChart" | b"c:surface3DChart" => "surface",
+        _ => "other",
+    };
+    kind.to_string()
+}
+
+fn axis_label(axis: &str) -> &str {
+    match axis {
+        "c:catAx" | "c:dateAx" => "Category axis",
+        "c:valAx" => "Value axis",
+        "c:serAx" => "Series axis",
+        _ => "Axis",
+    }
+}
+
+/// Link to the chart's embedded workbook, saved next to the images when an
+/// image path is configured and inlined as a data URI otherwise
+fn workbook_link(chart_part: &str, workbook_part: &str, data: &[u8]) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    let chart_name = Path::new(chart_part)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let workbook_name = Path::new(workbook_part)
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let filename = format!("{}-{}", chart_name, workbook_name);
+
+    if cfg.image_path.as_os_str().is_empty() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+        return Ok(format!(
+            "[Chart data ({})](data:{};base64,{})",
+            workbook_name,
+            workbook_mime_type(workbook_part),
+            encoded
+        ));
+    }
+
+    fs::create_dir_all(&cfg.image_path)
+        .map_err(|e| format!("Failed to create image directory: {}", e))?;
+    let file_path = cfg.image_path.join(&filename);
+    fs::write(&file_path, data).map_err(|e| format!("Failed to save chart workbook: {}", e))?;
+
+    // Relative to the output file when there is one, like image references
+    let output_dir = cfg
+        .output_path
+        .as_ref()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(|p| p.parent().unwrap_or(Path::new(".")).to_path_buf());
+    let link = match output_dir.and_then(|dir| file_path.strip_prefix(&dir).ok().map(Path::to_path_buf)) {
+        Some(relative) => relative,
+        None => file_path.canonicalize().unwrap_or(file_path),
+    };
+
+    Ok(format!("[Chart data ({})]({})", workbook_name, link.to_string_lossy()))
+}
+
+fn workbook_mime_type(workbook_part: &str) -> &'static str {
+    if workbook_part.ends_with(".xlsx") {
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    } else if workbook_part.ends_with(".xls") {
+        "application/vnd.ms-excel"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pptx2md::package::tests::archive;
+
+    /// A `c:ser` with a literal name, string categories and numeric values
+    fn series(name: &str, categories: &[&str], values: &[&str]) -> String {
+        let points = |items: &[&str]| {
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| format!(r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, i, v))
+                .collect::<String>()
+        };
+        format!(
+            concat!(
+                r#"<c:ser><c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:pt idx="0"><c:v>{}</c:v></c:pt>"#,
+                r#"</c:strCache></c:strRef></c:tx><c:cat><c:strRef><c:strCache>{}</c:strCache></c:strRef></c:cat>"#,
+                r#"<c:val><c:numRef><c:numCache>{}</c:numCache></c:numRef></c:val></c:ser>"#
+            ),
+            name,
+            points(categories),
+            points(values)
+        )
+    }
+
+    fn chart_space(plot_area: &str, title: &str) -> String {
+        format!(
+            r#"<c:chartSpace xmlns:c="c" xmlns:a="a"><c:chart>{}<c:plotArea>{}</c:plotArea></c:chart></c:chartSpace>"#,
+            title, plot_area
+        )
+    }
+
+    #[test]
+    fn test_rich_text_title() {
+        let title = r#"<c:title><c:tx><c:rich><a:p><a:r><a:t>Sales </a:t></a:r><a:r><a:t>2024</a:t></a:r>
+            </a:p></c:rich></c:tx></c:title>"#;
+        let chart = parse_chart(&chart_space("", title)).unwrap();
+        assert_eq!(chart.title.as_deref(), Some("Sales 2024"));
+    }
+
+    #[test]
+    fn test_string_reference_title() {
+        let title = r#"<c:title><c:tx><c:strRef><c:f>Sheet1!$A$1</c:f><c:strCache><c:ptCount val="1"/>
+            <c:pt idx="0"><c:v>Revenue</c:v></c:pt></c:strCache></c:strRef></c:tx></c:title>"#;
+        let chart = parse_chart(&chart_space(&series("East", &["Q1"], &["1"]), title)).unwrap();
+        assert_eq!(chart.title.as_deref(), Some("Revenue"));
+        assert_eq!(chart.series[0].name.as_deref(), Some("East"));
+    }
+
+    #[test]
+    fn test_series_kinds_and_axis_titles() {
+        let plot_area = format!(
+            concat!(
+                r#"<c:barChart><c:barDir val="bar"/>{}{}</c:barChart><c:lineChart>{}</c:lineChart>"#,
+                r#"<c:catAx><c:title><c:tx><c:rich><a:p><a:r><a:t>Quarter</a:t></a:r></a:p></c:rich></c:tx>"#,
+                r#"</c:title></c:catAx><c:valAx><c:title><c:tx><c:rich><a:p><a:r><a:t>EUR</a:t></a:r></a:p>"#,
+                r#"</c:rich></c:tx></c:title></c:valAx>"#
+            ),
+            series("East", &["Q1", "Q2"], &["1", "2"]),
+            series("West", &["Q1", "Q2"], &["3", "4"]),
+            series("Trend", &["Q1", "Q2"], &["2", "3"]),
+        );
+        let chart = parse_chart(&chart_space(&plot_area, "")).unwrap();
+        assert_eq!(chart.title, None);
+        assert_eq!(chart.kinds, vec!["bar", "line"]);
+        assert_eq!(
+            chart.axis_titles,
+            vec![
+                ("c:catAx".to_string(), "Quarter".to_string()),
+                ("c:valAx".to_string(), "EUR".to_string())
+            ]
+        );
+
+        let table = chart_table(&chart);
+        assert!(table.has_header);
+        assert_eq!(
+            table.rows,
+            vec![
+                vec!["Quarter", "East", "West", "Trend"],
+                vec!["Q1", "1", "3", "2"],
+                vec!["Q2", "2", "4", "3"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_fills_missing_names_and_points() {
+        let plot_area = concat!(
+            r#"<c:scatterChart><c:ser><c:yVal><c:numRef><c:numCache>"#,
+            r#"<c:pt idx="0"><c:v>5</c:v></c:pt><c:pt idx="2"><c:v>7</c:v></c:pt>"#,
+            r#"</c:numCache></c:numRef></c:yVal></c:ser></c:scatterChart>"#
+        );
+        let chart = parse_chart(&chart_space(plot_area, "")).unwrap();
+        assert_eq!(chart.kinds, vec!["scatter"]);
+        assert_eq!(
+            chart_table(&chart).rows,
+            vec![vec!["Category", "Series 1"], vec!["1", "5"], vec!["3", "7"]]
+        );
+    }
+
+    #[test]
+    fn test_multi_level_categories_keep_innermost_level() {
+        let plot_area = concat!(
+            r#"<c:barChart><c:ser><c:cat><c:multiLvlStrRef><c:multiLvlStrCache>"#,
+            r#"<c:lvl><c:pt idx="0"><c:v>Jan</c:v></c:pt><c:pt idx="1"><c:v>Feb</c:v></c:pt></c:lvl>"#,
+            r#"<c:lvl><c:pt idx="0"><c:v>2024</c:v></c:pt></c:lvl>"#,
+            r#"</c:multiLvlStrCache></c:multiLvlStrRef></c:cat><c:val><c:numRef><c:numCache>"#,
+            r#"<c:pt idx="0"><c:v>1</c:v></c:pt><c:pt idx="1"><c:v>2</c:v></c:pt>"#,
+            r#"</c:numCache></c:numRef></c:val></c:ser></c:barChart>"#
+        );
+        let chart = parse_chart(&chart_space(plot_area, "")).unwrap();
+        assert_eq!(chart.kinds, vec!["column"]);
+        let categories: Vec<&String> = chart.series[0].categories.values().collect();
+        assert_eq!(categories, vec!["Jan", "Feb"]);
+    }
+
+    #[test]
+    fn test_chart_to_markdown_links_workbook() {
+        let title = r#"<c:title><c:tx><c:rich><a:p><a:r><a:t>Sales</a:t></a:r></a:p></c:rich></c:tx></c:title>"#;
+        let xml = chart_space(
+            &format!("<c:pieChart>{}</c:pieChart>", series("2024", &["A", "B"], &["60", "40"])),
+            title,
+        );
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Target="../embeddings/Workbook1.xlsx"
+            Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/package"/></Relationships>"#;
+        let data = archive(&[
+            ("ppt/charts/chart1.xml", &xml),
+            ("ppt/charts/_rels/chart1.xml.rels", rels),
+            ("ppt/embeddings/Workbook1.xlsx", "PK"),
+        ]);
+        let mut package = Package::open(&data).unwrap();
+        let markdown = chart_to_markdown(&mut package, "ppt/charts/chart1.xml").unwrap();
+
+        assert!(markdown.starts_with("**Chart: Sales** (pie chart)\n\n"), "{}", markdown);
+        assert!(markdown.contains("| A | 60 |"), "{}", markdown);
+        assert!(
+            markdown.contains(concat!(
+                "[Chart data (Workbook1.xlsx)](data:application/vnd.openxmlformats-officedocument",
+                ".spreadsheetml.sheet;base64,UEs=)"
+            )),
+            "{}",
+            markdown
+        );
+        assert_eq!(chart_to_markdown(&mut package, "ppt/charts/chart2.xml").unwrap(), "");
+    }
+}
@@ -0,0 +1,182 @@
+use quick_xml::events::BytesStart;
+
+use super::attribute;
+
+/// Shapes whose tops are within a quarter inch (in EMU) are read as one row, left to right
+const ROW_TOLERANCE: i64 = 228_600;
+
+/// The child coordinate space of a group shape (`p:grpSp`, or the slide's `p:spTree`),
+/// from its `a:xfrm`: `a:off`/`a:ext` place the group on its parent and
+/// `a:chOff`/`a:chExt` are the extent its children are laid out in
+#[derive(Debug, Clone, Default)]
+pub struct GroupTransform {
+    off: (i64, i64),
+    ext: (i64, i64),
+    child_off: (i64, i64),
+    child_ext: (i64, i64),
+}
+
+impl GroupTransform {
+    /// Records one of the group's `a:off`, `a:ext`, `a:chOff` or `a:chExt` elements
+    pub fn set(&mut self, element: &BytesStart) {
+        match element.name().as_ref() {
+            b"a:off" => self.off = point(element, b"x", b"y"),
+            b"a:ext" => self.ext = point(element, b"cx", b"cy"),
+            b"a:chOff" => self.child_off = point(element, b"x", b"y"),
+            b"a:chExt" => self.child_ext = point(element, b"cx", b"cy"),
+            _ => {}
+        }
+    }
+
+    /// Maps a point from the group's child space to its parent's space
+    fn apply(&self, (x, y): (i64, i64)) -> (i64, i64) {
+        let scale = |value: i64, child_off: i64, child_ext: i64, off: i64, ext: i64| {
+            if child_ext == 0 {
+                off + value - child_off
+            } else {
+                off + ((value - child_off) as f64 * ext as f64 / child_ext as f64) as i64
+            }
+        };
+        (
+            scale(x, self.child_off.0, self.child_ext.0, self.off.0, self.ext.0),
+            scale(y, self.child_off.1, self.child_ext.1, self.off.1, self.ext.1),
+        )
+    }
+}
+
+/// Slide position of a shape's `a:off`, applying the enclosing groups innermost first
+pub fn slide_position(element: &BytesStart, groups: &[GroupTransform]) -> (i64, i64) {
+    groups
+        .iter()
+        .rev()
+        .fold(point(element, b"x", b"y"), |position, group| group.apply(position))
+}
+
+/// Offset of a `a:off` element, in EMU
+pub fn point(element: &BytesStart, x: &[u8], y: &[u8]) -> (i64, i64) {
+    let coordinate = |name| {
+        attribute(element, name)
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(0)
+    };
+    (coordinate(x), coordinate(y))
+}
+
+/// The Markdown of one shape and where it sits on the slide
+#[derive(Debug, Default)]
+pub struct ShapeBlock {
+    /// Top-left corner (x, y) in EMU, `None` if neither the shape nor its placeholder has one
+    pub position: Option<(i64, i64)>,
+    pub markdown: String,
+}
+
+/// Joins shape blocks in reading order: rows top to bottom, shapes in a row left to right.
+/// A shape without a position stays after the shape preceding it in the XML.
+pub fn order_blocks(mut blocks: Vec<ShapeBlock>) -> String {
+    let mut previous = (0, 0);
+    for block in &mut blocks {
+        match block.position {
+            Some(position) => previous = position,
+            None => block.position = Some(previous),
+        }
+    }
+    blocks.retain(|block| !block.markdown.trim().is_empty());
+
+    let position = |block: &ShapeBlock| block.position.unwrap_or_default();
+    blocks.sort_by_key(|block| (position(block).1, position(block).0));
+
+    let mut rows: Vec<Vec<ShapeBlock>> = Vec::new();
+    for block in blocks {
+        let same_row = rows
+            .last()
+            .is_some_and(|row| position(&block).1 - position(&row[0]).1 <= ROW_TOLERANCE);
+        match rows.last_mut() {
+            Some(row) if same_row => row.push(block),
+            _ => rows.push(vec![block]),
+        }
+    }
+
+    let mut markdown = String::new();
+    for mut row in rows {
+        row.sort_by_key(|block| position(block).0);
+        for block in row {
+            markdown.push_str(&block.markdown);
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    /// The empty elements of `xml`, in order
+    fn elements(xml: &str) -> Vec<BytesStart<'_>> {
+        let mut reader = Reader::from_str(xml);
+        let mut elements = Vec::new();
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Empty(element) => elements.push(element),
+                Event::Eof => return elements,
+                _ => {}
+            }
+        }
+    }
+
+    fn group(xml: &str) -> GroupTransform {
+        let mut group = GroupTransform::default();
+        for element in elements(xml) {
+            group.set(&element);
+        }
+        group
+    }
+
+    fn block(position: Option<(i64, i64)>, markdown: &str) -> ShapeBlock {
+        ShapeBlock { position, markdown: markdown.to_string() }
+    }
+
+    #[test]
+    fn test_point_defaults_missing_coordinates() {
+        let offset = &elements(r#"<a:off x="914400" y="oops"/>"#)[0];
+        assert_eq!(point(offset, b"x", b"y"), (914400, 0));
+    }
+
+    #[test]
+    fn test_slide_position_applies_groups_innermost_first() {
+        let offset = &elements(r#"<a:off x="150" y="300"/>"#)[0];
+        assert_eq!(slide_position(offset, &[]), (150, 300));
+
+        // children laid out in 200x400 at (100, 200), drawn at half size at (1000, 2000)
+        let outer = group(r#"<a:off x="1000" y="2000"/><a:ext cx="100" cy="200"/><a:chOff x="100" y="200"/>
+            <a:chExt cx="200" cy="400"/>"#);
+        assert_eq!(slide_position(offset, std::slice::from_ref(&outer)), (1025, 2050));
+
+        // an unscaled group nested inside it, moved by (50, 100)
+        let inner = group(r#"<a:off x="150" y="300"/><a:chOff x="100" y="200"/>"#);
+        assert_eq!(slide_position(offset, &[outer, inner]), (1050, 2100));
+    }
+
+    #[test]
+    fn test_order_blocks_reads_rows_left_to_right() {
+        let blocks = vec![
+            block(Some((5_000_000, 1_000_000)), "right\n"),
+            block(Some((0, 3_000_000)), "bottom\n"),
+            block(Some((0, 1_100_000)), "left\n"),
+            block(Some((0, 0)), "title\n"),
+        ];
+        assert_eq!(order_blocks(blocks), "title\nleft\nright\nbottom\n");
+    }
+
+    #[test]
+    fn test_order_blocks_keeps_unplaced_shapes_after_their_predecessor() {
+        let blocks = vec![
+            block(Some((0, 2_000_000)), "second\n"),
+            block(None, "after second\n"),
+            block(Some((0, 500_000)), "first\n"),
+            block(Some((0, 0)), "  \n"),
+        ];
+        assert_eq!(order_blocks(blocks), "first\nsecond\nafter second\n");
+    }
+}
@@ -0,0 +1,150 @@
+use base64::Engine;
+use quick_xml::events::BytesStart;
+use std::collections::HashMap;
+use std::fs;
+
+use super::attribute;
+use super::package::Relationship;
+use crate::config::SETTINGS;
+use crate::generator::image2md::{unique_file_name, ImageProcessingMode};
+
+/// The image parts referenced by one `a:blip`
+#[derive(Debug, Clone)]
+pub struct Picture {
+    /// `r:embed`: the image in the package (the PNG fallback when an SVG is present)
+    pub embed: Option<String>,
+    /// `r:link`: an image outside the package
+    pub link: Option<String>,
+    /// `r:embed` of the `asvg:svgBlip` extension
+    pub svg_embed: Option<String>,
+    /// `descr` of the shape's `p:cNvPr`
+    pub description: Option<String>,
+}
+
+impl Picture {
+    pub fn from_blip(element: &BytesStart, description: Option<String>) -> Picture {
+        Picture {
+            embed: attribute(element, b"r:embed"),
+            link: attribute(element, b"r:link"),
+            svg_embed: None,
+            description: description
+                .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|d| !d.is_empty()),
+        }
+    }
+
+    /// Image data of the raster and SVG parts, looked up through the slide relationships
+    pub fn resolve<'a>(
+        &self,
+        relationships: &HashMap<String, Relationship>,
+        images: &'a HashMap<String, Vec<u8>>,
+    ) -> (Option<&'a [u8]>, Option<&'a [u8]>) {
+        let lookup = |id: &Option<String>| {
+            let relationship = relationships.get(id.as_ref()?)?;
+            if relationship.external {
+                return None;
+            }
+            images.get(&relationship.target).map(|data| data.as_slice())
+        };
+        (lookup(&self.embed), lookup(&self.svg_embed))
+    }
+}
+
+/// `![generated name](...)` -> `![alt](...)`
+pub fn with_alt_text(image_md: &str, alt: &str) -> String {
+    let alt = alt.replace('[', "(").replace(']', ")");
+    match (image_md.strip_prefix("!["), image_md.find("](")) {
+        (Some(_), Some(end)) => format!("![{}{}", alt, &image_md[end..]),
+        _ => image_md.to_string(),
+    }
+}
+
+/// Like `image2md::run_with_mode`, for SVG data that has no raster fallback
+pub fn svg_to_markdown(svg_data: &[u8], mode: ImageProcessingMode) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    let image_name = format!("pic-{}", chrono::Utc::now().timestamp());
+
+    match mode {
+        ImageProcessingMode::Base64 => {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(svg_data);
+            Ok(format!("![{}](data:image/svg+xml;base64,{})", image_name, encoded))
+        }
+        ImageProcessingMode::SaveToFile => {
+            fs::create_dir_all(&cfg.image_path)
+                .map_err(|e| format!("Failed to create image directory: {}", e))?;
+            let filename = unique_file_name(&cfg.image_path, &image_name, "svg");
+            fs::write(cfg.image_path.join(&filename), svg_data)
+                .map_err(|e| format!("Failed to save image file: {}", e))?;
+            Ok(format!("![{}]({})", image_name, filename))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    fn blip(xml: &str, description: Option<&str>) -> Picture {
+        let mut reader = Reader::from_str(xml);
+        match reader.read_event().unwrap() {
+            Event::Start(element) | Event::Empty(element) => {
+                Picture::from_blip(&element, description.map(str::to_string))
+            }
+            event => panic!("unexpected {:?}", event),
+        }
+    }
+
+    fn relationship(target: &str, external: bool) -> Relationship {
+        Relationship {
+            rel_type: "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image".to_string(),
+            target: target.to_string(),
+            external,
+        }
+    }
+
+    #[test]
+    fn test_blip_attributes_and_alt_text() {
+        let picture = blip(r#"<a:blip r:embed="rId2" r:link="rId3"/>"#, Some("  A  red\n square "));
+        assert_eq!(picture.embed.as_deref(), Some("rId2"));
+        assert_eq!(picture.link.as_deref(), Some("rId3"));
+        assert_eq!(picture.description.as_deref(), Some("A red square"));
+        assert_eq!(blip(r#"<a:blip r:embed="rId2"/>"#, Some(" ")).description, None);
+    }
+
+    #[test]
+    fn test_resolve_through_relationships() {
+        let relationships = HashMap::from([
+            ("rId2".to_string(), relationship("ppt/media/image1.png", false)),
+            ("rId3".to_string(), relationship("ppt/media/image2.svg", false)),
+            ("rId4".to_string(), relationship("https://example.com/a.png", true)),
+        ]);
+        let images = HashMap::from([
+            ("ppt/media/image1.png".to_string(), b"png".to_vec()),
+            ("ppt/media/image2.svg".to_string(), b"<svg/>".to_vec()),
+        ]);
+
+        let mut picture = blip(r#"<a:blip r:embed="rId2"/>"#, None);
+        picture.svg_embed = Some("rId3".to_string());
+        assert_eq!(picture.resolve(&relationships, &images), (Some(&b"png"[..]), Some(&b"<svg/>"[..])));
+
+        let external = blip(r#"<a:blip r:embed="rId4"/>"#, None);
+        assert_eq!(external.resolve(&relationships, &images), (None, None));
+        let missing = blip(r#"<a:blip r:embed="rId9"/>"#, None);
+        assert_eq!(missing.resolve(&relationships, &images), (None, None));
+    }
+
+    #[test]
+    fn test_with_alt_text() {
+        assert_eq!(with_alt_text("![pic-1](a.png)", "Chart [2024]"), "![Chart (2024)](a.png)");
+        assert_eq!(with_alt_text("not an image", "alt"), "not an image");
+    }
+
+    #[test]
+    fn test_svg_as_base64() {
+        let markdown = svg_to_markdown(b"<svg/>", ImageProcessingMode::Base64).unwrap();
+        assert!(markdown.starts_with("![pic-"));
+        assert!(markdown.ends_with("](data:image/svg+xml;base64,PHN2Zy8+)"), "{}", markdown);
+    }
+}
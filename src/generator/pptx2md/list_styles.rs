@@ -0,0 +1,465 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+use super::attribute;
+use super::geometry::point;
+use super::package::{Package, Relationship};
+
+/// DrawingML has nine paragraph levels, `a:lvl1pPr` to `a:lvl9pPr`
+const LEVELS: usize = 9;
+
+/// Bullet of a paragraph level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bullet {
+    /// `a:buNone`
+    None,
+    /// `a:buChar` or `a:buBlip`
+    Char,
+    /// `a:buAutoNum`, numbered from `startAt`
+    AutoNum { start: usize },
+}
+
+impl Bullet {
+    fn from_element(element: &BytesStart) -> Option<Bullet> {
+        match element.name().as_ref() {
+            b"a:buNone" => Some(Bullet::None),
+            b"a:buChar" | b"a:buBlip" => Some(Bullet::Char),
+            b"a:buAutoNum" => {
+                let start = attribute(element, b"startAt")
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(1);
+                Some(Bullet::AutoNum { start })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Bullets per level of an `a:lstStyle` or master text style; `None` where not specified
+#[derive(Debug, Clone, Default)]
+pub struct ListStyle {
+    levels: [Option<Bullet>; LEVELS],
+}
+
+impl ListStyle {
+    pub fn bullet(&self, level: usize) -> Option<Bullet> {
+        self.levels.get(level).copied().flatten()
+    }
+
+    /// This style with the levels it leaves unspecified taken from `base`
+    pub fn inherit(&self, base: &ListStyle) -> ListStyle {
+        let mut levels = self.levels;
+        for (level, inherited) in levels.iter_mut().zip(base.levels) {
+            if level.is_none() {
+                *level = inherited;
+            }
+        }
+        ListStyle { levels }
+    }
+}
+
+/// A `p:ph` placeholder reference; a placeholder without `type` is a body placeholder
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub kind: String,
+    pub idx: Option<String>,
+}
+
+impl Placeholder {
+    pub fn from_element(element: &BytesStart) -> Placeholder {
+        Placeholder {
+            kind: attribute(element, b"type").unwrap_or_else(|| "body".to_string()),
+            idx: attribute(element, b"idx"),
+        }
+    }
+
+    pub fn is_title(&self) -> bool {
+        matches!(self.kind.as_str(), "title" | "ctrTitle")
+    }
+
+    /// Placeholder kinds that take the master's body text style
+    fn is_body(&self) -> bool {
+        matches!(self.kind.as_str(), "body" | "obj" | "subTitle")
+    }
+
+    fn same_kind(&self, other: &Placeholder) -> bool {
+        let kind = |p: &Placeholder| match p.kind.as_str() {
+            "obj" => "body".to_string(),
+            "ctrTitle" => "title".to_string(),
+            kind => kind.to_string(),
+        };
+        kind(self) == kind(other)
+    }
+}
+
+/// List styles of a slide master: its `p:txStyles` and placeholder overrides
+#[derive(Debug, Clone, Default)]
+struct MasterStyles {
+    title: ListStyle,
+    body: ListStyle,
+    other: ListStyle,
+    placeholders: Vec<PlaceholderShape>,
+}
+
+/// The bullet styles and placeholder positions a slide inherits from its layout and master
+#[derive(Debug, Clone, Default)]
+pub struct SlideStyles {
+    layout: Vec<PlaceholderShape>,
+    master: MasterStyles,
+}
+
+impl SlideStyles {
+    /// Loads the layout and master of a slide through its relationships.
+    /// Slides without them get empty styles, so only explicit bullets apply.
+    pub fn load(
+        package: &mut Package,
+        slide_relationships: &HashMap<String, Relationship>,
+    ) -> Result<SlideStyles, String> {
+        let Some(layout_part) = related_part(slide_relationships, "slideLayout") else {
+            return Ok(SlideStyles::default());
+        };
+        let layout_xml = package.read_string(&layout_part)?.unwrap_or_default();
+        let layout = read_placeholder_styles(&layout_xml)?;
+
+        let layout_relationships = package.relationships(&layout_part)?;
+        let master = match related_part(&layout_relationships, "slideMaster") {
+            Some(master_part) => {
+                let master_xml = package.read_string(&master_part)?.unwrap_or_default();
+                read_master_styles(&master_xml)?
+            }
+            None => MasterStyles::default(),
+        };
+
+        Ok(SlideStyles { layout, master })
+    }
+
+    /// The list style a shape starts from, before its own `a:lstStyle`
+    pub fn inherited(&self, placeholder: Option<&Placeholder>) -> ListStyle {
+        let Some(placeholder) = placeholder else {
+            return self.master.other.clone();
+        };
+
+        let layout = self
+            .layout_shape(placeholder)
+            .map(|shape| shape.list_style.clone())
+            .unwrap_or_default();
+        let master = self
+            .master_shape(placeholder)
+            .map(|shape| shape.list_style.clone())
+            .unwrap_or_default();
+        let text_style = if placeholder.is_title() {
+            &self.master.title
+        } else if placeholder.is_body() {
+            &self.master.body
+        } else {
+            &self.master.other
+        };
+
+        layout.inherit(&master).inherit(text_style)
+    }
+
+    /// Offset of the layout or master shape a slide placeholder takes its position from
+    pub fn placeholder_offset(&self, placeholder: &Placeholder) -> Option<(i64, i64)> {
+        self.layout_shape(placeholder)
+            .and_then(|shape| shape.offset)
+            .or_else(|| self.master_shape(placeholder).and_then(|shape| shape.offset))
+    }
+
+    /// Layout placeholders match by `idx` (by type if the shape has none)
+    fn layout_shape(&self, placeholder: &Placeholder) -> Option<&PlaceholderShape> {
+        self.layout.iter().find(|shape| match &placeholder.idx {
+            Some(idx) => shape.placeholder.idx.as_ref() == Some(idx),
+            None => shape.placeholder.same_kind(placeholder),
+        })
+    }
+
+    /// Master placeholders match by type
+    fn master_shape(&self, placeholder: &Placeholder) -> Option<&PlaceholderShape> {
+        self.master
+            .placeholders
+            .iter()
+            .find(|shape| shape.placeholder.same_kind(placeholder))
+    }
+}
+
+fn related_part(relationships: &HashMap<String, Relationship>, kind: &str) -> Option<String> {
+    relationships
+        .values()
+        .find(|r| r.has_type(kind) && !r.external)
+        .map(|r| r.target.clone())
+}
+
+/// Reads the level properties up to the end tag `end`
+pub fn read_list_style(reader: &mut Reader<&[u8]>, end: &[u8]) -> Result<ListStyle, String> {
+    let mut style = ListStyle::default();
+    let mut level: Option<usize> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element)) => {
+                if let Some(n) = level_of(element.name().as_ref()) {
+                    level = Some(n);
+                } else if let (Some(n), Some(bullet)) = (level, Bullet::from_element(&element)) {
+                    style.levels[n] = Some(bullet);
+                }
+            }
+            Ok(Event::End(element)) => {
+                let name = element.name();
+                if name.as_ref() == end {
+                    break;
+                }
+                if level_of(name.as_ref()).is_some() {
+                    level = None;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing list style: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(style)
+}
+
+/// Reads an `a:pPr` (after its start tag) and returns its own bullet, if any
+pub fn read_paragraph_bullet(reader: &mut Reader<&[u8]>) -> Result<Option<Bullet>, String> {
+    let mut bullet = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element)) => {
+                if let Some(own) = Bullet::from_element(&element) {
+                    bullet = Some(own);
+                }
+            }
+            Ok(Event::End(element)) if element.name().as_ref() == b"a:pPr" => break,
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing paragraph properties: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(bullet)
+}
+
+/// 0-based level of `a:pPr lvl`
+pub fn paragraph_level(element: &BytesStart) -> usize {
+    attribute(element, b"lvl")
+        .and_then(|l| l.parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(LEVELS - 1)
+}
+
+/// `a:lvl3pPr` -> 2
+fn level_of(name: &[u8]) -> Option<usize> {
+    let digit = name.strip_prefix(b"a:lvl")?.strip_suffix(b"pPr")?;
+    match digit {
+        [d @ b'1'..=b'9'] => Some((d - b'1') as usize),
+        _ => None,
+    }
+}
+
+/// A placeholder shape of a layout or master
+#[derive(Debug, Clone)]
+struct PlaceholderShape {
+    placeholder: Placeholder,
+    list_style: ListStyle,
+    /// `a:off` of the shape, which slide placeholders without their own inherit
+    offset: Option<(i64, i64)>,
+}
+
+/// Placeholder shapes of a layout or master, with their `a:lstStyle` and offset
+fn read_placeholder_styles(xml: &str) -> Result<Vec<PlaceholderShape>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut shapes = Vec::new();
+    let mut placeholder: Option<Placeholder> = None;
+    let mut list_style = ListStyle::default();
+    let mut offset: Option<(i64, i64)> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => match element.name().as_ref() {
+                b"p:sp" => {
+                    placeholder = None;
+                    list_style = ListStyle::default();
+                    offset = None;
+                }
+                b"p:ph" => placeholder = Some(Placeholder::from_element(&element)),
+                b"a:lstStyle" => list_style = read_list_style(&mut reader, b"a:lstStyle")?,
+                _ => {}
+            },
+            Ok(Event::Empty(element)) => match element.name().as_ref() {
+                b"p:ph" => placeholder = Some(Placeholder::from_element(&element)),
+                b"a:off" if offset.is_none() => offset = Some(point(&element, b"x", b"y")),
+                _ => {}
+            },
+            Ok(Event::End(element)) if element.name().as_ref() == b"p:sp" => {
+                if let Some(placeholder) = placeholder.take() {
+                    shapes.push(PlaceholderShape {
+                        placeholder,
+                        list_style: std::mem::take(&mut list_style),
+                        offset,
+                    });
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing slide layout XML: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(shapes)
+}
+
+fn read_master_styles(xml: &str) -> Result<MasterStyles, String> {
+    let mut master = MasterStyles {
+        placeholders: read_placeholder_styles(xml)?,
+        ..MasterStyles::default()
+    };
+
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => match element.name().as_ref() {
+                b"p:titleStyle" => master.title = read_list_style(&mut reader, b"p:titleStyle")?,
+                b"p:bodyStyle" => master.body = read_list_style(&mut reader, b"p:bodyStyle")?,
+                b"p:otherStyle" => master.other = read_list_style(&mut reader, b"p:otherStyle")?,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing slide master XML: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(master)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pptx2md::package::tests::archive;
+
+    fn list_style(xml: &str) -> ListStyle {
+        let mut reader = Reader::from_str(xml);
+        reader.read_event().unwrap();
+        read_list_style(&mut reader, b"a:lstStyle").unwrap()
+    }
+
+    fn rels(kind: &str, target: &str) -> String {
+        format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}"
+            Target="{}"/></Relationships>"#,
+            kind, target
+        )
+    }
+
+    #[test]
+    fn test_read_list_style_levels() {
+        let style = list_style(
+            r#"<a:lstStyle>
+                <a:lvl1pPr><a:buNone/></a:lvl1pPr>
+                <a:lvl2pPr marL="457200"><a:buFont typeface="Arial"/><a:buChar char="-"/></a:lvl2pPr>
+                <a:lvl4pPr><a:buAutoNum type="arabicPeriod" startAt="3"/></a:lvl4pPr>
+                <a:lvl5pPr><a:buAutoNum type="alphaLcParenR"/></a:lvl5pPr>
+            </a:lstStyle>"#,
+        );
+        assert_eq!(style.bullet(0), Some(Bullet::None));
+        assert_eq!(style.bullet(1), Some(Bullet::Char));
+        assert_eq!(style.bullet(2), None);
+        assert_eq!(style.bullet(3), Some(Bullet::AutoNum { start: 3 }));
+        assert_eq!(style.bullet(4), Some(Bullet::AutoNum { start: 1 }));
+        assert_eq!(style.bullet(LEVELS), None);
+    }
+
+    #[test]
+    fn test_inherit_fills_unspecified_levels() {
+        let own = list_style(r#"<a:lstStyle><a:lvl2pPr><a:buNone/></a:lvl2pPr></a:lstStyle>"#);
+        let base = list_style(
+            r#"<a:lstStyle><a:lvl1pPr><a:buChar char="*"/></a:lvl1pPr>
+            <a:lvl2pPr><a:buChar char="-"/></a:lvl2pPr></a:lstStyle>"#,
+        );
+        let style = own.inherit(&base);
+        assert_eq!(style.bullet(0), Some(Bullet::Char));
+        assert_eq!(style.bullet(1), Some(Bullet::None));
+        assert_eq!(style.bullet(2), None);
+    }
+
+    #[test]
+    fn test_paragraph_level() {
+        let mut reader = Reader::from_str(r#"<a:pPr lvl="2"/><a:pPr/><a:pPr lvl="12"/>"#);
+        let mut levels = Vec::new();
+        while let Ok(Event::Empty(element)) = reader.read_event() {
+            levels.push(paragraph_level(&element));
+        }
+        assert_eq!(levels, vec![2, 0, LEVELS - 1]);
+    }
+
+    #[test]
+    fn test_placeholder_kinds() {
+        let mut reader = Reader::from_str(r#"<p:ph idx="1"/><p:ph type="ctrTitle"/><p:ph type="dt" idx="10"/>"#);
+        let mut placeholders = Vec::new();
+        while let Ok(Event::Empty(element)) = reader.read_event() {
+            placeholders.push(Placeholder::from_element(&element));
+        }
+        let [body, title, date] = placeholders.as_slice() else { panic!("{:?}", placeholders) };
+        assert_eq!(body, &Placeholder { kind: "body".to_string(), idx: Some("1".to_string()) });
+        assert!(body.is_body() && !body.is_title());
+        assert!(title.is_title());
+        assert!(title.same_kind(&Placeholder { kind: "title".to_string(), idx: None }));
+        assert!(!date.is_body() && !date.is_title());
+    }
+
+    #[test]
+    fn test_slide_styles_inherit_from_layout_and_master() {
+        let layout = r#"<p:sldLayout><p:cSld><p:spTree>
+            <p:sp><p:nvSpPr><p:nvPr><p:ph type="body" idx="1"/></p:nvPr></p:nvSpPr>
+            <p:txBody><a:lstStyle><a:lvl1pPr><a:buAutoNum type="arabicPeriod"/></a:lvl1pPr></a:lstStyle></p:txBody>
+            </p:sp></p:spTree></p:cSld></p:sldLayout>"#;
+        let master = r#"<p:sldMaster><p:cSld><p:spTree>
+            <p:sp><p:nvSpPr><p:nvPr><p:ph type="body" idx="1"/></p:nvPr></p:nvSpPr>
+            <p:spPr><a:xfrm><a:off x="10" y="20"/></a:xfrm></p:spPr></p:sp>
+            <p:sp><p:nvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr>
+            <p:spPr><a:xfrm><a:off x="30" y="40"/></a:xfrm></p:spPr></p:sp>
+            </p:spTree></p:cSld><p:txStyles>
+            <p:titleStyle><a:lvl1pPr><a:buNone/></a:lvl1pPr></p:titleStyle>
+            <p:bodyStyle><a:lvl1pPr><a:buChar char="*"/></a:lvl1pPr>
+            <a:lvl2pPr><a:buChar char="-"/></a:lvl2pPr></p:bodyStyle>
+            <p:otherStyle><a:lvl1pPr><a:buNone/></a:lvl1pPr></p:otherStyle>
+            </p:txStyles></p:sldMaster>"#;
+        let slide_rels = rels("slideLayout", "../slideLayouts/slideLayout1.xml");
+        let layout_rels = rels("slideMaster", "../slideMasters/slideMaster1.xml");
+        let data = archive(&[
+            ("ppt/slides/_rels/slide1.xml.rels", &slide_rels),
+            ("ppt/slideLayouts/slideLayout1.xml", layout),
+            ("ppt/slideLayouts/_rels/slideLayout1.xml.rels", &layout_rels),
+            ("ppt/slideMasters/slideMaster1.xml", master),
+        ]);
+        let mut package = Package::open(&data).unwrap();
+        let relationships = package.relationships("ppt/slides/slide1.xml").unwrap();
+        let styles = SlideStyles::load(&mut package, &relationships).unwrap();
+
+        let body = Placeholder { kind: "body".to_string(), idx: Some("1".to_string()) };
+        let inherited = styles.inherited(Some(&body));
+        assert_eq!(inherited.bullet(0), Some(Bullet::AutoNum { start: 1 }));
+        assert_eq!(inherited.bullet(1), Some(Bullet::Char));
+        assert_eq!(styles.placeholder_offset(&body), Some((10, 20)));
+
+        let title = Placeholder { kind: "ctrTitle".to_string(), idx: None };
+        assert_eq!(styles.inherited(Some(&title)).bullet(0), Some(Bullet::None));
+        assert_eq!(styles.placeholder_offset(&title), Some((30, 40)));
+        assert_eq!(styles.inherited(None).bullet(0), Some(Bullet::None));
+    }
+
+    #[test]
+    fn test_slide_without_layout_has_empty_styles() {
+        let data = archive(&[]);
+        let mut package = Package::open(&data).unwrap();
+        let styles = SlideStyles::load(&mut package, &HashMap::new()).unwrap();
+        let body = Placeholder { kind: "body".to_string(), idx: None };
+        assert_eq!(styles.inherited(Some(&body)).bullet(0), None);
+        assert_eq!(styles.placeholder_offset(&body), None);
+    }
+}
@@ -0,0 +1,1050 @@
+mod charts;
+mod geometry;
+mod images;
+mod list_styles;
+mod notes;
+mod package;
+mod presentation;
+mod runs;
+mod smartart;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use geometry::{GroupTransform, ShapeBlock};
+use images::Picture;
+use list_styles::{Bullet, ListStyle, Placeholder, SlideStyles};
+use package::{Package, Relationship};
+use runs::{Links, RunFormat, TextRun};
+use crate::generator::image2md::{self, ImageProcessingMode};
+use crate::config::SETTINGS;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Default)]
+struct SlideContent {
+    /// Text of the title placeholder, if the slide has one
+    title: Option<String>,
+    body: String,
+}
+
+#[derive(Debug, Clone)]
+struct TableData {
+    rows: Vec<Vec<String>>,
+    /// Whether the first row is a header row (`a:tblPr firstRow`)
+    has_header: bool,
+}
+
+/// An `a:tc` with its merge attributes
+#[derive(Debug, Clone)]
+struct TableCell {
+    /// Paragraphs of the cell
+    lines: Vec<String>,
+    grid_span: usize,
+    row_span: usize,
+    /// Covered by a cell spanning from the left (`hMerge`) or from above (`vMerge`)
+    merged: bool,
+}
+
+impl TableCell {
+    fn is_spanning(&self) -> bool {
+        self.grid_span > 1 || self.row_span > 1 || self.merged
+    }
+}
+
+pub fn run(file_stream: &[u8]) -> Result<String, String> {
+    run_with_images(file_stream)
+}
+
+fn run_with_images(file_stream: &[u8]) -> Result<String, String> {
+    let mut package = Package::open(file_stream)?;
+
+    // First, extract all images from the archive
+    let mut images = HashMap::new();
+    for name in package.part_names() {
+        if !name.starts_with("ppt/media/") {
+            continue;
+        }
+        if let Some(image_data) = package.read(&name)? {
+            images.insert(name, image_data);
+        }
+    }
+
+    let cfg = &*SETTINGS.read().unwrap();
+    let slides = presentation::slides(&mut package)?;
+    let has_sections = slides.iter().any(|slide| slide.section.is_some());
+
+    let mut markdown = String::new();
+    markdown.push_str("# PowerPoint Presentation\n\n");
+
+    let mut current_section: Option<String> = None;
+    let slide_numbers: HashMap<String, usize> = slides
+        .iter()
+        .map(|slide| (slide.part.clone(), slide.number))
+        .collect();
+    // Hidden slides left out of the output, so that links to them can be dropped
+    let skipped: HashSet<usize> = slides
+        .iter()
+        .filter(|slide| slide.hidden && cfg.pptx_skip_hidden_slides)
+        .map(|slide| slide.number)
+        .collect();
+    let link_targets = RefCell::new(HashSet::new());
+    // Markdown in front of each slide's anchor (the section heading), the slide number
+    // and the slide itself; anchors are only known once every slide has been read
+    let mut rendered: Vec<(String, usize, String)> = Vec::new();
+
+    // Process slides in presentation order
+    for slide in &slides {
+        if skipped.contains(&slide.number) {
+            continue;
+        }
+        let content = package
+            .read_string(&slide.part)?
+            .ok_or_else(|| format!("Slide part {} is missing from the archive", slide.part))?;
+
+        let relationships = package.relationships(&slide.part)?;
+
+        let styles = SlideStyles::load(&mut package, &relationships)?;
+
+        let links = Links {
+            relationships: &relationships,
+            slide_numbers: &slide_numbers,
+            current_slide: slide.number,
+            slide_count: slides.len(),
+            skipped: &skipped,
+            targets: &link_targets,
+        };
+
+        let slide_content = parse_slide_content(&content, &mut package, &links, &styles, &images)?;
+
+        // Sections sit one level above their slides
+        let mut section_heading = String::new();
+        if has_sections && slide.section != current_section {
+            if let Some(section) = &slide.section {
+                section_heading = format!("## {}\n\n", section);
+            }
+            current_section = slide.section.clone();
+        }
+
+        let level = if has_sections { "###" } else { "##" };
+        let mut heading = match &slide_content.title {
+            Some(title) => format!("{} Slide {}: {}", level, slide.number, title),
+            None => format!("{} Slide {}", level, slide.number),
+        };
+        if slide.hidden {
+            heading.push_str(" (hidden)");
+        }
+        let mut slide_markdown = heading;
+        slide_markdown.push_str("\n\n");
+
+        slide_markdown.push_str(&slide_content.body);
+
+        let notes_part = notes::notes_part(&relationships).filter(|_| cfg.pptx_include_notes);
+        if let Some(notes_part) = notes_part {
+            let notes_xml = package.read_string(notes_part)?.unwrap_or_default();
+            let notes = notes::extract_notes(&notes_xml)?;
+            if !notes.is_empty() {
+                slide_markdown.push_str(&format!("{}# Notes\n\n", level));
+                slide_markdown.push_str(&notes.join("\n\n"));
+                slide_markdown.push_str("\n\n");
+            }
+        }
+
+        slide_markdown.push_str("\n\n---\n\n");
+        rendered.push((section_heading, slide.number, slide_markdown));
+    }
+
+    let link_targets = link_targets.into_inner();
+    for (section_heading, number, slide_markdown) in rendered {
+        markdown.push_str(&section_heading);
+        // Target of links to this slide from elsewhere in the deck
+        if link_targets.contains(&number) {
+            markdown.push_str(&format!("<a id=\"{}\"></a>\n\n", runs::slide_anchor_id(number)));
+        }
+        markdown.push_str(&slide_markdown);
+    }
+
+    Ok(markdown)
+}
+
+fn parse_slide_content(
+    xml_content: &str, 
+    package: &mut Package,
+    links: &Links,
+    styles: &SlideStyles,
+    images: &HashMap<String, Vec<u8>>
+) -> Result<SlideContent, String> {
+    let relationships = links.relationships;
+    let mut reader = Reader::from_str(xml_content);
+    let mut slide = SlideContent::default();
+    let mut buf = Vec::new();
+    // One block per shape, put in reading order once the slide is read
+    let mut blocks: Vec<ShapeBlock> = Vec::new();
+    // Transforms of the enclosing p:spTree / p:grpSp elements
+    let mut groups: Vec<GroupTransform> = Vec::new();
+    let mut in_group_properties = false;
+    let mut in_shape = false;
+    // Placeholder the shape being read fills, if any
+    let mut placeholder: Option<Placeholder> = None;
+    // Alt text of the current shape, from its p:cNvPr
+    let mut description: Option<String> = None;
+    // An a:blip whose extensions (SVG variant) are still being read
+    let mut picture: Option<Picture> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(element)) => {
+                match element.name().as_ref() {
+                    b"p:spTree" | b"p:grpSp" => groups.push(GroupTransform::default()),
+                    b"p:grpSpPr" => in_group_properties = true,
+                    b"p:sp" | b"p:pic" | b"p:graphicFrame" | b"p:cxnSp" => {
+                        blocks.push(ShapeBlock::default());
+                        in_shape = true;
+                        placeholder = None;
+                    }
+                    b"p:ph" => placeholder = Some(Placeholder::from_element(&element)),
+                    b"p:txBody" if placeholder.as_ref().is_some_and(Placeholder::is_title)
+                        && slide.title.is_none() => {
+                        let title = extract_plain_text(&mut reader)?;
+                        if !title.is_empty() {
+                            slide.title = Some(title);
+                        }
+                    }
+                    b"p:txBody" => {
+                        let inherited = styles.inherited(placeholder.as_ref());
+                        let text_content = extract_text_body(&mut reader, &inherited, links)?;
+                        if !text_content.trim().is_empty() {
+                            let body = current_block(&mut blocks);
+                            body.push_str(&text_content);
+                            body.push_str("\n\n");
+                        }
+                    }
+                    b"a:tbl" => {
+                        let table_content = extract_table(&mut reader)?;
+                        let body = current_block(&mut blocks);
+                        body.push_str(&table_content);
+                        body.push('\n');
+                    }
+                    b"p:cNvPr" => description = attribute(&element, b"descr"),
+                    b"a:blip" => picture = Some(Picture::from_blip(&element, description.clone())),
+                    _ => {}
+                }
+            }
+            Ok(Event::Empty(element)) => match element.name().as_ref() {
+                b"a:off" | b"a:ext" | b"a:chOff" | b"a:chExt" if in_group_properties => {
+                    if let Some(group) = groups.last_mut() {
+                        group.set(&element);
+                    }
+                }
+                b"a:off" if in_shape => {
+                    let block = blocks.last_mut().filter(|b| b.position.is_none());
+                    if let Some(block) = block {
+                        block.position = Some(geometry::slide_position(&element, &groups));
+                    }
+                }
+                b"p:ph" => placeholder = Some(Placeholder::from_element(&element)),
+                b"p:cNvPr" => description = attribute(&element, b"descr"),
+                b"a:blip" => {
+                    let picture = Picture::from_blip(&element, description.clone());
+                    push_picture(current_block(&mut blocks), &picture, relationships, images)?;
+                }
+                b"c:chart" => {
+                    if let Some(chart_part) = related_part(&element, b"r:id", relationships) {
+                        let chart = charts::chart_to_markdown(package, &chart_part)?;
+                        current_block(&mut blocks).push_str(&chart);
+                    }
+                }
+                b"dgm:relIds" => {
+                    if let Some(data_part) = related_part(&element, b"r:dm", relationships) {
+                        let diagram = smartart::smartart_to_markdown(package, &data_part)?;
+                        current_block(&mut blocks).push_str(&diagram);
+                    }
+                }
+                b"asvg:svgBlip" => {
+                    if let Some(picture) = picture.as_mut() {
+                        picture.svg_embed = attribute(&element, b"r:embed");
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(element)) => match element.name().as_ref() {
+                b"a:blip" => {
+                    if let Some(picture) = picture.take() {
+                        push_picture(current_block(&mut blocks), &picture, relationships, images)?;
+                    }
+                }
+                b"p:spTree" | b"p:grpSp" => {
+                    groups.pop();
+                }
+                b"p:grpSpPr" => in_group_properties = false,
+                b"p:sp" | b"p:pic" | b"p:graphicFrame" | b"p:cxnSp" => {
+                    in_shape = false;
+                    // placeholders without their own transform sit where the layout puts them
+                    let inherited = placeholder.as_ref().and_then(|p| styles.placeholder_offset(p));
+                    let block = blocks.last_mut().filter(|b| b.position.is_none());
+                    if let Some(block) = block {
+                        block.position = inherited;
+                    }
+                    placeholder = None;
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing slide XML: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    slide.body = geometry::order_blocks(blocks);
+    Ok(slide)
+}
+
+/// Markdown of the shape being read; content outside any shape (a background picture) gets its own block
+fn current_block(blocks: &mut Vec<ShapeBlock>) -> &mut String {
+    if blocks.is_empty() {
+        blocks.push(ShapeBlock::default());
+    }
+    let last = blocks.len() - 1;
+    &mut blocks[last].markdown
+}
+
+/// Target part of the relationship an element refers to through the attribute `name`
+fn related_part(
+    element: &BytesStart,
+    name: &[u8],
+    relationships: &HashMap<String, Relationship>
+) -> Option<String> {
+    attribute(element, name)
+        .and_then(|id| relationships.get(&id))
+        .filter(|r| !r.external)
+        .map(|r| r.target.clone())
+}
+
+fn push_picture(
+    markdown: &mut String,
+    picture: &Picture,
+    relationships: &HashMap<String, Relationship>,
+    images: &HashMap<String, Vec<u8>>
+) -> Result<(), String> {
+    if let Some(image_md) = process_image_element(picture, relationships, images)? {
+        markdown.push_str(&image_md);
+        markdown.push_str("\n\n");
+    }
+    Ok(())
+}
+
+fn process_image_element(
+    picture: &Picture,
+    relationships: &HashMap<String, Relationship>,
+    images: &HashMap<String, Vec<u8>>
+) -> Result<Option<String>, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    
+    // Determine processing mode based on configuration
+    let mode = if cfg.image_path.as_os_str().is_empty() {
+        ImageProcessingMode::Base64
+    } else {
+        ImageProcessingMode::SaveToFile
+    };
+
+    // Linked pictures are not part of the package, reference them by URL
+    if let Some(relationship) = picture.link.as_ref().and_then(|id| relationships.get(id)) {
+        let alt = picture.description.as_deref().unwrap_or("Linked image");
+        return Ok(Some(format!("![{}]({})", alt, relationship.target)));
+    }
+
+    // The raster r:embed is PowerPoint's fallback for an SVG; use it when present
+    // since image2md only handles raster formats, and the SVG otherwise
+    let image_data = match picture.resolve(relationships, images) {
+        (Some(raster), _) => raster,
+        (None, Some(svg_data)) => {
+            let image_md = images::svg_to_markdown(svg_data, mode)?;
+            return finish_image_markdown(image_md, picture).map(Some);
+        }
+        (None, None) => {
+            let embed_id = picture.embed.as_deref().or(picture.svg_embed.as_deref());
+            return Ok(embed_id.map(|id| format!("![Image not found]({})", id)));
+        }
+    };
+
+    // Use the image2md module to process the image with proper mode
+    let image_md = image2md::run_with_mode(image_data, mode)?;
+    finish_image_markdown(image_md, picture).map(Some)
+}
+
+fn finish_image_markdown(image_md: String, picture: &Picture) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+
+    let image_md = match &picture.description {
+        Some(alt) => images::with_alt_text(&image_md, alt),
+        None => image_md,
+    };
+
+    // Handle relative paths if needed
+    if !cfg.image_path.as_os_str().is_empty() {
+        adjust_image_path_in_markdown(image_md)
+    } else {
+        Ok(image_md)
+    }
+}
+
+fn adjust_image_path_in_markdown(markdown: String) -> Result<String, String> {
+    let cfg = &*SETTINGS.read().unwrap();
+    
+    // If we have an output path and it's not empty, try to make image paths relative
+    if let Some(output_path) = &cfg.output_path {
+        if !output_path.as_os_str().is_empty() {
+            // Calculate relative path from output directory to image directory
+            let output_dir = output_path.parent().unwrap_or(Path::new("."));
+            
+            if let Ok(relative_path) = cfg.image_path.strip_prefix(output_dir) {
+                let relative_str = relative_path.to_string_lossy();
+                
+                // Simple approach: since we control image generation, we can do direct replacement
+                // Look for patterns like ![alt](filename.ext) and replace with ![alt](relative_path/filename.ext)
+                let lines: Vec<&str> = markdown.lines().collect();
+                let mut result = String::new();
+                
+                for line in lines {
+                    if line.contains("![") && line.contains("](") {
+                        // Find and replace image references
+                        let mut new_line = line.to_string();
+                        
+                        // Look for image markdown pattern and extract filename
+                        if let Some(start) = line.find("](") {
+                            if let Some(end) = line[start+2..].find(")") {
+                                let path_part = &line[start+2..start+2+end];
+                                
+                                // Check if it's just a filename (no path separators)
+                                if !path_part.contains('/') && !path_part.contains('\\') && 
+                                   (path_part.ends_with(".png") || path_part.ends_with(".jpg") || 
+                                    path_part.ends_with(".jpeg") || path_part.ends_with(".gif") || 
+                                    path_part.ends_with(".webp") || path_part.ends_with(".svg")) {
+                                    
+                                    let new_path = format!("{}/{}", relative_str, path_part);
+                                    new_line = line.replace(&format!("]({})", path_part), &format!("]({})", new_path));
+                                }
+                            }
+                        }
+                        result.push_str(&new_line);
+                    } else {
+                        result.push_str(line);
+                    }
+                    result.push('\n');
+                }
+                
+                return Ok(result);
+            }
+        }
+    }
+    
+    // If no output path configured or empty (stdout), use absolute paths
+    let abs_path = cfg.image_path.canonicalize()
+        .unwrap_or_else(|_| cfg.image_path.clone())
+        .to_string_lossy()
+        .to_string();
+    
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut result = String::new();
+    
+    for line in lines {
+        if line.contains("![") && line.contains("](") {
+            // Find and replace image references with absolute paths
+            let mut new_line = line.to_string();
+            
+            // Look for image markdown pattern and extract filename
+            if let Some(start) = line.find("](") {
+                if let Some(end) = line[start+2..].find(")") {
+                    let path_part = &line[start+2..start+2+end];
+                    
+                    // Check if it's just a filename (no path separators)
+                    if !path_part.contains('/') && !path_part.contains('\\') && 
+                       (path_part.ends_with(".png") || path_part.ends_with(".jpg") || 
+                        path_part.ends_with(".jpeg") || path_part.ends_with(".gif") || 
+                        path_part.ends_with(".webp") || path_part.ends_with(".svg")) {
+                        
+                        let new_path = format!("{}/{}", abs_path, path_part);
+                        new_line = line.replace(&format!("]({})", path_part), &format!("]({})", new_path));
+                    }
+                }
+            }
+            result.push_str(&new_line);
+        } else {
+            result.push_str(line);
+        }
+        result.push('\n');
+    }
+    
+    Ok(result)
+}
+
+/// A text body paragraph with its resolved list properties
+struct TextParagraph {
+    level: usize,
+    bullet: Bullet,
+    text: String,
+}
+
+fn extract_text_body(
+    reader: &mut Reader<&[u8]>,
+    inherited: &ListStyle,
+    links: &Links
+) -> Result<String, String> {
+    let mut paragraphs = Vec::new();
+    let mut list_style = inherited.clone();
+    let mut buf = Vec::new();
+    let mut runs: Vec<TextRun> = Vec::new();
+    let mut format = RunFormat::default();
+    let mut level = 0;
+    let mut own_bullet: Option<Bullet> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(element)) => {
+                match element.name().as_ref() {
+                    b"a:lstStyle" => {
+                        list_style = list_styles::read_list_style(reader, b"a:lstStyle")?
+                            .inherit(inherited);
+                    }
+                    b"a:pPr" => {
+                        level = list_styles::paragraph_level(&element);
+                        own_bullet = list_styles::read_paragraph_bullet(reader)?;
+                    }
+                    b"a:r" | b"a:fld" => format = RunFormat::default(),
+                    b"a:rPr" => format = RunFormat::read(reader, &element, false, links)?,
+                    b"a:t" => {
+                        let text = extract_text_run(reader)?;
+                        runs.push(TextRun { format: format.clone(), text });
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Empty(element)) => {
+                match element.name().as_ref() {
+                    b"a:pPr" => level = list_styles::paragraph_level(&element),
+                    b"a:rPr" => format = RunFormat::read(reader, &element, true, links)?,
+                    b"a:br" => runs.push(TextRun { format: RunFormat::default(), text: " ".to_string() }),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(element)) => {
+                match element.name().as_ref() {
+                    b"a:p" => {
+                        let bullet = own_bullet
+                            .or_else(|| list_style.bullet(level))
+                            .unwrap_or(Bullet::None);
+                        paragraphs.push(TextParagraph {
+                            level,
+                            bullet,
+                            text: runs::format_runs(&runs).trim().to_string(),
+                        });
+                        runs.clear();
+                        level = 0;
+                        own_bullet = None;
+                    }
+                    b"p:txBody" => break,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error extracting text body: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(format_paragraphs(&paragraphs))
+}
+
+/// Renders bulleted paragraphs as (nested) Markdown lists and the rest as plain paragraphs
+fn format_paragraphs(paragraphs: &[TextParagraph]) -> String {
+    let mut markdown = String::new();
+    // Paragraph levels of the open lists, outermost first, with the last
+    // number used at each (None for bulleted lists)
+    let mut open_lists: Vec<(usize, Option<usize>)> = Vec::new();
+
+    for paragraph in paragraphs {
+        if paragraph.text.is_empty() {
+            continue;
+        }
+
+        if paragraph.bullet == Bullet::None {
+            if !markdown.is_empty() {
+                markdown.push('\n');
+            }
+            markdown.push_str(&paragraph.text);
+            markdown.push('\n');
+            open_lists.clear();
+            continue;
+        }
+
+        if open_lists.is_empty() && !markdown.is_empty() {
+            markdown.push('\n');
+        }
+
+        // Levels can skip (0 -> 2); nest one Markdown level at a time regardless
+        while open_lists.last().is_some_and(|(level, _)| *level > paragraph.level) {
+            open_lists.pop();
+        }
+        if open_lists.last().is_none_or(|(level, _)| *level < paragraph.level) {
+            open_lists.push((paragraph.level, None));
+        }
+        let depth = open_lists.len() - 1;
+        let last_number = &mut open_lists[depth].1;
+
+        let marker = match paragraph.bullet {
+            Bullet::AutoNum { start } => {
+                let number = last_number.map_or(start, |n| n + 1);
+                *last_number = Some(number);
+                format!("{}.", number)
+            }
+            _ => {
+                *last_number = None;
+                "-".to_string()
+            }
+        };
+
+        markdown.push_str(&format!("{}{} {}\n", "    ".repeat(depth), marker, paragraph.text));
+    }
+
+    markdown
+}
+
+/// Text of a `p:txBody` with its paragraphs joined on one line
+fn extract_plain_text(reader: &mut Reader<&[u8]>) -> Result<String, String> {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current_paragraph = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(element)) if element.name().as_ref() == b"a:t" => {
+                current_paragraph.push_str(&extract_text_run(reader)?);
+            }
+            Ok(Event::Empty(element)) if element.name().as_ref() == b"a:br" => {
+                current_paragraph.push(' ');
+            }
+            Ok(Event::End(element)) => match element.name().as_ref() {
+                b"a:p" => {
+                    let text = current_paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if !text.is_empty() {
+                        paragraphs.push(text);
+                    }
+                    current_paragraph.clear();
+                }
+                b"p:txBody" => break,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error extracting text body: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(paragraphs.join(" "))
+}
+
+fn extract_text_run(reader: &mut Reader<&[u8]>) -> Result<String, String> {
+    let mut text = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Text(e)) => {
+                text.push_str(&e.unescape().unwrap_or_default());
+            }
+            Ok(Event::End(element)) => {
+                if element.name().as_ref() == b"a:t" {
+                    break;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error extracting text run: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(text)
+}
+
+fn extract_table(reader: &mut Reader<&[u8]>) -> Result<String, String> {
+    let mut rows: Vec<Vec<TableCell>> = vec![];
+    let mut has_header = false;
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(element)) => {
+                match element.name().as_ref() {
+                    b"a:tblPr" => has_header = is_flag_set(&element, b"firstRow"),
+                    b"a:tr" => rows.push(vec![]),
+                    b"a:tc" => {
+                        let cell = extract_table_cell(reader, &element)?;
+                        if let Some(row) = rows.last_mut() {
+                            row.push(cell);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Empty(element)) => {
+                match element.name().as_ref() {
+                    b"a:tblPr" => has_header = is_flag_set(&element, b"firstRow"),
+                    b"a:tc" => {
+                        if let Some(row) = rows.last_mut() {
+                            row.push(empty_table_cell(&element));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::End(element)) => {
+                if element.name().as_ref() == b"a:tbl" {
+                    break;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error extracting table: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    // GFM tables have no merged cells; keep the structure with an HTML table instead
+    if rows.iter().flatten().any(TableCell::is_spanning) {
+        return Ok(format_table_as_html(&rows, has_header));
+    }
+
+    let rows = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(|cell| cell.lines.join("<br>")).collect())
+        .collect();
+    Ok(format_table_as_markdown(&TableData { rows, has_header }))
+}
+
+fn empty_table_cell(element: &BytesStart) -> TableCell {
+    let span = |name| {
+        attribute(element, name)
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(1)
+            .max(1)
+    };
+    TableCell {
+        lines: vec![],
+        grid_span: span(b"gridSpan"),
+        row_span: span(b"rowSpan"),
+        merged: is_flag_set(element, b"hMerge") || is_flag_set(element, b"vMerge"),
+    }
+}
+
+fn extract_table_cell(reader: &mut Reader<&[u8]>, element: &BytesStart) -> Result<TableCell, String> {
+    let mut cell = empty_table_cell(element);
+    let mut cell_content = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Text(e)) => {
+                cell_content.push_str(&e.unescape().unwrap_or_default());
+            }
+            Ok(Event::End(element)) => {
+                match element.name().as_ref() {
+                    b"a:p" => {
+                        if !cell_content.trim().is_empty() {
+                            cell.lines.push(cell_content.trim().to_string());
+                        }
+                        cell_content.clear();
+                    }
+                    b"a:tc" => break,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error extracting table cell: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(cell)
+}
+
+fn is_flag_set(element: &BytesStart, name: &[u8]) -> bool {
+    attribute(element, name).is_some_and(|v| v == "1" || v == "true")
+}
+
+fn format_table_as_markdown(table: &TableData) -> String {
+    if table.rows.is_empty() {
+        return String::new();
+    }
+
+    let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+    let format_row = |row: &[String]| {
+        let mut line = String::from("|");
+        for i in 0..columns {
+            let cell = row.get(i).map(|c| c.replace('|', "\\|")).unwrap_or_default();
+            line.push_str(&format!(" {} |", cell));
+        }
+        line.push('\n');
+        line
+    };
+
+    let mut markdown = String::new();
+
+    // GFM needs a header row; without one it is left empty
+    let body = if table.has_header {
+        markdown.push_str(&format_row(&table.rows[0]));
+        &table.rows[1..]
+    } else {
+        markdown.push_str(&format_row(&[]));
+        &table.rows[..]
+    };
+
+    // Separator row
+    markdown.push('|');
+    for _ in 0..columns {
+        markdown.push_str("---|");
+    }
+    markdown.push('\n');
+
+    // Data rows
+    for row in body {
+        markdown.push_str(&format_row(row));
+    }
+
+    markdown
+}
+
+/// HTML table keeping `colspan`/`rowspan`; cells covered by a merge are left out
+fn format_table_as_html(rows: &[Vec<TableCell>], has_header: bool) -> String {
+    let mut html = String::from("<table>\n");
+
+    for (i, row) in rows.iter().enumerate() {
+        let tag = if has_header && i == 0 { "th" } else { "td" };
+        html.push_str("  <tr>");
+        for cell in row.iter().filter(|cell| !cell.merged) {
+            html.push_str(&format!("<{}", tag));
+            if cell.grid_span > 1 {
+                html.push_str(&format!(" colspan=\"{}\"", cell.grid_span));
+            }
+            if cell.row_span > 1 {
+                html.push_str(&format!(" rowspan=\"{}\"", cell.row_span));
+            }
+            let lines: Vec<String> = cell.lines.iter().map(|line| escape_html(line)).collect();
+            html.push_str(&format!(">{}</{}>", lines.join("<br>"), tag));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</table>\n");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use package::tests::archive;
+
+    const NS: &str = r#"xmlns:a="a" xmlns:p="p" xmlns:r="r""#;
+    const GIF: &[u8] = b"GIF89a\x01\x00\x01\x00\x00\x00\x00";
+
+    fn relationship(id: &str, rel_type: &str, target: &str) -> (String, Relationship) {
+        let relationship = Relationship {
+            rel_type: format!("http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}", rel_type),
+            target: target.to_string(),
+            external: target.contains("://"),
+        };
+        (id.to_string(), relationship)
+    }
+
+    /// Markdown of a slide whose shape tree is `shapes`, as slide 1 of 2
+    fn slide_body(
+        shapes: &str,
+        relationships: HashMap<String, Relationship>,
+        images: HashMap<String, Vec<u8>>,
+        parts: &[(&str, &str)],
+    ) -> String {
+        let data = archive(parts);
+        let mut package = Package::open(&data).unwrap();
+        let slide_numbers = HashMap::from([("ppt/slides/slide2.xml".to_string(), 2)]);
+        let (skipped, targets) = (HashSet::new(), RefCell::new(HashSet::new()));
+        let links = Links {
+            relationships: &relationships,
+            slide_numbers: &slide_numbers,
+            current_slide: 1,
+            slide_count: 2,
+            skipped: &skipped,
+            targets: &targets,
+        };
+        let xml = format!("<p:sld {}><p:cSld><p:spTree>{}</p:spTree></p:cSld></p:sld>", NS, shapes);
+        parse_slide_content(&xml, &mut package, &links, &SlideStyles::default(), &images)
+            .unwrap()
+            .body
+    }
+
+    fn picture(id: u32, description: &str, blip: &str, y: i64) -> String {
+        format!(
+            r#"<p:pic><p:nvPicPr><p:cNvPr id="{}" name="Picture" descr="{}"/></p:nvPicPr>
+            <p:blipFill>{}</p:blipFill><p:spPr><a:xfrm><a:off x="0" y="{}"/></a:xfrm></p:spPr></p:pic>"#,
+            id, description, blip, y
+        )
+    }
+
+    #[test]
+    fn test_pictures_resolve_through_slide_relationships() {
+        let shapes = [
+            picture(2, "A dot", r#"<a:blip r:embed="rId2"/>"#, 0),
+            picture(3, "", r#"<a:blip r:embed="rId9"/>"#, 100),
+            picture(4, "Logo", r#"<a:blip r:link="rId3"/>"#, 200),
+        ]
+        .concat();
+        let relationships = HashMap::from([
+            relationship("rId2", "image", "ppt/media/image1.gif"),
+            relationship("rId3", "image", "https://example.com/logo.png"),
+        ]);
+        let images = HashMap::from([("ppt/media/image1.gif".to_string(), GIF.to_vec())]);
+        let body = slide_body(&shapes, relationships, images, &[]);
+
+        let lines: Vec<&str> = body.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines.len(), 3, "{}", body);
+        assert!(lines[0].starts_with("![A dot](data:image/gif;base64,"), "{}", lines[0]);
+        assert_eq!(lines[1], "![Image not found](rId9)");
+        assert_eq!(lines[2], "![Logo](https://example.com/logo.png)");
+    }
+
+    #[test]
+    fn test_svg_picture_prefers_raster_fallback() {
+        let blip = r#"<a:blip r:embed="rId2"><a:extLst><a:ext>
+            <asvg:svgBlip r:embed="rId3"/></a:ext></a:extLst></a:blip>"#;
+        let relationships = HashMap::from([
+            relationship("rId2", "image", "ppt/media/image1.gif"),
+            relationship("rId3", "image", "ppt/media/image2.svg"),
+        ]);
+        let svg_only = HashMap::from([("ppt/media/image2.svg".to_string(), b"<svg/>".to_vec())]);
+        let body = slide_body(&picture(2, "Icon", blip, 0), relationships.clone(), svg_only, &[]);
+        assert!(body.starts_with("![Icon](data:image/svg+xml;base64,"), "{}", body);
+
+        let both = HashMap::from([
+            ("ppt/media/image1.gif".to_string(), GIF.to_vec()),
+            ("ppt/media/image2.svg".to_string(), b"<svg/>".to_vec()),
+        ]);
+        let body = slide_body(&picture(2, "Icon", blip, 0), relationships, both, &[]);
+        assert!(body.starts_with("![Icon](data:image/gif;base64,"), "{}", body);
+    }
+
+    fn text_shape(y: i64, body: &str) -> String {
+        format!(
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Text"/><p:nvPr/></p:nvSpPr>
+            <p:spPr><a:xfrm><a:off x="0" y="{}"/></a:xfrm></p:spPr><p:txBody>{}</p:txBody></p:sp>"#,
+            y, body
+        )
+    }
+
+    #[test]
+    fn test_nested_lists_follow_paragraph_levels() {
+        let body = concat!(
+            r#"<a:lstStyle><a:lvl1pPr><a:buChar char="*"/></a:lvl1pPr>"#,
+            r#"<a:lvl2pPr><a:buAutoNum type="arabicPeriod" startAt="4"/></a:lvl2pPr></a:lstStyle>"#,
+            r#"<a:p><a:pPr><a:buNone/></a:pPr><a:r><a:t>Intro</a:t></a:r></a:p>"#,
+            r#"<a:p><a:r><a:t>Fruit</a:t></a:r></a:p>"#,
+            r#"<a:p><a:pPr lvl="1"/><a:r><a:t>Apple</a:t></a:r></a:p>"#,
+            r#"<a:p><a:pPr lvl="1"/><a:r><a:t>Pear</a:t></a:r></a:p>"#,
+            r#"<a:p><a:r><a:t>Nuts</a:t></a:r></a:p>"#,
+            r#"<a:p><a:pPr><a:buNone/></a:pPr><a:r><a:t>Done</a:t></a:r></a:p>"#,
+        );
+        let markdown = slide_body(&text_shape(0, body), HashMap::new(), HashMap::new(), &[]);
+        assert_eq!(markdown.trim(), "Intro\n\n- Fruit\n    4. Apple\n    5. Pear\n- Nuts\n\nDone");
+    }
+
+    /// Output of `extract_table` for an `a:tbl` whose rows are `rows`
+    fn table(properties: &str, rows: &[&[&str]]) -> String {
+        let rows: String = rows
+            .iter()
+            .map(|cells| format!("<a:tr h=\"370840\">{}</a:tr>", cells.concat()))
+            .collect();
+        let xml = format!("<a:tbl>{}<a:tblGrid/>{}</a:tbl>", properties, rows);
+        let mut reader = Reader::from_str(&xml);
+        reader.read_event().unwrap();
+        extract_table(&mut reader).unwrap()
+    }
+
+    /// An `a:tc` with one paragraph per line
+    fn cell(attributes: &str, lines: &[&str]) -> String {
+        let paragraphs: String = lines
+            .iter()
+            .map(|line| format!("<a:p><a:r><a:t>{}</a:t></a:r></a:p>", line))
+            .collect();
+        format!("<a:tc{}><a:txBody><a:bodyPr/>{}</a:txBody><a:tcPr/></a:tc>", attributes, paragraphs)
+    }
+
+    #[test]
+    fn test_table_with_header_row() {
+        let (name, size) = (cell("", &["Name"]), cell("", &["Size"]));
+        let (a, b) = (cell("", &["a|b"]), cell("", &["1", "2"]));
+        let markdown = table(r#"<a:tblPr firstRow="1" bandRow="1"/>"#, &[&[&name, &size], &[&a, &b]]);
+        assert_eq!(markdown, "| Name | Size |\n|---|---|\n| a\\|b | 1<br>2 |\n");
+    }
+
+    #[test]
+    fn test_table_without_header_row_gets_an_empty_one() {
+        let (a, b, c) = (cell("", &["a"]), cell("", &["b"]), cell("", &["c"]));
+        let markdown = table("<a:tblPr/>", &[&[&a, &b], &[&c]]);
+        assert_eq!(markdown, "|  |  |\n|---|---|\n| a | b |\n| c |  |\n");
+    }
+
+    #[test]
+    fn test_merged_cells_fall_back_to_html() {
+        let header = cell(r#" gridSpan="2""#, &["Q1 &amp; Q2"]);
+        let covered = r#"<a:tc hMerge="1"/>"#.to_string();
+        let region = cell(r#" rowSpan="2""#, &["&lt;North&gt;"]);
+        let (first, second) = (cell("", &["1"]), cell("", &["2"]));
+        let below = cell(r#" vMerge="1""#, &[]);
+        let html = table(
+            r#"<a:tblPr firstRow="1"/>"#,
+            &[&[&header, &covered], &[&region, &first], &[&below, &second]],
+        );
+        assert_eq!(
+            html,
+            concat!(
+                "<table>\n",
+                "  <tr><th colspan=\"2\">Q1 &amp; Q2</th></tr>\n",
+                "  <tr><td rowspan=\"2\">&lt;North&gt;</td><td>1</td></tr>\n",
+                "  <tr><td>2</td></tr>\n",
+                "</table>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_table_in_graphic_frame() {
+        let frame = format!(
+            concat!(
+                r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="4" name="Table"/></p:nvGraphicFramePr>"#,
+                r#"<p:xfrm><a:off x="0" y="0"/></p:xfrm><a:graphic><a:graphicData><a:tbl><a:tblPr firstRow="1"/>"#,
+                r#"<a:tr>{}</a:tr><a:tr>{}</a:tr></a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#
+            ),
+            cell("", &["Key"]),
+            cell("", &["Value"])
+        );
+        let body = slide_body(&frame, HashMap::new(), HashMap::new(), &[]);
+        assert_eq!(body.trim(), "| Key |\n|---|\n| Value |");
+    }
+}
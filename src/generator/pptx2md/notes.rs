@@ -0,0 +1,105 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+use super::attribute;
+use super::package::Relationship;
+
+/// Part name of the notes slide linked from a slide's relationships
+pub fn notes_part(relationships: &HashMap<String, Relationship>) -> Option<&str> {
+    relationships
+        .values()
+        .find(|r| r.has_type("notesSlide") && !r.external)
+        .map(|r| r.target.as_str())
+}
+
+/// Speaker notes of a notes slide, one entry per non-empty paragraph.
+/// Only the body placeholder is read; the slide thumbnail, slide number
+/// and header/footer placeholders are skipped.
+pub fn extract_notes(xml_content: &str) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(xml_content);
+    let mut paragraphs = Vec::new();
+    let mut current_paragraph = String::new();
+    let mut in_body = false;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => match element.name().as_ref() {
+                b"p:sp" => in_body = false,
+                b"p:ph" => in_body = is_body_placeholder(&element),
+                b"a:t" => in_text = in_body,
+                _ => {}
+            },
+            Ok(Event::Empty(element)) => match element.name().as_ref() {
+                b"p:ph" => in_body = is_body_placeholder(&element),
+                b"a:br" if in_body => current_paragraph.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Text(text)) if in_text => {
+                current_paragraph.push_str(&text.unescape().unwrap_or_default());
+            }
+            Ok(Event::End(element)) => match element.name().as_ref() {
+                b"a:t" => in_text = false,
+                b"a:p" => {
+                    if !current_paragraph.trim().is_empty() {
+                        paragraphs.push(current_paragraph.trim().to_string());
+                    }
+                    current_paragraph.clear();
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing notes slide XML: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(paragraphs)
+}
+
+fn is_body_placeholder(element: &quick_xml::events::BytesStart) -> bool {
+    attribute(element, b"type").as_deref() == Some("body")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(placeholder: &str, paragraphs: &str) -> String {
+        format!(
+            "<p:sp><p:nvSpPr><p:nvPr>{}</p:nvPr></p:nvSpPr><p:txBody>{}</p:txBody></p:sp>",
+            placeholder, paragraphs
+        )
+    }
+
+    #[test]
+    fn test_notes_come_from_the_body_placeholder() {
+        let xml = format!(
+            "<p:notes><p:cSld><p:spTree>{}{}{}</p:spTree></p:cSld></p:notes>",
+            shape(r#"<p:ph type="sldImg"/>"#, ""),
+            shape(
+                r#"<p:ph type="body" idx="1"/>"#,
+                "<a:p><a:r><a:t>Say </a:t></a:r><a:r><a:t>hello</a:t></a:r></a:p>\
+                 <a:p></a:p><a:p><a:r><a:t>First</a:t></a:r><a:br/><a:r><a:t>second</a:t></a:r></a:p>"
+            ),
+            shape(r#"<p:ph type="sldNum"/>"#, "<a:p><a:r><a:t>3</a:t></a:r></a:p>"),
+        );
+        assert_eq!(extract_notes(&xml).unwrap(), vec!["Say hello", "First\nsecond"]);
+    }
+
+    #[test]
+    fn test_notes_part_from_relationships() {
+        let relationship = |rel_type: &str, target: &str| Relationship {
+            rel_type: format!("http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}", rel_type),
+            target: target.to_string(),
+            external: false,
+        };
+        let relationships = HashMap::from([
+            ("rId1".to_string(), relationship("slideLayout", "ppt/slideLayouts/slideLayout1.xml")),
+            ("rId2".to_string(), relationship("notesSlide", "ppt/notesSlides/notesSlide4.xml")),
+        ]);
+        assert_eq!(notes_part(&relationships), Some("ppt/notesSlides/notesSlide4.xml"));
+        assert_eq!(notes_part(&HashMap::new()), None);
+    }
+}
@@ -0,0 +1,181 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+/// A relationship from a `_rels/*.rels` part, with its target resolved to a part name
+#[derive(Debug, Clone)]
+pub struct Relationship {
+    pub rel_type: String,
+    pub target: String,
+    pub external: bool,
+}
+
+impl Relationship {
+    /// Whether the relationship type URI ends in `/kind` (`slide`, `notesSlide`, ...), so
+    /// that transitional and strict (`http://purl.oclc.org/ooxml/...`) packages both match
+    pub fn has_type(&self, kind: &str) -> bool {
+        self.rel_type.rsplit('/').next() == Some(kind)
+    }
+}
+
+/// Read access to the parts of an OPC (Office Open XML) package
+pub struct Package<'a> {
+    archive: ZipArchive<Cursor<&'a [u8]>>,
+}
+
+impl<'a> Package<'a> {
+    pub fn open(file_stream: &'a [u8]) -> Result<Package<'a>, String> {
+        let archive = ZipArchive::new(Cursor::new(file_stream))
+            .map_err(|e| format!("Failed to open PPTX archive: {}", e))?;
+        Ok(Package { archive })
+    }
+
+    pub fn part_names(&self) -> Vec<String> {
+        self.archive.file_names().map(|name| name.to_string()).collect()
+    }
+
+    /// Raw bytes of a part, `None` if the package has no such part
+    pub fn read(&mut self, name: &str) -> Result<Option<Vec<u8>>, String> {
+        let mut file = match self.archive.by_name(name) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(format!("Failed to access {} in PPTX archive: {}", name, e)),
+        };
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        Ok(Some(data))
+    }
+
+    pub fn read_string(&mut self, name: &str) -> Result<Option<String>, String> {
+        match self.read(name)? {
+            Some(data) => String::from_utf8(data)
+                .map(Some)
+                .map_err(|e| format!("{} is not valid UTF-8: {}", name, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Relationships of `part`, keyed by `r:id`. Empty if the part has no rels.
+    pub fn relationships(&mut self, part: &str) -> Result<HashMap<String, Relationship>, String> {
+        let mut relationships = HashMap::new();
+        let Some(xml) = self.read_string(&rels_path(part))? else {
+            return Ok(relationships);
+        };
+
+        let mut reader = Reader::from_str(&xml);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(element)) | Ok(Event::Empty(element))
+                    if element.local_name().as_ref() == b"Relationship" =>
+                {
+                    let mut id = String::new();
+                    let mut rel_type = String::new();
+                    let mut target = String::new();
+                    let mut external = false;
+                    for attr in element.attributes().flatten() {
+                        let value = attr.unescape_value().unwrap_or_default().to_string();
+                        match attr.key.as_ref() {
+                            b"Id" => id = value,
+                            b"Type" => rel_type = value,
+                            b"Target" => target = value,
+                            b"TargetMode" => external = value == "External",
+                            _ => {}
+                        }
+                    }
+                    if !external {
+                        target = resolve_target(part, &target);
+                    }
+                    relationships.insert(id, Relationship { rel_type, target, external });
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(format!("Error parsing relationships of {}: {}", part, e)),
+                _ => {}
+            }
+        }
+
+        Ok(relationships)
+    }
+}
+
+/// `ppt/slides/slide1.xml` -> `ppt/slides/_rels/slide1.xml.rels`
+fn rels_path(part: &str) -> String {
+    match part.rsplit_once('/') {
+        Some((dir, file)) => format!("{}/_rels/{}.rels", dir, file),
+        None => format!("_rels/{}.rels", part),
+    }
+}
+
+/// Resolves a relationship target relative to the directory of its source part
+fn resolve_target(part: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+
+    let mut segments: Vec<&str> = part.split('/').collect();
+    segments.pop();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::{write::FileOptions, ZipWriter};
+
+    /// A zip archive holding `parts` (name, content)
+    pub fn archive(parts: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn relationship(rel_type: &str) -> Relationship {
+        Relationship {
+            rel_type: rel_type.to_string(),
+            target: String::new(),
+            external: false,
+        }
+    }
+
+    #[test]
+    fn test_relationship_type_in_either_namespace() {
+        let transitional = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide";
+        let strict = "http://purl.oclc.org/ooxml/officeDocument/relationships/slide";
+        assert!(relationship(transitional).has_type("slide"));
+        assert!(relationship(strict).has_type("slide"));
+        assert!(!relationship(&format!("{}Layout", strict)).has_type("slide"));
+    }
+
+    #[test]
+    fn test_relationships_resolve_targets() {
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Type="t/slideLayout" Target="../slideLayouts/slideLayout2.xml"/>
+            <Relationship Id="rId2" Type="t/hyperlink" Target="https://example.com" TargetMode="External"/>
+            <Relationship Id="rId3" Type="t/image" Target="/ppt/media/image1.png"/>
+        </Relationships>"#;
+        let data = archive(&[("ppt/slides/_rels/slide1.xml.rels", rels)]);
+        let mut package = Package::open(&data).unwrap();
+        let relationships = package.relationships("ppt/slides/slide1.xml").unwrap();
+
+        assert_eq!(relationships["rId1"].target, "ppt/slideLayouts/slideLayout2.xml");
+        assert_eq!(relationships["rId2"].target, "https://example.com");
+        assert!(relationships["rId2"].external);
+        assert_eq!(relationships["rId3"].target, "ppt/media/image1.png");
+        assert!(package.relationships("ppt/slides/slide2.xml").unwrap().is_empty());
+    }
+}
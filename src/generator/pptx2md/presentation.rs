@@ -0,0 +1,196 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+use super::attribute;
+use super::package::Package;
+
+/// A slide in presentation order
+#[derive(Debug, Clone)]
+pub struct SlideRef {
+    /// 1-based position, as shown in PowerPoint
+    pub number: usize,
+    pub part: String,
+    pub section: Option<String>,
+    /// Hidden in the slide show (`<p:sld show="0">`)
+    pub hidden: bool,
+}
+
+/// Slides in the order of `p:sldIdLst` in `ppt/presentation.xml`, with the
+/// `p14:sectionLst` section each belongs to. Falls back to the numeric order of
+/// `ppt/slides/slideN.xml` when the presentation part is missing or lists no slides.
+pub fn slides(package: &mut Package) -> Result<Vec<SlideRef>, String> {
+    let mut slides = match package.read_string("ppt/presentation.xml")? {
+        Some(xml) => slides_in_presentation(package, &xml)?,
+        None => Vec::new(),
+    };
+    if slides.is_empty() {
+        slides = slides_by_part_name(package);
+    }
+    for slide in &mut slides {
+        slide.hidden = is_hidden(package, &slide.part)?;
+    }
+    Ok(slides)
+}
+
+fn slides_in_presentation(package: &mut Package, xml: &str) -> Result<Vec<SlideRef>, String> {
+    let relationships = package.relationships("ppt/presentation.xml")?;
+
+    let mut reader = Reader::from_str(xml);
+    // (slide id, r:id) in p:sldIdLst order
+    let mut slide_ids: Vec<(String, String)> = Vec::new();
+    let mut sections: HashMap<String, String> = HashMap::new();
+    let mut current_section: Option<String> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element)) => {
+                match element.name().as_ref() {
+                    b"p:sldId" => {
+                        let id = attribute(&element, b"id").unwrap_or_default();
+                        if let Some(rel_id) = attribute(&element, b"r:id") {
+                            slide_ids.push((id, rel_id));
+                        }
+                    }
+                    b"p14:section" => current_section = attribute(&element, b"name"),
+                    b"p14:sldId" => {
+                        let id = attribute(&element, b"id");
+                        if let (Some(id), Some(section)) = (id, &current_section) {
+                            sections.insert(id, section.clone());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::End(element)) if element.name().as_ref() == b"p14:section" => {
+                current_section = None;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing presentation XML: {}", e)),
+            _ => {}
+        }
+    }
+
+    let slides = slide_ids
+        .into_iter()
+        .filter_map(|(id, rel_id)| {
+            let relationship = relationships.get(&rel_id)?;
+            (relationship.has_type("slide") && !relationship.external)
+                .then(|| (relationship.target.clone(), sections.get(&id).cloned()))
+        })
+        .enumerate()
+        .map(|(i, (part, section))| SlideRef {
+            number: i + 1,
+            part,
+            section,
+            hidden: false,
+        })
+        .collect();
+
+    Ok(slides)
+}
+
+fn slides_by_part_name(package: &Package) -> Vec<SlideRef> {
+    let mut parts: Vec<(usize, String)> = package
+        .part_names()
+        .into_iter()
+        .filter_map(|name| {
+            let number = name
+                .strip_prefix("ppt/slides/slide")?
+                .strip_suffix(".xml")?
+                .parse::<usize>()
+                .ok()?;
+            Some((number, name))
+        })
+        .collect();
+    parts.sort();
+
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, part))| SlideRef {
+            number: i + 1,
+            part,
+            section: None,
+            hidden: false,
+        })
+        .collect()
+}
+
+/// Whether the slide part's root is a `<p:sld show="0">`, read without parsing the slide
+fn is_hidden(package: &mut Package, part: &str) -> Result<bool, String> {
+    let Some(xml) = package.read_string(part)? else {
+        return Ok(false);
+    };
+    let mut reader = Reader::from_str(&xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element)) => {
+                let show = attribute(&element, b"show");
+                return Ok(element.name().as_ref() == b"p:sld"
+                    && show.is_some_and(|show| show == "0" || show == "false"));
+            }
+            Ok(Event::Eof) => return Ok(false),
+            Err(e) => return Err(format!("Error parsing slide {}: {}", part, e)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pptx2md::package::tests::archive;
+
+    const STRICT: &str = "http://purl.oclc.org/ooxml/officeDocument/relationships/slide";
+
+    fn slide(show: &str) -> String {
+        format!(r#"<p:sld xmlns:p="p"{}><p:cSld/></p:sld>"#, show)
+    }
+
+    #[test]
+    fn test_strict_slides_in_presentation_order() {
+        let presentation = r#"<p:presentation xmlns:p="p" xmlns:r="r" xmlns:p14="p14"><p:sldIdLst>
+            <p:sldId id="256" r:id="rId2"/><p:sldId id="257" r:id="rId1"/>
+            </p:sldIdLst><p:extLst><p:ext><p14:sectionLst>
+            <p14:section name="Intro"><p14:sldIdLst><p14:sldId id="256"/></p14:sldIdLst></p14:section>
+            </p14:sectionLst></p:ext></p:extLst></p:presentation>"#;
+        let rels = format!(
+            r#"<Relationships><Relationship Id="rId1" Type="{0}" Target="slides/slide1.xml"/>
+            <Relationship Id="rId2" Type="{0}" Target="slides/slide2.xml"/></Relationships>"#,
+            STRICT
+        );
+        let data = archive(&[
+            ("ppt/presentation.xml", presentation),
+            ("ppt/_rels/presentation.xml.rels", &rels),
+            ("ppt/slides/slide1.xml", &slide(r#" show="0""#)),
+            ("ppt/slides/slide2.xml", &slide("")),
+        ]);
+        let slides = slides(&mut Package::open(&data).unwrap()).unwrap();
+
+        let summary: Vec<_> = slides
+            .iter()
+            .map(|s| (s.number, s.part.as_str(), s.section.as_deref(), s.hidden))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "ppt/slides/slide2.xml", Some("Intro"), false),
+                (2, "ppt/slides/slide1.xml", None, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slides_by_part_name_without_presentation() {
+        let data = archive(&[
+            ("ppt/slides/slide10.xml", &slide("")),
+            ("ppt/slides/slide2.xml", &slide(r#" show="false""#)),
+            ("ppt/slides/_rels/slide2.xml.rels", "<Relationships/>"),
+        ]);
+        let slides = slides(&mut Package::open(&data).unwrap()).unwrap();
+
+        let summary: Vec<_> = slides.iter().map(|s| (s.number, s.part.as_str(), s.hidden)).collect();
+        assert_eq!(summary, vec![(1, "ppt/slides/slide2.xml", true), (2, "ppt/slides/slide10.xml", false)]);
+    }
+}
@@ -0,0 +1,320 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use super::attribute;
+use super::package::Relationship;
+
+/// Typefaces rendered as inline code
+const CODE_FONTS: [&str; 9] = [
+    "consolas",
+    "courier",
+    "courier new",
+    "lucida console",
+    "menlo",
+    "monaco",
+    "source code pro",
+    "fira code",
+    "cascadia code",
+];
+
+/// What click actions of one slide resolve against
+pub struct Links<'a> {
+    pub relationships: &'a HashMap<String, Relationship>,
+    /// slide part name -> slide number
+    pub slide_numbers: &'a HashMap<String, usize>,
+    pub current_slide: usize,
+    pub slide_count: usize,
+    /// Numbers of the slides left out of the output, whose links are dropped
+    pub skipped: &'a HashSet<usize>,
+    /// Numbers of the slides the resolved links jump to, shared by all slides
+    pub targets: &'a RefCell<HashSet<usize>>,
+}
+
+impl Links<'_> {
+    /// Target of an `a:hlinkClick`: the URL of an external link, or the anchor of a
+    /// slide for slide jumps (`ppaction://hlinksldjump` and next/previous/first/last)
+    fn resolve(&self, element: &BytesStart) -> Option<String> {
+        let action = attribute(element, b"action").unwrap_or_default();
+        if let Some(jump) = action.strip_prefix("ppaction://hlinkshowjump?jump=") {
+            let number = match jump {
+                "firstslide" => 1,
+                "lastslide" => self.slide_count,
+                "nextslide" => self.current_slide + 1,
+                "previousslide" => self.current_slide.saturating_sub(1),
+                _ => return None,
+            };
+            if !(1..=self.slide_count).contains(&number) {
+                return None;
+            }
+            return self.slide_anchor(number);
+        }
+
+        let relationship = self.relationships.get(&attribute(element, b"r:id")?)?;
+        if relationship.external {
+            return Some(relationship.target.clone()).filter(|t| !t.is_empty());
+        }
+        if relationship.has_type("slide") {
+            let number = self.slide_numbers.get(&relationship.target)?;
+            return self.slide_anchor(*number);
+        }
+        None
+    }
+
+    /// Link to the anchor of slide `number`, noting that the slide needs one;
+    /// `None` for a skipped slide
+    fn slide_anchor(&self, number: usize) -> Option<String> {
+        if self.skipped.contains(&number) {
+            return None;
+        }
+        self.targets.borrow_mut().insert(number);
+        Some(format!("#{}", slide_anchor_id(number)))
+    }
+}
+
+/// Id of the anchor written in front of the heading of a linked slide
+pub fn slide_anchor_id(number: usize) -> String {
+    format!("slide-{}", number)
+}
+
+/// Character formatting of an `a:r`, from its `a:rPr`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunFormat {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+impl RunFormat {
+    /// Reads an `a:rPr`; `is_empty` is true for a self-closing element,
+    /// otherwise its children are consumed up to `</a:rPr>`
+    pub fn read(
+        reader: &mut Reader<&[u8]>,
+        element: &BytesStart,
+        is_empty: bool,
+        links: &Links,
+    ) -> Result<RunFormat, String> {
+        let flag = |name| attribute(element, name).is_some_and(|v| v == "1" || v == "true");
+        let mut format = RunFormat {
+            bold: flag(b"b"),
+            italic: flag(b"i"),
+            underline: attribute(element, b"u").is_some_and(|u| u != "none"),
+            strike: attribute(element, b"strike").is_some_and(|s| s != "noStrike"),
+            ..RunFormat::default()
+        };
+        if is_empty {
+            return Ok(format);
+        }
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(child)) | Ok(Event::Empty(child)) => match child.name().as_ref() {
+                    b"a:latin" => {
+                        format.code = attribute(&child, b"typeface").is_some_and(|t| is_code_font(&t));
+                    }
+                    b"a:hlinkClick" => format.link = links.resolve(&child),
+                    _ => {}
+                },
+                Ok(Event::End(child)) if child.name().as_ref() == b"a:rPr" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(format!("Error parsing run properties: {}", e)),
+                _ => {}
+            }
+        }
+
+        Ok(format)
+    }
+}
+
+fn is_code_font(typeface: &str) -> bool {
+    let typeface = typeface.trim().to_lowercase();
+    CODE_FONTS.contains(&typeface.as_str()) || typeface.contains(" mono")
+}
+
+/// A run of text and its formatting
+#[derive(Debug, Clone)]
+pub struct TextRun {
+    pub format: RunFormat,
+    pub text: String,
+}
+
+/// Renders runs as inline Markdown, merging neighbours with the same formatting
+/// so a word split over several runs gets one pair of markers
+pub fn format_runs(runs: &[TextRun]) -> String {
+    let mut merged: Vec<TextRun> = Vec::new();
+    for run in runs {
+        match merged.last_mut() {
+            Some(last) if last.format == run.format => last.text.push_str(&run.text),
+            _ => merged.push(run.clone()),
+        }
+    }
+
+    merged.iter().map(format_run).collect()
+}
+
+fn format_run(run: &TextRun) -> String {
+    // emphasis markers must hug the text, so surrounding whitespace goes outside them
+    let core = run.text.trim();
+    if core.is_empty() {
+        return run.text.clone();
+    }
+    let start = run.text.find(core).unwrap_or(0);
+    let leading = &run.text[..start];
+    let trailing = &run.text[start + core.len()..];
+
+    let format = &run.format;
+    let mut text = if format.code {
+        let fence = if core.contains('`') { "``" } else { "`" };
+        format!("{}{}{}", fence, core, fence)
+    } else {
+        core.to_string()
+    };
+    if format.strike {
+        text = format!("~~{}~~", text);
+    }
+    if format.italic {
+        text = format!("*{}*", text);
+    }
+    if format.bold {
+        text = format!("**{}**", text);
+    }
+    if format.underline && format.link.is_none() {
+        text = format!("<u>{}</u>", text);
+    }
+    if let Some(link) = &format.link {
+        if link.contains([' ', '(', ')']) {
+            text = format!("[{}](<{}>)", text, link);
+        } else {
+            text = format!("[{}]({})", text, link);
+        }
+    }
+
+    format!("{}{}{}", leading, text, trailing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads the `a:rPr` in `xml` on slide 2 of 4, whose `rId1` links to a website,
+    /// `rId2` to slide 3 and `rId3` to slide 4, a skipped slide
+    fn read_format(xml: &str, skipped: &[usize]) -> (RunFormat, HashSet<usize>) {
+        let relationship = |rel_type: &str, target: &str, external| Relationship {
+            rel_type: format!("http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}", rel_type),
+            target: target.to_string(),
+            external,
+        };
+        let relationships = HashMap::from([
+            ("rId1".to_string(), relationship("hyperlink", "https://example.com/a b", true)),
+            ("rId2".to_string(), relationship("slide", "ppt/slides/slide3.xml", false)),
+            ("rId3".to_string(), relationship("slide", "ppt/slides/slide4.xml", false)),
+        ]);
+        let slide_numbers = HashMap::from([
+            ("ppt/slides/slide3.xml".to_string(), 3),
+            ("ppt/slides/slide4.xml".to_string(), 4),
+        ]);
+        let skipped: HashSet<usize> = skipped.iter().copied().collect();
+        let targets = RefCell::new(HashSet::new());
+        let links = Links {
+            relationships: &relationships,
+            slide_numbers: &slide_numbers,
+            current_slide: 2,
+            slide_count: 4,
+            skipped: &skipped,
+            targets: &targets,
+        };
+
+        let mut reader = Reader::from_str(xml);
+        let format = match reader.read_event().unwrap() {
+            Event::Start(element) => RunFormat::read(&mut reader, &element, false, &links),
+            Event::Empty(element) => RunFormat::read(&mut reader, &element, true, &links),
+            event => panic!("unexpected {:?}", event),
+        };
+        (format.unwrap(), targets.into_inner())
+    }
+
+    fn link(xml: &str) -> Option<String> {
+        read_format(&format!("<a:rPr>{}</a:rPr>", xml), &[]).0.link
+    }
+
+    fn run(format: RunFormat, text: &str) -> TextRun {
+        TextRun { format, text: text.to_string() }
+    }
+
+    #[test]
+    fn test_read_character_formatting() {
+        let (format, _) = read_format(r#"<a:rPr b="1" i="true" u="sng" strike="sngStrike"/>"#, &[]);
+        assert!(format.bold && format.italic && format.underline && format.strike && !format.code);
+
+        let (format, _) = read_format(r#"<a:rPr b="0" u="none" strike="noStrike"/>"#, &[]);
+        assert_eq!(format, RunFormat::default());
+
+        let (format, _) = read_format(r#"<a:rPr><a:latin typeface="JetBrains Mono"/></a:rPr>"#, &[]);
+        assert!(format.code);
+        let (format, _) = read_format(r#"<a:rPr><a:latin typeface="Calibri"/></a:rPr>"#, &[]);
+        assert!(!format.code);
+    }
+
+    #[test]
+    fn test_links_resolve_urls_and_slide_jumps() {
+        assert_eq!(link(r#"<a:hlinkClick r:id="rId1"/>"#).as_deref(), Some("https://example.com/a b"));
+        let slide = r#"<a:hlinkClick r:id="rId2" action="ppaction://hlinksldjump"/>"#;
+        assert_eq!(link(slide).as_deref(), Some("#slide-3"));
+        assert_eq!(link(r#"<a:hlinkClick r:id="rId9"/>"#), None);
+
+        let jump = |to: &str| {
+            link(&format!(r#"<a:hlinkClick r:id="" action="ppaction://hlinkshowjump?jump={}"/>"#, to))
+        };
+        assert_eq!(jump("firstslide").as_deref(), Some("#slide-1"));
+        assert_eq!(jump("previousslide").as_deref(), Some("#slide-1"));
+        assert_eq!(jump("nextslide").as_deref(), Some("#slide-3"));
+        assert_eq!(jump("lastslide").as_deref(), Some("#slide-4"));
+        assert_eq!(jump("endshow"), None);
+    }
+
+    #[test]
+    fn test_slide_links_record_targets_and_skip_hidden_slides() {
+        let xml = r#"<a:rPr><a:hlinkClick r:id="rId2" action="ppaction://hlinksldjump"/></a:rPr>"#;
+        let (format, targets) = read_format(xml, &[]);
+        assert_eq!(format.link.as_deref(), Some("#slide-3"));
+        assert_eq!(targets, HashSet::from([3]));
+
+        let xml = r#"<a:rPr><a:hlinkClick r:id="rId3" action="ppaction://hlinksldjump"/></a:rPr>"#;
+        let (format, targets) = read_format(xml, &[4]);
+        assert_eq!(format.link, None);
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn test_format_runs_merges_neighbours() {
+        let bold = RunFormat { bold: true, ..RunFormat::default() };
+        let runs = [
+            run(RunFormat::default(), "A "),
+            run(bold.clone(), "spl"),
+            run(bold, "it word "),
+            run(RunFormat { italic: true, strike: true, ..RunFormat::default() }, "gone"),
+        ];
+        assert_eq!(format_runs(&runs), "A **split word** *~~gone~~*");
+    }
+
+    #[test]
+    fn test_format_run_code_underline_and_links() {
+        let code = RunFormat { code: true, ..RunFormat::default() };
+        assert_eq!(format_runs(&[run(code.clone(), " x = 1 ")]), " `x = 1` ");
+        assert_eq!(format_runs(&[run(code, "a`b")]), "``a`b``");
+
+        let underline = RunFormat { underline: true, ..RunFormat::default() };
+        assert_eq!(format_runs(&[run(underline.clone(), "note")]), "<u>note</u>");
+
+        let linked = |link: &str| RunFormat { link: Some(link.to_string()), ..underline.clone() };
+        assert_eq!(format_runs(&[run(linked("#slide-3"), "next")]), "[next](#slide-3)");
+        assert_eq!(
+            format_runs(&[run(linked("https://example.com/a (b)"), "site")]),
+            "[site](<https://example.com/a (b)>)"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+use super::attribute;
+use super::package::Package;
+
+/// A `dgm:pt` of a SmartArt data model
+#[derive(Debug, Default)]
+struct Node {
+    kind: String,
+    text: String,
+}
+
+/// Renders the node text of a SmartArt data part (`ppt/diagrams/dataN.xml`) as a
+/// nested list following the parent-of connections from the document node
+pub fn smartart_to_markdown(package: &mut Package, data_part: &str) -> Result<String, String> {
+    let Some(xml) = package.read_string(data_part)? else {
+        return Ok(String::new());
+    };
+
+    let mut reader = Reader::from_str(&xml);
+    let mut nodes: HashMap<String, Node> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    // parent id -> (source order, child id)
+    let mut children: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+    let mut current: Option<String> = None;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element)) => match element.name().as_ref() {
+                b"dgm:pt" => {
+                    let id = attribute(&element, b"modelId").unwrap_or_default();
+                    let kind = attribute(&element, b"type").unwrap_or_else(|| "node".to_string());
+                    nodes.insert(id.clone(), Node { kind, text: String::new() });
+                    order.push(id.clone());
+                    current = Some(id);
+                }
+                b"dgm:cxn" => {
+                    let kind = attribute(&element, b"type").unwrap_or_else(|| "parOf".to_string());
+                    let source = attribute(&element, b"srcId");
+                    let destination = attribute(&element, b"destId");
+                    if let (true, Some(source), Some(destination)) = (kind == "parOf", source, destination) {
+                        let position = attribute(&element, b"srcOrd")
+                            .and_then(|o| o.parse().ok())
+                            .unwrap_or(0);
+                        children.entry(source).or_default().push((position, destination));
+                    }
+                }
+                b"a:t" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Text(text)) if in_text => {
+                let node = current.as_ref().and_then(|id| nodes.get_mut(id));
+                if let Some(node) = node {
+                    node.text.push_str(&text.unescape().unwrap_or_default());
+                }
+            }
+            Ok(Event::End(element)) => match element.name().as_ref() {
+                b"a:t" => in_text = false,
+                b"a:p" => {
+                    let node = current.as_ref().and_then(|id| nodes.get_mut(id));
+                    if let Some(node) = node.filter(|n| !n.text.is_empty() && !n.text.ends_with(' ')) {
+                        node.text.push(' ');
+                    }
+                }
+                b"dgm:pt" => current = None,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Error parsing SmartArt data XML: {}", e)),
+            _ => {}
+        }
+    }
+
+    for list in children.values_mut() {
+        list.sort_by_key(|(position, _)| *position);
+    }
+
+    let mut markdown = String::new();
+    let roots = order.iter().filter(|id| nodes.get(*id).is_some_and(|n| n.kind == "doc"));
+    for root in roots {
+        write_children(root, 0, &nodes, &children, &mut markdown, &mut Vec::new());
+    }
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    Ok(markdown)
+}
+
+fn write_children(
+    parent: &str,
+    depth: usize,
+    nodes: &HashMap<String, Node>,
+    children: &HashMap<String, Vec<(usize, String)>>,
+    markdown: &mut String,
+    visited: &mut Vec<String>,
+) {
+    let Some(list) = children.get(parent) else {
+        return;
+    };
+    for (_, child) in list {
+        if visited.contains(child) {
+            continue;
+        }
+        visited.push(child.clone());
+
+        let text = nodes.get(child).map(|n| n.text.trim()).unwrap_or_default();
+        // nodes without text (e.g. picture placeholders) don't add a level
+        let child_depth = if text.is_empty() {
+            depth
+        } else {
+            markdown.push_str(&format!("{}- {}\n", "    ".repeat(depth), text));
+            depth + 1
+        };
+        write_children(child, child_depth, nodes, children, markdown, visited);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::pptx2md::package::tests::archive;
+
+    fn point(id: &str, kind: Option<&str>, paragraphs: &[&str]) -> String {
+        let kind = kind.map(|k| format!(r#" type="{}""#, k)).unwrap_or_default();
+        let text: String = paragraphs
+            .iter()
+            .map(|p| format!("<a:p><a:r><a:t>{}</a:t></a:r></a:p>", p))
+            .collect();
+        format!(r#"<dgm:pt modelId="{}"{}><dgm:t><a:bodyPr/>{}</dgm:t></dgm:pt>"#, id, kind, text)
+    }
+
+    fn connection(kind: Option<&str>, source: &str, destination: &str, order: usize) -> String {
+        let kind = kind.map(|k| format!(r#" type="{}""#, k)).unwrap_or_default();
+        format!(
+            r#"<dgm:cxn modelId="c{}{}"{} srcId="{}" destId="{}" srcOrd="{}"/>"#,
+            source, destination, kind, source, destination, order
+        )
+    }
+
+    fn render(points: &[String], connections: &[String]) -> String {
+        let xml = format!(
+            r#"<dgm:dataModel xmlns:dgm="dgm" xmlns:a="a"><dgm:ptLst>{}</dgm:ptLst><dgm:cxnLst>{}</dgm:cxnLst>
+            </dgm:dataModel>"#,
+            points.concat(),
+            connections.concat()
+        );
+        let data = archive(&[("ppt/diagrams/data1.xml", &xml)]);
+        let mut package = Package::open(&data).unwrap();
+        smartart_to_markdown(&mut package, "ppt/diagrams/data1.xml").unwrap()
+    }
+
+    #[test]
+    fn test_nodes_nest_by_parent_connections_in_source_order() {
+        let points = [
+            point("0", Some("doc"), &[]),
+            point("1", None, &["Plan"]),
+            point("2", None, &["Build"]),
+            point("3", None, &["Design", "and review"]),
+            point("4", Some("pres"), &["Shape text"]),
+        ];
+        let connections = [
+            connection(None, "0", "2", 1),
+            connection(None, "0", "1", 0),
+            connection(Some("parOf"), "1", "3", 0),
+            connection(Some("presOf"), "1", "4", 0),
+        ];
+        assert_eq!(
+            render(&points, &connections),
+            "- Plan\n    - Design and review\n- Build\n\n"
+        );
+    }
+
+    #[test]
+    fn test_nodes_without_text_add_no_level() {
+        let points = [
+            point("0", Some("doc"), &[]),
+            point("1", None, &[]),
+            point("2", None, &["Photo caption"]),
+        ];
+        let connections = [connection(None, "0", "1", 0), connection(None, "1", "2", 0)];
+        assert_eq!(render(&points, &connections), "- Photo caption\n\n");
+    }
+
+    #[test]
+    fn test_connection_cycles_are_written_once() {
+        let points = [point("0", Some("doc"), &[]), point("1", None, &["Loop"])];
+        let connections = [connection(None, "0", "1", 0), connection(None, "1", "1", 0)];
+        assert_eq!(render(&points, &connections), "- Loop\n\n");
+    }
+
+    #[test]
+    fn test_missing_data_part() {
+        let data = archive(&[]);
+        let mut package = Package::open(&data).unwrap();
+        assert_eq!(smartart_to_markdown(&mut package, "ppt/diagrams/data1.xml").unwrap(), "");
+    }
+}
@@ -3,6 +3,7 @@ pub mod config;
 pub mod generator;
 pub mod converter;
 use config::{SETTINGS};
+use generator::pdf2md::{PdfError, PdfPageMarkdown};
 
 pub struct ConverterFile {
     pub file_path: Option<String>,
@@ -177,6 +178,41 @@ pub fn convert_from_path(file_path: &str) -> Result<String, String> {
     convert(file)
 }
 
+// PDF -> markdown, with a typed error so that a missing or wrong password can be told apart
+pub fn convert_pdf_from_path(file_path: &str) -> Result<String, PdfError> {
+    let file_stream = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
+
+    let markdown = generator::pdf2md::run(&file_stream)?;
+
+    let is_ai_sweep = SETTINGS.read().unwrap().is_ai_sweep;
+    if is_ai_sweep {
+        Ok(ai_sweep(Ok(markdown))?)
+    } else {
+        Ok(markdown)
+    }
+}
+
+// PDF -> one markdown document per page
+pub fn convert_pdf_pages_from_path(file_path: &str) -> Result<Vec<PdfPageMarkdown>, PdfError> {
+    let file_stream = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
+
+    let pages = generator::pdf2md::run_pages(&file_stream)?;
+
+    let is_ai_sweep = SETTINGS.read().unwrap().is_ai_sweep;
+    if !is_ai_sweep {
+        return Ok(pages);
+    }
+    pages
+        .into_iter()
+        .map(|page| {
+            let markdown = ai_sweep(Ok(page.markdown))?;
+            Ok(PdfPageMarkdown { markdown, ..page })
+        })
+        .collect()
+}
+
 fn ai_sweep(markdown: Result<String, String>) -> Result<String, String> {
     let markdown_content = markdown?;
     